@@ -65,6 +65,7 @@ where
         &self,
         req: &SlackApiChatPostEphemeralRequest,
     ) -> ClientResult<SlackApiChatPostEphemeralResponse> {
+        self.client.validate_block_kit(|| req.content.validate())?;
         self.http_api.http_post("chat.postEphemeral", req).await
     }
 
@@ -75,6 +76,7 @@ where
         &self,
         req: &SlackApiChatPostMessageRequest,
     ) -> ClientResult<SlackApiChatPostMessageResponse> {
+        self.client.validate_block_kit(|| req.content.validate())?;
         self.http_api.http_post("chat.postMessage", req).await
     }
 
@@ -85,6 +87,7 @@ where
         &self,
         req: &SlackApiChatScheduleMessageRequest,
     ) -> ClientResult<SlackApiChatScheduleMessageResponse> {
+        self.client.validate_block_kit(|| req.content.validate())?;
         self.http_api.http_post("chat.scheduleMessage", req).await
     }
 
@@ -105,6 +108,7 @@ where
         &self,
         req: &SlackApiChatUpdateRequest,
     ) -> ClientResult<SlackApiChatUpdateResponse> {
+        self.client.validate_block_kit(|| req.content.validate())?;
         self.http_api.http_post("chat.update", req).await
     }
 
@@ -328,8 +332,7 @@ impl SlackApiScrollableResponse for SlackApiChatScheduledMessagesListResponse {
     fn next_cursor(&self) -> Option<&Self::CursorType> {
         self.response_metadata
             .as_ref()
-            .map(|rm| rm.next_cursor.as_ref())
-            .flatten()
+            .and_then(|rm| rm.next_cursor.as_ref())
     }
 
     fn scrollable_items<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self::ResponseItemType> + 'a> {
@@ -63,6 +63,10 @@ where
                     ("inclusive", req.inclusive.map(|v| v.to_string()).as_ref()),
                     ("latest", req.latest.as_ref().map(|x| x.value())),
                     ("oldest", req.oldest.as_ref().map(|x| x.value())),
+                    (
+                        "include_all_metadata",
+                        req.include_all_metadata.map(|v| v.to_string()).as_ref(),
+                    ),
                 ],
             )
             .await
@@ -240,6 +244,10 @@ where
                     ("inclusive", req.inclusive.map(|v| v.to_string()).as_ref()),
                     ("latest", req.latest.as_ref().map(|x| x.value())),
                     ("oldest", req.oldest.as_ref().map(|x| x.value())),
+                    (
+                        "include_all_metadata",
+                        req.include_all_metadata.map(|v| v.to_string()).as_ref(),
+                    ),
                 ],
             )
             .await
@@ -324,6 +332,7 @@ pub struct SlackApiConversationsHistoryRequest {
     pub limit: Option<u16>,
     pub oldest: Option<SlackTs>,
     pub inclusive: Option<bool>,
+    pub include_all_metadata: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -362,8 +371,7 @@ impl SlackApiScrollableResponse for SlackApiConversationsHistoryResponse {
     fn next_cursor(&self) -> Option<&Self::CursorType> {
         self.response_metadata
             .as_ref()
-            .map(|rm| rm.next_cursor.as_ref())
-            .flatten()
+            .and_then(|rm| rm.next_cursor.as_ref())
     }
 
     fn scrollable_items<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self::ResponseItemType> + 'a> {
@@ -477,8 +485,7 @@ impl SlackApiScrollableResponse for SlackApiConversationsListResponse {
     fn next_cursor(&self) -> Option<&Self::CursorType> {
         self.response_metadata
             .as_ref()
-            .map(|rm| rm.next_cursor.as_ref())
-            .flatten()
+            .and_then(|rm| rm.next_cursor.as_ref())
     }
 
     fn scrollable_items<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self::ResponseItemType> + 'a> {
@@ -528,8 +535,7 @@ impl SlackApiScrollableResponse for SlackApiConversationsMembersResponse {
     fn next_cursor(&self) -> Option<&Self::CursorType> {
         self.response_metadata
             .as_ref()
-            .map(|rm| rm.next_cursor.as_ref())
-            .flatten()
+            .and_then(|rm| rm.next_cursor.as_ref())
     }
 
     fn scrollable_items<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self::ResponseItemType> + 'a> {
@@ -579,6 +585,7 @@ pub struct SlackApiConversationsRepliesRequest {
     pub limit: Option<u16>,
     pub oldest: Option<SlackTs>,
     pub inclusive: Option<bool>,
+    pub include_all_metadata: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -616,8 +623,7 @@ impl SlackApiScrollableResponse for SlackApiConversationsRepliesResponse {
     fn next_cursor(&self) -> Option<&Self::CursorType> {
         self.response_metadata
             .as_ref()
-            .map(|rm| rm.next_cursor.as_ref())
-            .flatten()
+            .and_then(|rm| rm.next_cursor.as_ref())
     }
 
     fn scrollable_items<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self::ResponseItemType> + 'a> {
@@ -2,6 +2,7 @@ mod bots;
 mod chat;
 mod conversations;
 mod oauth;
+mod response_url;
 mod team;
 mod test;
 mod users;
@@ -12,6 +13,7 @@ pub use bots::*;
 pub use chat::*;
 pub use conversations::*;
 pub use oauth::*;
+pub use response_url::*;
 pub use team::*;
 pub use test::*;
 pub use users::*;
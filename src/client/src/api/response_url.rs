@@ -0,0 +1,241 @@
+//!
+//! Support for Slack response URLs of commands and interactions
+//!
+
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::errors::*;
+use crate::SlackClient;
+use crate::{ClientResult, SlackClientHttpConnector};
+use slack_morphism_models::*;
+
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    ///
+    /// Respond to a command or an interaction using its response url:
+    /// post `in_channel`/`ephemeral` messages, replace or delete the original message, or reply in a thread.
+    ///
+    /// Slack allows to use a response URL up to 5 times within 30 minutes,
+    /// so this returns `SlackClientError::ResponseUrlError` for expired and overused URLs.
+    ///
+    pub async fn respond_to_url(
+        &self,
+        response_url: &str,
+        req: &SlackApiResponseUrlRequest,
+    ) -> ClientResult<SlackApiResponseUrlResponse> {
+        self.response_url_tracker
+            .acquire(response_url)
+            .map_err(SlackClientError::ResponseUrlError)?;
+
+        self.http_api
+            .connector
+            .http_post_uri(response_url.parse()?, req, None)
+            .await
+            .map_err(Self::map_response_url_error)
+    }
+
+    fn map_response_url_error(
+        err: Box<dyn std::error::Error + Send + Sync>,
+    ) -> Box<dyn std::error::Error + Send + Sync> {
+        let reason = match err.downcast_ref::<SlackClientError>() {
+            Some(SlackClientError::HttpError(http_error))
+                if http_error.status_code == 404 || http_error.status_code == 410 =>
+            {
+                Some((
+                    SlackResponseUrlErrorReason::Expired,
+                    http_error.http_response_body.clone(),
+                ))
+            }
+            Some(SlackClientError::ApiError(api_error)) if api_error.code == "expired_url" => {
+                Some((
+                    SlackResponseUrlErrorReason::Expired,
+                    api_error.http_response_body.clone(),
+                ))
+            }
+            Some(SlackClientError::ApiError(api_error)) if api_error.code == "used_url" => Some((
+                SlackResponseUrlErrorReason::UsesExceeded,
+                api_error.http_response_body.clone(),
+            )),
+            _ => None,
+        };
+
+        match reason {
+            Some((reason, http_response_body)) => SlackClientError::ResponseUrlError(
+                SlackClientResponseUrlError::new(reason).opt_http_response_body(http_response_body),
+            )
+            .into(),
+            None => err,
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiResponseUrlRequest {
+    #[serde(flatten)]
+    pub content: SlackMessageContent,
+    pub response_type: Option<SlackMessageResponseType>,
+    pub replace_original: Option<bool>,
+    pub delete_original: Option<bool>,
+    pub thread_ts: Option<SlackTs>,
+}
+
+impl SlackApiResponseUrlRequest {
+    pub fn new_in_channel(content: SlackMessageContent) -> Self {
+        Self::new(content).with_response_type(SlackMessageResponseType::InChannel)
+    }
+
+    pub fn new_ephemeral(content: SlackMessageContent) -> Self {
+        Self::new(content).with_response_type(SlackMessageResponseType::Ephemeral)
+    }
+
+    pub fn new_replace_original(content: SlackMessageContent) -> Self {
+        Self::new(content).with_replace_original(true)
+    }
+
+    pub fn new_delete_original() -> Self {
+        Self::new(SlackMessageContent::new()).with_delete_original(true)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackApiResponseUrlResponse {}
+
+#[derive(Debug, Clone, Copy)]
+struct SlackResponseUrlUsage {
+    first_seen: Instant,
+    uses: usize,
+}
+
+///
+/// Tracks uses of response URLs to report expired or overused URLs without calling Slack.
+/// Only registered URLs are tracked (the listener routes register URLs of incoming events)
+/// and the expiration time is counted from the moment a URL was registered.
+/// Expired URLs are forgotten after they have been reported, so later uses are left to Slack to reject.
+///
+#[derive(Debug)]
+pub struct SlackResponseUrlTracker {
+    usages: Mutex<HashMap<String, SlackResponseUrlUsage>>,
+}
+
+impl SlackResponseUrlTracker {
+    pub const MAX_USES: usize = 5;
+    pub const EXPIRES_IN: Duration = Duration::from_secs(30 * 60);
+
+    pub fn new() -> Self {
+        Self {
+            usages: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn register(&self, response_url: &str) {
+        let mut usages = self.usages.lock().unwrap();
+        Self::remove_expired(&mut usages);
+        usages
+            .entry(response_url.into())
+            .or_insert_with(|| SlackResponseUrlUsage {
+                first_seen: Instant::now(),
+                uses: 0,
+            });
+    }
+
+    pub fn remaining_uses(&self, response_url: &str) -> usize {
+        let usages = self.usages.lock().unwrap();
+        match usages.get(response_url) {
+            Some(usage) if usage.first_seen.elapsed() > Self::EXPIRES_IN => 0,
+            Some(usage) => Self::MAX_USES.saturating_sub(usage.uses),
+            None => Self::MAX_USES,
+        }
+    }
+
+    pub(crate) fn acquire(&self, response_url: &str) -> Result<(), SlackClientResponseUrlError> {
+        let mut usages = self.usages.lock().unwrap();
+        let result = match usages.get_mut(response_url) {
+            Some(usage) if usage.first_seen.elapsed() > Self::EXPIRES_IN => Err(
+                SlackClientResponseUrlError::new(SlackResponseUrlErrorReason::Expired),
+            ),
+            Some(usage) if usage.uses >= Self::MAX_USES => Err(SlackClientResponseUrlError::new(
+                SlackResponseUrlErrorReason::UsesExceeded,
+            )),
+            Some(usage) => {
+                usage.uses += 1;
+                Ok(())
+            }
+            None => Ok(()),
+        };
+        Self::remove_expired(&mut usages);
+        result
+    }
+
+    fn remove_expired(usages: &mut HashMap<String, SlackResponseUrlUsage>) {
+        usages.retain(|_, usage| usage.first_seen.elapsed() <= Self::EXPIRES_IN);
+    }
+}
+
+#[test]
+fn check_response_url_uses_limit() {
+    let tracker = SlackResponseUrlTracker::new();
+    let response_url = "https://hooks.slack.com/commands/T1/1/abc";
+    tracker.register(response_url);
+
+    for _ in 0..SlackResponseUrlTracker::MAX_USES {
+        assert!(tracker.acquire(response_url).is_ok());
+    }
+    assert_eq!(tracker.remaining_uses(response_url), 0);
+    assert_eq!(
+        tracker.acquire(response_url).map_err(|err| err.reason),
+        Err(SlackResponseUrlErrorReason::UsesExceeded)
+    );
+}
+
+#[test]
+fn check_response_url_tracking() {
+    let tracker = SlackResponseUrlTracker::new();
+    let expired_url = "https://hooks.slack.com/commands/T1/1/expired";
+    let registered_url = "https://hooks.slack.com/commands/T1/2/abc";
+
+    assert!(tracker
+        .acquire("https://hooks.slack.com/commands/T1/3/unknown")
+        .is_ok());
+    assert_eq!(tracker.usages.lock().unwrap().len(), 0);
+
+    tracker.usages.lock().unwrap().insert(
+        expired_url.into(),
+        SlackResponseUrlUsage {
+            first_seen: Instant::now()
+                - SlackResponseUrlTracker::EXPIRES_IN
+                - Duration::from_secs(1),
+            uses: 0,
+        },
+    );
+    tracker.register(registered_url);
+    assert_eq!(tracker.usages.lock().unwrap().len(), 1);
+
+    tracker.usages.lock().unwrap().insert(
+        expired_url.into(),
+        SlackResponseUrlUsage {
+            first_seen: Instant::now()
+                - SlackResponseUrlTracker::EXPIRES_IN
+                - Duration::from_secs(1),
+            uses: 0,
+        },
+    );
+    assert_eq!(
+        tracker.acquire(expired_url).map_err(|err| err.reason),
+        Err(SlackResponseUrlErrorReason::Expired)
+    );
+    assert!(tracker.acquire(registered_url).is_ok());
+    assert_eq!(tracker.usages.lock().unwrap().len(), 1);
+    assert_eq!(
+        tracker.remaining_uses(registered_url),
+        SlackResponseUrlTracker::MAX_USES - 1
+    );
+}
@@ -22,6 +22,7 @@ where
         &self,
         req: &SlackApiViewsOpenRequest,
     ) -> ClientResult<SlackApiViewsOpenResponse> {
+        self.client.validate_block_kit(|| req.view.validate())?;
         self.http_api.http_post("views.open", req).await
     }
 
@@ -32,6 +33,7 @@ where
         &self,
         req: &SlackApiViewsPublishRequest,
     ) -> ClientResult<SlackApiViewsPublishResponse> {
+        self.client.validate_block_kit(|| req.view.validate())?;
         self.http_api.http_post("views.publish", req).await
     }
 
@@ -42,6 +44,7 @@ where
         &self,
         req: &SlackApiViewsPushRequest,
     ) -> ClientResult<SlackApiViewsPushResponse> {
+        self.client.validate_block_kit(|| req.view.validate())?;
         self.http_api.http_post("views.push", req).await
     }
 
@@ -52,6 +55,7 @@ where
         &self,
         req: &SlackApiViewsUpdateRequest,
     ) -> ClientResult<SlackApiViewsUpdateResponse> {
+        self.client.validate_block_kit(|| req.view.validate())?;
         self.http_api.http_post("views.update", req).await
     }
 }
@@ -0,0 +1,310 @@
+//!
+//! Links to Slack Block Kit Builder to preview messages and views,
+//! and parsing of such links back into the models
+//!
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use slack_morphism_models::blocks::*;
+use slack_morphism_models::*;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use url::Url;
+
+pub const SLACK_BLOCK_KIT_BUILDER_URL: &str = "https://app.slack.com/block-kit-builder";
+
+// The same characters as JavaScript `encodeURIComponent` encodes
+const SLACK_BLOCK_KIT_BUILDER_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackBlockKitBuilderMode {
+    Message,
+    Modal,
+    Home,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackBlockKitBuilderPayload {
+    Message(SlackMessageContent),
+    View(SlackView),
+}
+
+impl SlackBlockKitBuilderPayload {
+    pub fn mode(&self) -> SlackBlockKitBuilderMode {
+        match self {
+            SlackBlockKitBuilderPayload::View(SlackView::Modal(_)) => {
+                SlackBlockKitBuilderMode::Modal
+            }
+            SlackBlockKitBuilderPayload::View(SlackView::Home(_)) => SlackBlockKitBuilderMode::Home,
+            SlackBlockKitBuilderPayload::View(SlackView::Other(view)) => {
+                match view.get("type").and_then(|view_type| view_type.as_str()) {
+                    Some("home") => SlackBlockKitBuilderMode::Home,
+                    _ => SlackBlockKitBuilderMode::Modal,
+                }
+            }
+            SlackBlockKitBuilderPayload::Message(_) => SlackBlockKitBuilderMode::Message,
+        }
+    }
+}
+
+impl From<SlackMessageContent> for SlackBlockKitBuilderPayload {
+    fn from(content: SlackMessageContent) -> Self {
+        SlackBlockKitBuilderPayload::Message(content)
+    }
+}
+
+impl From<SlackView> for SlackBlockKitBuilderPayload {
+    fn from(view: SlackView) -> Self {
+        SlackBlockKitBuilderPayload::View(view)
+    }
+}
+
+///
+/// A Block Kit Builder link with a message, a modal or a home tab encoded as JSON in its fragment.
+/// Links open the builder in the workspace of `team_id`, or in the last used workspace.
+///
+/// Parsing accepts only `https://app.slack.com/block-kit-builder` links,
+/// and also supports legacy links with `mode` and `blocks` query parameters.
+///
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackBlockKitBuilderUrl {
+    pub payload: SlackBlockKitBuilderPayload,
+    pub team_id: Option<SlackTeamId>,
+}
+
+impl SlackBlockKitBuilderUrl {
+    pub fn to_url(&self) -> Result<String, SlackBlockKitBuilderError> {
+        let json = match &self.payload {
+            SlackBlockKitBuilderPayload::Message(content) => {
+                // The builder takes only blocks and attachments of messages
+                let mut message = serde_json::Map::new();
+                message.insert(
+                    "blocks".into(),
+                    Self::to_json(content.blocks.as_ref().unwrap_or(&vec![]))?,
+                );
+                if let Some(attachments) = &content.attachments {
+                    message.insert("attachments".into(), Self::to_json(attachments)?);
+                }
+                serde_json::Value::Object(message)
+            }
+            SlackBlockKitBuilderPayload::View(view) => Self::to_json(view)?,
+        };
+
+        Ok(format!(
+            "{}/{}#{}",
+            SLACK_BLOCK_KIT_BUILDER_URL,
+            self.team_id
+                .as_ref()
+                .map(|team_id| team_id.value().as_str())
+                .unwrap_or_default(),
+            utf8_percent_encode(&json.to_string(), SLACK_BLOCK_KIT_BUILDER_ENCODE_SET)
+        ))
+    }
+
+    pub fn parse(url: &str) -> Result<Self, SlackBlockKitBuilderError> {
+        let url = Url::parse(url).map_err(|err| Self::url_error(&err.to_string()))?;
+
+        let mut path_segments = url.path_segments().into_iter().flatten();
+        if url.scheme() != "https"
+            || url.host_str() != Some("app.slack.com")
+            || path_segments.next() != Some("block-kit-builder")
+        {
+            return Err(Self::url_error("not a Block Kit Builder URL"));
+        }
+
+        let team_id = path_segments
+            .next()
+            .filter(|team_id| !team_id.is_empty())
+            .map(|team_id| SlackTeamId(team_id.into()));
+
+        let mode = url
+            .query_pairs()
+            .find(|(name, _)| name == "mode")
+            .map(|(_, mode)| mode.into_owned());
+
+        let mut json: serde_json::Value = match url.fragment().filter(|json| !json.is_empty()) {
+            Some(fragment) => {
+                let json = percent_decode_str(fragment)
+                    .decode_utf8()
+                    .map_err(|err| Self::url_error(&err.to_string()))?;
+                serde_json::from_str(&json).map_err(Self::json_error)?
+            }
+            None => {
+                let blocks = url
+                    .query_pairs()
+                    .find(|(name, _)| name == "blocks")
+                    .map(|(_, blocks)| blocks.into_owned())
+                    .ok_or_else(|| Self::url_error("no blocks in the URL"))?;
+                let blocks: serde_json::Value =
+                    serde_json::from_str(&blocks).map_err(Self::json_error)?;
+                serde_json::json!({ "blocks": blocks })
+            }
+        };
+
+        if let (Some(mode), Some(object)) = (&mode, json.as_object_mut()) {
+            if !object.contains_key("type") {
+                match mode.as_str() {
+                    "modal" => object.insert("type".into(), "modal".into()),
+                    "appHome" | "home" => object.insert("type".into(), "home".into()),
+                    _ => None,
+                };
+            }
+        }
+
+        let payload = match json.get("type").and_then(|view_type| view_type.as_str()) {
+            Some(_) => SlackBlockKitBuilderPayload::View(
+                serde_json::from_value(json).map_err(Self::json_error)?,
+            ),
+            None => SlackBlockKitBuilderPayload::Message(
+                serde_json::from_value(json).map_err(Self::json_error)?,
+            ),
+        };
+
+        Ok(Self { payload, team_id })
+    }
+
+    fn to_json<T: serde::Serialize>(
+        value: &T,
+    ) -> Result<serde_json::Value, SlackBlockKitBuilderError> {
+        serde_json::to_value(value).map_err(Self::json_error)
+    }
+
+    fn json_error(err: serde_json::Error) -> SlackBlockKitBuilderError {
+        SlackBlockKitBuilderError::JsonError(SlackBlockKitBuilderJsonError::new(err.to_string()))
+    }
+
+    fn url_error(message: &str) -> SlackBlockKitBuilderError {
+        SlackBlockKitBuilderError::UrlError(SlackBlockKitBuilderUrlError::new(message.into()))
+    }
+}
+
+///
+/// Block Kit Builder links for messages and views
+///
+pub trait SlackBlockKitBuilderExt {
+    fn to_block_kit_builder_url(&self) -> Result<String, SlackBlockKitBuilderError>;
+}
+
+impl SlackBlockKitBuilderExt for SlackMessageContent {
+    fn to_block_kit_builder_url(&self) -> Result<String, SlackBlockKitBuilderError> {
+        SlackBlockKitBuilderUrl::new(self.clone().into()).to_url()
+    }
+}
+
+impl SlackBlockKitBuilderExt for SlackView {
+    fn to_block_kit_builder_url(&self) -> Result<String, SlackBlockKitBuilderError> {
+        SlackBlockKitBuilderUrl::new(self.clone().into()).to_url()
+    }
+}
+
+#[derive(Debug)]
+pub enum SlackBlockKitBuilderError {
+    JsonError(SlackBlockKitBuilderJsonError),
+    UrlError(SlackBlockKitBuilderUrlError),
+}
+
+impl Display for SlackBlockKitBuilderError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match *self {
+            SlackBlockKitBuilderError::JsonError(ref err) => err.fmt(f),
+            SlackBlockKitBuilderError::UrlError(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for SlackBlockKitBuilderError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match *self {
+            SlackBlockKitBuilderError::JsonError(ref err) => Some(err),
+            SlackBlockKitBuilderError::UrlError(ref err) => Some(err),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackBlockKitBuilderJsonError {
+    pub message: String,
+}
+
+impl Display for SlackBlockKitBuilderJsonError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack Block Kit Builder JSON error: {}", self.message)
+    }
+}
+
+impl Error for SlackBlockKitBuilderJsonError {}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackBlockKitBuilderUrlError {
+    pub message: String,
+}
+
+impl Display for SlackBlockKitBuilderUrlError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack Block Kit Builder URL error: {}", self.message)
+    }
+}
+
+impl Error for SlackBlockKitBuilderUrlError {}
+
+#[test]
+fn check_block_kit_builder_url() {
+    let content = SlackMessageContent::new().with_blocks(vec![SlackSectionBlock::new()
+        .with_text(SlackBlockText::MarkDown("Hello *world* & 100%".into()))
+        .into()]);
+
+    let url = content.to_block_kit_builder_url().unwrap();
+    assert_eq!(
+        url,
+        "https://app.slack.com/block-kit-builder/#%7B%22blocks%22%3A%5B%7B%22text%22%3A%7B%22text%22%3A%22Hello%20*world*%20%26%20100%25%22%2C%22type%22%3A%22mrkdwn%22%7D%2C%22type%22%3A%22section%22%7D%5D%7D"
+    );
+
+    let parsed = SlackBlockKitBuilderUrl::parse(&url).unwrap();
+    assert_eq!(
+        parsed.payload,
+        SlackBlockKitBuilderPayload::Message(content)
+    );
+    assert_eq!(parsed.team_id, None);
+
+    let view = SlackView::Modal(SlackModalView::new(
+        "Preview".into(),
+        vec![SlackDividerBlock::new().into()],
+    ));
+    let url = SlackBlockKitBuilderUrl::new(view.clone().into())
+        .with_team_id("T1".into())
+        .to_url()
+        .unwrap();
+    let parsed = SlackBlockKitBuilderUrl::parse(&url).unwrap();
+    assert_eq!(parsed.payload.mode(), SlackBlockKitBuilderMode::Modal);
+    assert_eq!(parsed.payload, SlackBlockKitBuilderPayload::View(view));
+    assert_eq!(parsed.team_id, Some("T1".into()));
+
+    let legacy = SlackBlockKitBuilderUrl::parse(
+        "https://app.slack.com/block-kit-builder/T1?mode=appHome&blocks=%5B%7B%22type%22%3A%22divider%22%7D%5D",
+    )
+    .unwrap();
+    assert_eq!(legacy.payload.mode(), SlackBlockKitBuilderMode::Home);
+
+    for url in &[
+        "https://example.com/block-kit-builder/#%7B%22blocks%22%3A%5B%5D%7D",
+        "https://app.slack.com.example.com/block-kit-builder/#%7B%22blocks%22%3A%5B%5D%7D",
+        "https://app.slack.com/client/T1#%7B%22blocks%22%3A%5B%5D%7D",
+        "http://app.slack.com/block-kit-builder/#%7B%22blocks%22%3A%5B%5D%7D",
+    ] {
+        assert!(matches!(
+            SlackBlockKitBuilderUrl::parse(url),
+            Err(SlackBlockKitBuilderError::UrlError(_))
+        ));
+    }
+}
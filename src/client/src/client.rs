@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use crate::api::SlackResponseUrlTracker;
+use crate::errors::*;
 use crate::token::*;
 
 use futures_util::future::BoxFuture;
 use lazy_static::*;
+use slack_morphism_models::blocks::SlackBlockKitViolation;
 use slack_morphism_models::{SlackClientId, SlackClientSecret};
 use url::Url;
 
@@ -13,6 +16,8 @@ where
     SCHC: SlackClientHttpConnector + Send,
 {
     pub http_api: SlackClientHttpApi<SCHC>,
+    pub(crate) response_url_tracker: SlackResponseUrlTracker,
+    pub(crate) block_kit_validation: bool,
 }
 
 #[derive(Debug)]
@@ -29,7 +34,8 @@ where
     SCHC: SlackClientHttpConnector + Send,
 {
     pub http_api: SlackClientHttpSessionApi<'a, SCHC>,
-    client: &'a SlackClient<SCHC>,
+    pub(crate) client: &'a SlackClient<SCHC>,
+    #[allow(dead_code)]
     token: &'a SlackApiToken,
 }
 
@@ -178,8 +184,7 @@ impl SlackClientHttpApiUri {
         let url_query_params: Vec<(String, String)> = params
             .clone()
             .into_iter()
-            .map(|(k, vo)| vo.map(|v| (k.to_string(), v.to_string())))
-            .flatten()
+            .filter_map(|(k, vo)| vo.map(|v| (k.to_string(), v.to_string())))
             .collect();
 
         Url::parse_with_params(url_str, url_query_params)
@@ -197,9 +202,30 @@ where
     pub fn new(http_connector: SCHC) -> Self {
         Self {
             http_api: SlackClientHttpApi::new(http_connector),
+            response_url_tracker: SlackResponseUrlTracker::new(),
+            block_kit_validation: false,
+        }
+    }
+
+    ///
+    /// Validate Block Kit messages and views against Slack limits before sending them
+    /// (`chat.*` and `views.*` methods), returning `SlackClientError::BlockKitValidationError`
+    /// instead of calling Slack.
+    ///
+    pub fn with_block_kit_validation(self) -> Self {
+        Self {
+            block_kit_validation: true,
+            ..self
         }
     }
 
+    ///
+    /// Tracks uses of response URLs from events to respect Slack limits
+    ///
+    pub fn response_url_tracker(&self) -> &SlackResponseUrlTracker {
+        &self.response_url_tracker
+    }
+
     pub fn open_session<'a>(&'a self, token: &'a SlackApiToken) -> SlackClientSession<'a, SCHC> {
         let http_session_api = SlackClientHttpSessionApi {
             client: self,
@@ -214,6 +240,27 @@ where
     }
 }
 
+impl<SCHC> SlackClient<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send,
+{
+    pub(crate) fn validate_block_kit<F>(&self, violations: F) -> ClientResult<()>
+    where
+        F: FnOnce() -> Vec<SlackBlockKitViolation>,
+    {
+        if self.block_kit_validation {
+            let violations = violations();
+            if !violations.is_empty() {
+                return Err(SlackClientError::BlockKitValidationError(
+                    SlackClientBlockKitValidationError::new(violations),
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<'a, SCHC> SlackClientHttpSessionApi<'a, SCHC>
 where
     SCHC: SlackClientHttpConnector + Send,
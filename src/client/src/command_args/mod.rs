@@ -0,0 +1,604 @@
+//!
+//! Parsing of slash command texts: quoted strings, flags and options, subcommands
+//! and Slack escaped entities, using a declarative command specification
+//! that also provides usage and help responses.
+//!
+
+use rsb_derive::Builder;
+use slack_morphism_models::events::*;
+use slack_morphism_models::*;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+mod tokenizer;
+pub use tokenizer::*;
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackCommandArgSpec {
+    pub name: String,
+    pub description: Option<String>,
+    #[default = "true"]
+    pub required: bool,
+    #[default = "false"]
+    pub variadic: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackCommandOptionSpec {
+    pub name: String,
+    pub short: Option<char>,
+    pub description: Option<String>,
+    #[default = "false"]
+    pub takes_value: bool,
+}
+
+///
+/// Declarative specification of a command (or a subcommand) with its positional arguments and options.
+/// Options without values are flags.
+///
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackCommandSpec {
+    pub name: String,
+    pub description: Option<String>,
+    #[default = "Vec::new()"]
+    pub args: Vec<SlackCommandArgSpec>,
+    #[default = "Vec::new()"]
+    pub options: Vec<SlackCommandOptionSpec>,
+    #[default = "Vec::new()"]
+    pub subcommands: Vec<SlackCommandSpec>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackCommandArgs {
+    pub subcommand_path: Vec<String>,
+    pub args: Vec<(String, SlackCommandArgValue)>,
+    pub options: HashMap<String, SlackCommandArgValue>,
+    pub flags: HashSet<String>,
+}
+
+impl SlackCommandArgs {
+    pub fn subcommand(&self) -> Option<&str> {
+        self.subcommand_path.last().map(|s| s.as_str())
+    }
+
+    pub fn arg(&self, name: &str) -> Option<&SlackCommandArgValue> {
+        self.args
+            .iter()
+            .find(|(arg_name, _)| arg_name == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn arg_values(&self, name: &str) -> Vec<&SlackCommandArgValue> {
+        self.args
+            .iter()
+            .filter(|(arg_name, _)| arg_name == name)
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    pub fn option(&self, name: &str) -> Option<&SlackCommandArgValue> {
+        self.options.get(name)
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackCommandArgsError {
+    UnterminatedQuote,
+    UnknownOption(String),
+    MissingOptionValue(String),
+    UnknownSubcommand(String),
+    MissingSubcommand,
+    MissingArgument(String),
+    UnexpectedArgument(String),
+    HelpRequested(Vec<String>),
+}
+
+impl Display for SlackCommandArgsError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SlackCommandArgsError::UnterminatedQuote => write!(f, "Unterminated quote"),
+            SlackCommandArgsError::UnknownOption(option) => write!(f, "Unknown option: {}", option),
+            SlackCommandArgsError::MissingOptionValue(option) => {
+                write!(f, "Missing value for option: {}", option)
+            }
+            SlackCommandArgsError::UnknownSubcommand(subcommand) => {
+                write!(f, "Unknown subcommand: {}", subcommand)
+            }
+            SlackCommandArgsError::MissingSubcommand => write!(f, "Missing subcommand"),
+            SlackCommandArgsError::MissingArgument(arg) => {
+                write!(f, "Missing required argument: {}", arg)
+            }
+            SlackCommandArgsError::UnexpectedArgument(arg) => {
+                write!(f, "Unexpected argument: {}", arg)
+            }
+            SlackCommandArgsError::HelpRequested(_) => write!(f, "Help requested"),
+        }
+    }
+}
+
+impl Error for SlackCommandArgsError {}
+
+impl SlackCommandSpec {
+    pub fn with_arg(mut self, arg: SlackCommandArgSpec) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    pub fn with_option(mut self, option: SlackCommandOptionSpec) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    pub fn with_subcommand(mut self, subcommand: SlackCommandSpec) -> Self {
+        self.subcommands.push(subcommand);
+        self
+    }
+
+    pub fn parse(
+        &self,
+        event: &SlackCommandEvent,
+    ) -> Result<SlackCommandArgs, SlackCommandArgsError> {
+        self.parse_text(event.text.as_deref().unwrap_or(""))
+    }
+
+    pub fn parse_text(&self, text: &str) -> Result<SlackCommandArgs, SlackCommandArgsError> {
+        let tokens = tokenize_command_text(text)?;
+        let (subcommand_path, spec) = self.resolve_subcommand(&tokens);
+        let mut tokens_iter = tokens.iter().skip(subcommand_path.len()).peekable();
+
+        let mut positional: Vec<&SlackCommandToken> = Vec::new();
+        let mut options: HashMap<String, SlackCommandArgValue> = HashMap::new();
+        let mut flags: HashSet<String> = HashSet::new();
+        let mut options_ended = false;
+
+        if subcommand_path.is_empty()
+            && tokens.len() == 1
+            && !tokens[0].quoted
+            && tokens[0].text == "help"
+        {
+            return Err(SlackCommandArgsError::HelpRequested(subcommand_path));
+        }
+
+        while let Some(token) = tokens_iter.next() {
+            if token.quoted || options_ended {
+                positional.push(token);
+            } else if token.text == "--" {
+                options_ended = true;
+            } else if token.text == "--help" || token.text == "-h" {
+                return Err(SlackCommandArgsError::HelpRequested(subcommand_path));
+            } else if let Some(long_name) = token.text.strip_prefix("--") {
+                let (option_name, inline_value) = match long_name.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (long_name, None),
+                };
+                let option_spec = spec
+                    .options
+                    .iter()
+                    .find(|option| option.name == option_name)
+                    .ok_or_else(|| SlackCommandArgsError::UnknownOption(token.text.clone()))?;
+                Self::parse_option(
+                    option_spec,
+                    inline_value,
+                    &mut tokens_iter,
+                    &mut options,
+                    &mut flags,
+                )?;
+            } else if let Some(short_names) = token.text.strip_prefix('-').filter(|short_names| {
+                !short_names.is_empty() && !short_names.starts_with(|c: char| c.is_ascii_digit())
+            }) {
+                // Short options may be combined (`-rf`), an option with a value ends the group (`-fr eu`, `-freu`)
+                for (short_idx, short) in short_names.char_indices() {
+                    let option_spec = spec
+                        .options
+                        .iter()
+                        .find(|option| option.short == Some(short))
+                        .ok_or_else(|| SlackCommandArgsError::UnknownOption(token.text.clone()))?;
+                    if option_spec.takes_value {
+                        let rest = &short_names[short_idx + short.len_utf8()..];
+                        let inline_value = if rest.is_empty() {
+                            None
+                        } else {
+                            Some(rest.strip_prefix('=').unwrap_or(rest))
+                        };
+                        Self::parse_option(
+                            option_spec,
+                            inline_value,
+                            &mut tokens_iter,
+                            &mut options,
+                            &mut flags,
+                        )?;
+                        break;
+                    } else {
+                        flags.insert(option_spec.name.clone());
+                    }
+                }
+            } else {
+                positional.push(token);
+            }
+        }
+
+        if positional.is_empty() && spec.args.is_empty() && !spec.subcommands.is_empty() {
+            return Err(SlackCommandArgsError::MissingSubcommand);
+        }
+
+        let mut args: Vec<(String, SlackCommandArgValue)> = Vec::new();
+        let mut positional_iter = positional.into_iter().peekable();
+        for arg_spec in &spec.args {
+            if arg_spec.required && positional_iter.peek().is_none() {
+                return Err(SlackCommandArgsError::MissingArgument(
+                    arg_spec.name.clone(),
+                ));
+            }
+            if arg_spec.variadic {
+                args.extend(positional_iter.by_ref().map(|token| {
+                    (
+                        arg_spec.name.clone(),
+                        SlackCommandArgValue::from_token(token),
+                    )
+                }));
+            } else if let Some(token) = positional_iter.next() {
+                args.push((
+                    arg_spec.name.clone(),
+                    SlackCommandArgValue::from_token(token),
+                ));
+            }
+        }
+
+        if let Some(unexpected) = positional_iter.next() {
+            return Err(if spec.args.is_empty() && !spec.subcommands.is_empty() {
+                SlackCommandArgsError::UnknownSubcommand(unexpected.text.clone())
+            } else {
+                SlackCommandArgsError::UnexpectedArgument(unexpected.text.clone())
+            });
+        }
+
+        Ok(SlackCommandArgs {
+            subcommand_path,
+            args,
+            options,
+            flags,
+        })
+    }
+
+    fn parse_option<'t, I>(
+        option_spec: &SlackCommandOptionSpec,
+        inline_value: Option<&str>,
+        tokens_iter: &mut I,
+        options: &mut HashMap<String, SlackCommandArgValue>,
+        flags: &mut HashSet<String>,
+    ) -> Result<(), SlackCommandArgsError>
+    where
+        I: Iterator<Item = &'t SlackCommandToken>,
+    {
+        if option_spec.takes_value {
+            let value = match inline_value {
+                Some(value) => SlackCommandArgValue::parse(value),
+                None => tokens_iter
+                    .next()
+                    .map(SlackCommandArgValue::from_token)
+                    .ok_or_else(|| {
+                        SlackCommandArgsError::MissingOptionValue(option_spec.name.clone())
+                    })?,
+            };
+            options.insert(option_spec.name.clone(), value);
+        } else {
+            flags.insert(option_spec.name.clone());
+        }
+        Ok(())
+    }
+
+    fn resolve_subcommand(&self, tokens: &[SlackCommandToken]) -> (Vec<String>, &SlackCommandSpec) {
+        let mut spec = self;
+        let mut subcommand_path: Vec<String> = Vec::new();
+        for token in tokens {
+            match spec
+                .subcommands
+                .iter()
+                .find(|subcommand| !token.quoted && subcommand.name == token.text)
+            {
+                Some(subcommand) => {
+                    subcommand_path.push(subcommand.name.clone());
+                    spec = subcommand;
+                }
+                None => break,
+            }
+        }
+        (subcommand_path, spec)
+    }
+
+    ///
+    /// Path of subcommands in the command text, resolved as far as the text matches the specification
+    ///
+    pub fn subcommand_path(&self, text: &str) -> Vec<String> {
+        tokenize_command_text(text)
+            .map(|tokens| self.resolve_subcommand(&tokens).0)
+            .unwrap_or_default()
+    }
+
+    fn find_subcommand(&self, subcommand_path: &[String]) -> (String, &SlackCommandSpec) {
+        subcommand_path.iter().fold(
+            (self.name.clone(), self),
+            |(full_name, spec), subcommand_name| match spec
+                .subcommands
+                .iter()
+                .find(|subcommand| &subcommand.name == subcommand_name)
+            {
+                Some(subcommand) => (format!("{} {}", full_name, subcommand.name), subcommand),
+                None => (full_name, spec),
+            },
+        )
+    }
+
+    pub fn usage(&self, subcommand_path: &[String]) -> String {
+        let (full_name, spec) = self.find_subcommand(subcommand_path);
+        let mut usage = vec![full_name];
+
+        if !spec.subcommands.is_empty() {
+            usage.push("<subcommand>".into());
+        }
+
+        for option in &spec.options {
+            let option_name = match option.short {
+                Some(short) => format!("-{}|--{}", short, option.name),
+                None => format!("--{}", option.name),
+            };
+            if option.takes_value {
+                usage.push(format!("[{} <{}>]", option_name, option.name));
+            } else {
+                usage.push(format!("[{}]", option_name));
+            }
+        }
+
+        for arg in &spec.args {
+            let arg_name = if arg.variadic {
+                format!("{}...", arg.name)
+            } else {
+                arg.name.clone()
+            };
+            if arg.required {
+                usage.push(format!("<{}>", arg_name));
+            } else {
+                usage.push(format!("[{}]", arg_name));
+            }
+        }
+
+        usage.join(" ")
+    }
+
+    ///
+    /// Help text in Slack mrkdwn format for the command or its subcommand
+    ///
+    pub fn help_text(&self, subcommand_path: &[String]) -> String {
+        let (_, spec) = self.find_subcommand(subcommand_path);
+        let mut lines = vec![format!("*Usage:* `{}`", self.usage(subcommand_path))];
+
+        if let Some(description) = &spec.description {
+            lines.push(description.clone());
+        }
+
+        if !spec.args.is_empty() {
+            lines.push("*Arguments:*".into());
+            lines.extend(
+                spec.args.iter().map(|arg| {
+                    Self::help_line(&format!("`{}`", arg.name), arg.description.as_ref())
+                }),
+            );
+        }
+
+        if !spec.options.is_empty() {
+            lines.push("*Options:*".into());
+            lines.extend(spec.options.iter().map(|option| {
+                let option_name = match option.short {
+                    Some(short) => format!("`-{}`, `--{}`", short, option.name),
+                    None => format!("`--{}`", option.name),
+                };
+                Self::help_line(&option_name, option.description.as_ref())
+            }));
+        }
+
+        if !spec.subcommands.is_empty() {
+            lines.push("*Subcommands:*".into());
+            lines.extend(spec.subcommands.iter().map(|subcommand| {
+                Self::help_line(
+                    &format!("`{}`", subcommand.name),
+                    subcommand.description.as_ref(),
+                )
+            }));
+        }
+
+        lines.join("\n")
+    }
+
+    fn help_line(name: &str, description: Option<&String>) -> String {
+        match description {
+            Some(description) => format!("• {} — {}", name, description),
+            None => format!("• {}", name),
+        }
+    }
+
+    pub fn help_response(&self, subcommand_path: &[String]) -> SlackCommandEventResponse {
+        SlackCommandEventResponse::new(
+            SlackMessageContent::new().with_text(self.help_text(subcommand_path)),
+        )
+        .with_response_type(SlackMessageResponseType::Ephemeral)
+    }
+
+    ///
+    /// Response for parsing errors of the command event: the help for `HelpRequested`
+    /// or the error with the usage of the (sub)command
+    ///
+    pub fn error_response(
+        &self,
+        event: &SlackCommandEvent,
+        err: &SlackCommandArgsError,
+    ) -> SlackCommandEventResponse {
+        self.error_response_text(event.text.as_deref().unwrap_or(""), err)
+    }
+
+    pub fn error_response_text(
+        &self,
+        text: &str,
+        err: &SlackCommandArgsError,
+    ) -> SlackCommandEventResponse {
+        match err {
+            SlackCommandArgsError::HelpRequested(subcommand_path) => {
+                self.help_response(subcommand_path)
+            }
+            other_err => {
+                SlackCommandEventResponse::new(SlackMessageContent::new().with_text(format!(
+                    "{}\n*Usage:* `{}`",
+                    other_err,
+                    self.usage(&self.subcommand_path(text))
+                )))
+                .with_response_type(SlackMessageResponseType::Ephemeral)
+            }
+        }
+    }
+
+    pub fn command_id(&self) -> SlackCommandId {
+        self.name.clone().into()
+    }
+}
+
+#[test]
+fn check_command_args_parsing() {
+    let spec = SlackCommandSpec::new("/deploy".into()).with_subcommand(
+        SlackCommandSpec::new("service".into())
+            .with_arg(SlackCommandArgSpec::new("name".into()))
+            .with_arg(
+                SlackCommandArgSpec::new("notify".into())
+                    .with_variadic(true)
+                    .with_required(false),
+            )
+            .with_option(
+                SlackCommandOptionSpec::new("region".into())
+                    .with_short('r')
+                    .with_takes_value(true),
+            )
+            .with_option(SlackCommandOptionSpec::new("force".into()).with_short('f')),
+    );
+
+    let args = spec
+        .parse_text(r#"service “my service” -f --region=eu <@U123|john> <#C456|general> <!subteam^S1|@team>"#)
+        .unwrap();
+
+    assert_eq!(args.subcommand(), Some("service"));
+    assert_eq!(
+        args.arg("name"),
+        Some(&SlackCommandArgValue::Text("my service".into()))
+    );
+    assert!(args.has_flag("force"));
+    assert_eq!(
+        args.option("region"),
+        Some(&SlackCommandArgValue::Text("eu".into()))
+    );
+    assert_eq!(
+        args.arg_values("notify"),
+        vec![
+            &SlackCommandArgValue::User("U123".into()),
+            &SlackCommandArgValue::Channel("C456".into()),
+            &SlackCommandArgValue::UserGroup("S1".into())
+        ]
+    );
+
+    let args = spec.parse_text(r#"service "a &amp; b" a&lt;b"#).unwrap();
+    assert_eq!(
+        args.arg("name"),
+        Some(&SlackCommandArgValue::Text("a & b".into()))
+    );
+    assert_eq!(
+        args.arg_values("notify"),
+        vec![&SlackCommandArgValue::Text("a<b".into())]
+    );
+
+    assert_eq!(
+        spec.parse_text("service"),
+        Err(SlackCommandArgsError::MissingArgument("name".into()))
+    );
+    assert_eq!(
+        spec.parse_text("service --help"),
+        Err(SlackCommandArgsError::HelpRequested(vec!["service".into()]))
+    );
+    assert_eq!(
+        spec.parse_text("rollback"),
+        Err(SlackCommandArgsError::UnknownSubcommand("rollback".into()))
+    );
+    assert_eq!(
+        spec.usage(&["service".into()]),
+        "/deploy service [-r|--region <region>] [-f|--force] <name> [notify...]"
+    );
+}
+
+#[test]
+fn check_command_args_short_options() {
+    let spec = SlackCommandSpec::new("/deploy".into()).with_subcommand(
+        SlackCommandSpec::new("service".into())
+            .with_arg(SlackCommandArgSpec::new("name".into()))
+            .with_option(
+                SlackCommandOptionSpec::new("region".into())
+                    .with_short('r')
+                    .with_takes_value(true),
+            )
+            .with_option(SlackCommandOptionSpec::new("force".into()).with_short('f'))
+            .with_option(SlackCommandOptionSpec::new("verbose".into()).with_short('v')),
+    );
+
+    let args = spec.parse_text("service -vf api").unwrap();
+    assert!(args.has_flag("verbose"));
+    assert!(args.has_flag("force"));
+    assert_eq!(
+        args.arg("name"),
+        Some(&SlackCommandArgValue::Text("api".into()))
+    );
+
+    let args = spec.parse_text("service -fr eu api").unwrap();
+    assert!(args.has_flag("force"));
+    assert_eq!(
+        args.option("region"),
+        Some(&SlackCommandArgValue::Text("eu".into()))
+    );
+
+    let args = spec.parse_text("service -freu api").unwrap();
+    assert_eq!(
+        args.option("region"),
+        Some(&SlackCommandArgValue::Text("eu".into()))
+    );
+
+    assert_eq!(
+        spec.parse_text("service -fx api"),
+        Err(SlackCommandArgsError::UnknownOption("-fx".into()))
+    );
+    assert_eq!(
+        spec.parse_text("service -f -- -1 extra"),
+        Err(SlackCommandArgsError::UnexpectedArgument("extra".into()))
+    );
+
+    assert_eq!(
+        spec.error_response_text(
+            "service -fx api",
+            &SlackCommandArgsError::UnknownOption("-fx".into())
+        )
+        .content
+        .text,
+        Some(
+            "Unknown option: -fx\n*Usage:* `/deploy service [-r|--region <region>] [-f|--force] [-v|--verbose] <name>`"
+                .into()
+        )
+    );
+
+    let spec = SlackCommandSpec::new("/deploy".into())
+        .with_option(SlackCommandOptionSpec::new("verbose".into()).with_takes_value(true));
+    assert_eq!(
+        spec.parse_text("-verbose"),
+        Err(SlackCommandArgsError::UnknownOption("-verbose".into()))
+    );
+    assert_eq!(
+        spec.parse_text("--verbose high").unwrap().option("verbose"),
+        Some(&SlackCommandArgValue::Text("high".into()))
+    );
+}
@@ -0,0 +1,194 @@
+use crate::command_args::SlackCommandArgsError;
+use slack_morphism_models::*;
+use std::fmt::{Display, Formatter};
+
+///
+/// A raw token of a command text. Quoted tokens are never treated as options, subcommands or Slack entities.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackCommandToken {
+    pub text: String,
+    pub quoted: bool,
+}
+
+///
+/// A value of a command argument or option with resolved Slack escaped entities
+///
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackCommandArgValue {
+    Text(String),
+    User(SlackUserId),
+    Channel(SlackChannelId),
+    UserGroup(SlackUserGroupId),
+    Url(String),
+    Special(String),
+}
+
+impl SlackCommandArgValue {
+    pub fn from_token(token: &SlackCommandToken) -> Self {
+        if token.quoted {
+            SlackCommandArgValue::Text(Self::unescape(&token.text))
+        } else {
+            Self::parse(&token.text)
+        }
+    }
+
+    ///
+    /// Parses Slack escaped entities like `<@U123|name>`, `<#C123|chan>`, `<!subteam^S1>` and `<https://...>`.
+    /// Other values are treated as text with unescaped `&amp;`, `&lt;` and `&gt;`.
+    ///
+    pub fn parse(value: &str) -> Self {
+        match value
+            .strip_prefix('<')
+            .and_then(|entity| entity.strip_suffix('>'))
+        {
+            Some(entity) => {
+                let target = entity.split('|').next().unwrap_or(entity);
+                if let Some(user_id) = target.strip_prefix('@') {
+                    SlackCommandArgValue::User(user_id.into())
+                } else if let Some(channel_id) = target.strip_prefix('#') {
+                    SlackCommandArgValue::Channel(channel_id.into())
+                } else if let Some(user_group_id) = target.strip_prefix("!subteam^") {
+                    SlackCommandArgValue::UserGroup(user_group_id.into())
+                } else if let Some(special) = target.strip_prefix('!') {
+                    SlackCommandArgValue::Special(special.into())
+                } else {
+                    SlackCommandArgValue::Url(Self::unescape(target))
+                }
+            }
+            None => SlackCommandArgValue::Text(Self::unescape(value)),
+        }
+    }
+
+    fn unescape(value: &str) -> String {
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&")
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            SlackCommandArgValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_user_id(&self) -> Option<&SlackUserId> {
+        match self {
+            SlackCommandArgValue::User(user_id) => Some(user_id),
+            _ => None,
+        }
+    }
+
+    pub fn as_channel_id(&self) -> Option<&SlackChannelId> {
+        match self {
+            SlackCommandArgValue::Channel(channel_id) => Some(channel_id),
+            _ => None,
+        }
+    }
+
+    pub fn as_user_group_id(&self) -> Option<&SlackUserGroupId> {
+        match self {
+            SlackCommandArgValue::UserGroup(user_group_id) => Some(user_group_id),
+            _ => None,
+        }
+    }
+
+    pub fn as_url(&self) -> Option<&str> {
+        match self {
+            SlackCommandArgValue::Url(url) => Some(url),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SlackCommandArgValue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            SlackCommandArgValue::Text(text) => write!(f, "{}", text),
+            SlackCommandArgValue::User(user_id) => write!(f, "<@{}>", user_id.0),
+            SlackCommandArgValue::Channel(channel_id) => write!(f, "<#{}>", channel_id.0),
+            SlackCommandArgValue::UserGroup(user_group_id) => {
+                write!(f, "<!subteam^{}>", user_group_id.0)
+            }
+            SlackCommandArgValue::Url(url) => write!(f, "<{}>", url),
+            SlackCommandArgValue::Special(special) => write!(f, "<!{}>", special),
+        }
+    }
+}
+
+fn closing_quote(opening: char) -> Option<char> {
+    match opening {
+        '"' => Some('"'),
+        '\'' => Some('\''),
+        '“' => Some('”'),
+        '‘' => Some('’'),
+        _ => None,
+    }
+}
+
+///
+/// Splits a command text into tokens by whitespaces respecting quotes (including the "smart" quotes
+/// produced by Slack clients), backslash escapes and Slack escaped entities.
+///
+pub fn tokenize_command_text(text: &str) -> Result<Vec<SlackCommandToken>, SlackCommandArgsError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quoted = false;
+    let mut closing: Option<char> = None;
+    let mut in_entity = false;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if let Some(closing_char) = closing {
+            if c == closing_char {
+                closing = None;
+            } else if c == '\\' {
+                current.extend(chars.next());
+            } else {
+                current.push(c);
+            }
+        } else if in_entity {
+            current.push(c);
+            in_entity = c != '>';
+        } else if c.is_whitespace() {
+            if in_token {
+                tokens.push(SlackCommandToken {
+                    text: std::mem::take(&mut current),
+                    quoted,
+                });
+                in_token = false;
+                quoted = false;
+            }
+        } else if let Some(closing_char) = closing_quote(c).filter(|_| !in_token) {
+            closing = Some(closing_char);
+            in_token = true;
+            quoted = true;
+        } else {
+            in_token = true;
+            match c {
+                '\\' => current.extend(chars.next()),
+                '<' => {
+                    in_entity = true;
+                    current.push(c);
+                }
+                _ => current.push(c),
+            }
+        }
+    }
+
+    if closing.is_some() {
+        return Err(SlackCommandArgsError::UnterminatedQuote);
+    }
+
+    if in_token {
+        tokens.push(SlackCommandToken {
+            text: current,
+            quoted,
+        });
+    }
+
+    Ok(tokens)
+}
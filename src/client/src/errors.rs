@@ -1,4 +1,5 @@
 use rsb_derive::Builder;
+use slack_morphism_models::blocks::SlackBlockKitViolation;
 use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -10,6 +11,8 @@ pub enum SlackClientError {
     EndOfStream(SlackClientEndOfStreamError),
     SystemError(SlackClientSystemError),
     ProtocolError(SlackClientProtocolError),
+    ResponseUrlError(SlackClientResponseUrlError),
+    BlockKitValidationError(SlackClientBlockKitValidationError),
 }
 
 impl SlackClientError {
@@ -28,6 +31,8 @@ impl Display for SlackClientError {
             SlackClientError::EndOfStream(ref err) => err.fmt(f),
             SlackClientError::ProtocolError(ref err) => err.fmt(f),
             SlackClientError::SystemError(ref err) => err.fmt(f),
+            SlackClientError::ResponseUrlError(ref err) => err.fmt(f),
+            SlackClientError::BlockKitValidationError(ref err) => err.fmt(f),
         }
     }
 }
@@ -40,6 +45,8 @@ impl Error for SlackClientError {
             SlackClientError::EndOfStream(ref err) => Some(err),
             SlackClientError::ProtocolError(ref err) => Some(err),
             SlackClientError::SystemError(ref err) => Some(err),
+            SlackClientError::ResponseUrlError(ref err) => Some(err),
+            SlackClientError::BlockKitValidationError(ref err) => Some(err),
         }
     }
 }
@@ -72,7 +79,12 @@ pub struct SlackClientHttpError {
 
 impl Display for SlackClientHttpError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "Slack HTTP error: {}", self)
+        write!(
+            f,
+            "Slack HTTP error status: {}. Body: '{}'",
+            self.status_code,
+            SlackClientError::option_to_string(&self.http_response_body)
+        )
     }
 }
 
@@ -97,7 +109,11 @@ pub struct SlackClientProtocolError {
 
 impl Display for SlackClientProtocolError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "Slack protocol error: {}", self)
+        write!(
+            f,
+            "Slack protocol error: {}. Body: '{}'",
+            self.json_error, self.http_response_body
+        )
     }
 }
 
@@ -119,3 +135,55 @@ impl Display for SlackClientSystemError {
 }
 
 impl std::error::Error for SlackClientSystemError {}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SlackResponseUrlErrorReason {
+    Expired,
+    UsesExceeded,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackClientResponseUrlError {
+    pub reason: SlackResponseUrlErrorReason,
+    pub http_response_body: Option<String>,
+}
+
+impl Display for SlackClientResponseUrlError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self.reason {
+            SlackResponseUrlErrorReason::Expired => write!(
+                f,
+                "Slack response URL has expired. Body: '{}'",
+                SlackClientError::option_to_string(&self.http_response_body)
+            ),
+            SlackResponseUrlErrorReason::UsesExceeded => write!(
+                f,
+                "Slack response URL has been used the maximum number of times. Body: '{}'",
+                SlackClientError::option_to_string(&self.http_response_body)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SlackClientResponseUrlError {}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackClientBlockKitValidationError {
+    pub violations: Vec<SlackBlockKitViolation>,
+}
+
+impl Display for SlackClientBlockKitValidationError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Slack Block Kit validation error: {}",
+            self.violations
+                .iter()
+                .map(|violation| violation.to_string())
+                .collect::<Vec<String>>()
+                .join("; ")
+        )
+    }
+}
+
+impl std::error::Error for SlackClientBlockKitValidationError {}
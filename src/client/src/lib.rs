@@ -89,9 +89,13 @@ pub use scroller::*;
 pub use token::*;
 
 pub mod api;
+pub mod block_kit_builder;
 mod client;
+pub mod command_args;
 pub mod errors;
 pub mod listener;
+pub mod private_metadata;
+pub mod router;
 mod scroller;
 pub mod signature_verifier;
 mod token;
@@ -1,11 +1,16 @@
 use crate::{SlackClient, SlackClientHttpConnector};
+use futures::future::{BoxFuture, FutureExt};
 use rsb_derive::Builder;
+use slack_morphism_models::SlackTeamId;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::ops::Deref;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-type UserStatesMap = HashMap<TypeId, Box<dyn Any + Send + Sync + 'static>>;
+type UserStatesMap = HashMap<TypeId, Arc<dyn Any + Send + Sync + 'static>>;
 
 pub struct SlackClientEventsListenerEnvironment<SCHC>
 where
@@ -14,23 +19,54 @@ where
     pub client: Arc<SlackClient<SCHC>>,
     pub error_handler: BoxedErrorHandler<SCHC>,
     pub user_state_storage: Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    pub pre_handler_hooks: Vec<BoxedPreHandlerHook>,
+    pub post_handler_hooks: Vec<BoxedPostHandlerHook>,
 }
 
 impl<SCHC> SlackClientEventsListenerEnvironment<SCHC>
 where
-    SCHC: SlackClientHttpConnector + Send + Clone + Sync,
+    SCHC: SlackClientHttpConnector + Send + Clone + Sync + 'static,
 {
     pub fn new(client: Arc<SlackClient<SCHC>>) -> Self {
         Self {
             client,
-            error_handler: Box::new(Self::empty_error_handler),
+            error_handler: Arc::new(|err, _context, client, user_state_storage| {
+                let status_code = Self::empty_error_handler(err, client, user_state_storage);
+                async move { Self::error_response(status_code) }.boxed()
+            }),
             user_state_storage: Arc::new(RwLock::new(SlackClientEventsUserStateStorage::new())),
+            pre_handler_hooks: Vec::new(),
+            post_handler_hooks: Vec::new(),
         }
     }
 
     pub fn with_error_handler(self, error_handler: ErrorHandler<SCHC>) -> Self {
+        self.with_async_error_handler(move |err, _context, client, user_state_storage| {
+            let status_code = error_handler(err, client, user_state_storage);
+            async move { Self::error_response(status_code) }
+        })
+    }
+
+    ///
+    /// Error handler that has access to the request context and returns a full HTTP response to Slack
+    ///
+    pub fn with_async_error_handler<H, HF>(self, error_handler: H) -> Self
+    where
+        H: Fn(
+                Box<dyn std::error::Error + Send + Sync + 'static>,
+                SlackClientEventsListenerContext,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = http::Response<String>> + 'static + Send,
+    {
         Self {
-            error_handler: Box::new(error_handler),
+            error_handler: Arc::new(move |err, context, client, user_state_storage| {
+                error_handler(err, context, client, user_state_storage).boxed()
+            }),
             ..self
         }
     }
@@ -43,6 +79,16 @@ where
         http::StatusCode::BAD_REQUEST
     }
 
+    fn error_response(status_code: http::StatusCode) -> http::Response<String> {
+        let mut response = http::Response::new(String::new());
+        *response.status_mut() = status_code;
+        response
+    }
+
+    pub fn user_state<T: Send + Sync + 'static>(&self) -> Option<SlackUserState<T>> {
+        SlackUserState::extract(&self.user_state_storage)
+    }
+
     pub fn with_user_state<T: Send + Sync + 'static>(self, state: T) -> Self {
         self.user_state_storage
             .write()
@@ -50,6 +96,83 @@ where
             .set_user_state(state);
         self
     }
+
+    ///
+    /// Hooks executed before calling listener handlers (e.g. for logging and metrics)
+    ///
+    pub fn with_pre_handler_hook<H, HF>(mut self, hook: H) -> Self
+    where
+        H: Fn(SlackClientEventsListenerContext) -> HF + 'static + Send + Sync,
+        HF: Future<Output = ()> + 'static + Send,
+    {
+        self.pre_handler_hooks
+            .push(Arc::new(move |context| hook(context).boxed()));
+        self
+    }
+
+    ///
+    /// Hooks executed after listener handlers with the outcome of handling
+    ///
+    pub fn with_post_handler_hook<H, HF>(mut self, hook: H) -> Self
+    where
+        H: Fn(SlackClientEventsListenerContext, SlackClientEventsListenerOutcome) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = ()> + 'static + Send,
+    {
+        self.post_handler_hooks
+            .push(Arc::new(move |context, outcome| {
+                hook(context, outcome).boxed()
+            }));
+        self
+    }
+
+    pub async fn handle_error(
+        &self,
+        err: Box<dyn std::error::Error + Send + Sync + 'static>,
+        context: SlackClientEventsListenerContext,
+    ) -> http::Response<String> {
+        (self.error_handler)(
+            err,
+            context,
+            self.client.clone(),
+            self.user_state_storage.clone(),
+        )
+        .await
+    }
+
+    pub async fn run_pre_handler_hooks(&self, context: &SlackClientEventsListenerContext) {
+        for hook in &self.pre_handler_hooks {
+            hook(context.clone()).await;
+        }
+    }
+
+    pub async fn run_post_handler_hooks(
+        &self,
+        context: &SlackClientEventsListenerContext,
+        outcome: &SlackClientEventsListenerOutcome,
+    ) {
+        for hook in &self.post_handler_hooks {
+            hook(context.clone(), outcome.clone()).await;
+        }
+    }
+}
+
+///
+/// The context of an incoming Slack request, available for error handlers and hooks
+///
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackClientEventsListenerContext {
+    pub path: String,
+    pub team_id: Option<SlackTeamId>,
+    pub event_type: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackClientEventsListenerOutcome {
+    pub status_code: http::StatusCode,
+    pub elapsed: Duration,
 }
 
 pub struct SlackClientEventsUserStateStorage {
@@ -66,12 +189,23 @@ impl SlackClientEventsUserStateStorage {
     pub fn get_user_state<T: Send + Sync + 'static>(&self) -> Option<&T> {
         self.user_state_map
             .get(&TypeId::of::<T>())
-            .and_then(|boxed| (&**boxed as &(dyn Any + 'static)).downcast_ref())
+            .and_then(|state| (&**state as &(dyn Any + 'static)).downcast_ref())
+    }
+
+    ///
+    /// Returns a shared handle to the state that doesn't borrow the storage,
+    /// so it can be safely held across `.await` points
+    ///
+    pub fn get_state<T: Send + Sync + 'static>(&self) -> Option<SlackUserState<T>> {
+        self.user_state_map
+            .get(&TypeId::of::<T>())
+            .and_then(|state| state.clone().downcast::<T>().ok())
+            .map(SlackUserState)
     }
 
     pub fn set_user_state<T: Send + Sync + 'static>(&mut self, state: T) {
         self.user_state_map
-            .insert(TypeId::of::<T>(), Box::new(state));
+            .insert(TypeId::of::<T>(), Arc::new(state));
     }
 
     pub fn len(&self) -> usize {
@@ -83,7 +217,123 @@ impl SlackClientEventsUserStateStorage {
     }
 }
 
-pub type BoxedErrorHandler<SCHC> = Box<ErrorHandler<SCHC>>;
+///
+/// Typed user state registered with `with_user_state`, backed by `Arc<T>`.
+/// Use interior mutability (e.g. `tokio::sync::Mutex` or atomics) inside `T` for mutable states.
+///
+#[derive(Debug)]
+pub struct SlackUserState<T>(pub Arc<T>);
+
+impl<T> SlackUserState<T>
+where
+    T: Send + Sync + 'static,
+{
+    ///
+    /// Extracts the state from the storage passed to handlers. The storage lock is released
+    /// immediately, so the result can be held across `.await` points.
+    ///
+    pub fn extract(
+        user_state_storage: &Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) -> Option<Self> {
+        user_state_storage.read().unwrap().get_state::<T>()
+    }
+}
+
+impl<T> Clone for SlackUserState<T> {
+    fn clone(&self) -> Self {
+        SlackUserState(self.0.clone())
+    }
+}
+
+impl<T> Deref for SlackUserState<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+///
+/// Adapts a handler receiving a typed `SlackUserState<T>` to the listener and router handler functions.
+///
+/// Returns an error if the state `T` has not been registered with `with_user_state` in the listener environment,
+/// so missing states are detected when routes are built instead of while handling events.
+///
+/// The state is resolved once when the route is built and handlers get the captured `Arc<T>`,
+/// so handling events doesn't lock the user state storage (the storage argument is kept only
+/// to fit the listener and router handler signatures). States must be registered before building routes.
+///
+#[allow(clippy::type_complexity)]
+pub fn with_user_state_fn<T, E, R, SCHC, H, HF>(
+    environment: &SlackClientEventsListenerEnvironment<SCHC>,
+    handler: H,
+) -> Result<
+    impl Fn(
+            E,
+            Arc<SlackClient<SCHC>>,
+            Arc<RwLock<SlackClientEventsUserStateStorage>>,
+        ) -> BoxFuture<'static, R>
+        + 'static
+        + Send
+        + Sync
+        + Clone,
+    SlackUserStateNotRegisteredError,
+>
+where
+    T: Send + Sync + 'static,
+    E: 'static,
+    SCHC: SlackClientHttpConnector + Send + Clone + Sync + 'static,
+    H: Fn(E, Arc<SlackClient<SCHC>>, SlackUserState<T>) -> HF + 'static + Send + Sync,
+    HF: Future<Output = R> + 'static + Send,
+{
+    let registered_user_state = environment
+        .user_state::<T>()
+        .ok_or_else(|| SlackUserStateNotRegisteredError::new(std::any::type_name::<T>().into()))?;
+    let shared_handler = Arc::new(handler);
+    Ok(move |event, client, _user_state_storage| {
+        shared_handler(event, client, registered_user_state.clone()).boxed()
+    })
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackUserStateNotRegisteredError {
+    pub type_name: String,
+}
+
+impl Display for SlackUserStateNotRegisteredError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "User state {} has not been registered in the listener environment",
+            self.type_name
+        )
+    }
+}
+
+impl std::error::Error for SlackUserStateNotRegisteredError {}
+
+pub type BoxedErrorHandler<SCHC> = Arc<
+    dyn Fn(
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+            SlackClientEventsListenerContext,
+            Arc<SlackClient<SCHC>>,
+            Arc<RwLock<SlackClientEventsUserStateStorage>>,
+        ) -> BoxFuture<'static, http::Response<String>>
+        + Send
+        + Sync,
+>;
+
+pub type BoxedPreHandlerHook =
+    Arc<dyn Fn(SlackClientEventsListenerContext) -> BoxFuture<'static, ()> + Send + Sync>;
+
+pub type BoxedPostHandlerHook = Arc<
+    dyn Fn(
+            SlackClientEventsListenerContext,
+            SlackClientEventsListenerOutcome,
+        ) -> BoxFuture<'static, ()>
+        + Send
+        + Sync,
+>;
 
 pub type ErrorHandler<SCHC> = fn(
     Box<dyn std::error::Error + Send + Sync + 'static>,
@@ -124,6 +374,27 @@ impl SlackInteractionEventsListenerConfig {
     pub const DEFAULT_EVENTS_URL_VALUE: &'static str = "/interaction";
 }
 
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackOptionsLoadListenerConfig {
+    pub events_signing_secret: String,
+    #[default = "SlackOptionsLoadListenerConfig::DEFAULT_EVENTS_URL_VALUE.into()"]
+    pub events_path: String,
+}
+
+impl SlackOptionsLoadListenerConfig {
+    pub const DEFAULT_EVENTS_URL_VALUE: &'static str = "/options";
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackLazyHandlersConfig {
+    #[default = "SlackLazyHandlersConfig::DEFAULT_MAX_CONCURRENT_HANDLERS_VALUE"]
+    pub max_concurrent_handlers: usize,
+}
+
+impl SlackLazyHandlersConfig {
+    pub const DEFAULT_MAX_CONCURRENT_HANDLERS_VALUE: usize = 64;
+}
+
 #[derive(Debug, PartialEq, Clone, Builder)]
 pub struct SlackOAuthListenerConfig {
     pub client_id: String,
@@ -158,3 +429,28 @@ impl SlackOAuthListenerConfig {
         )
     }
 }
+
+#[test]
+fn check_typed_user_state() {
+    struct CounterState {
+        value: u64,
+    }
+
+    let storage = Arc::new(RwLock::new(SlackClientEventsUserStateStorage::new()));
+    storage
+        .write()
+        .unwrap()
+        .set_user_state(CounterState { value: 42 });
+
+    let state = SlackUserState::<CounterState>::extract(&storage).unwrap();
+    assert_eq!(state.value, 42);
+    assert!(SlackUserState::<String>::extract(&storage).is_none());
+    assert_eq!(
+        storage
+            .read()
+            .unwrap()
+            .get_user_state::<CounterState>()
+            .map(|s| s.value),
+        Some(42)
+    );
+}
@@ -1,5 +1,7 @@
 pub use super::api::*; // Slack Web API methods (chat, users, views, etc)
+pub use super::command_args::*;
 pub use super::listener::*;
+pub use super::router::*;
 pub use super::*; // access to network/client functions // Slack Events API listener (routes) implementation
 
 pub use slack_morphism_models::blocks::*; // Slack Block Kit models
@@ -0,0 +1,355 @@
+//!
+//! Typed `private_metadata` for views to carry your own state between
+//! `views.open`, `views.push`, `views.update` and `view_submission` events
+//!
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use ring::hmac;
+use rsb_derive::Builder;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use slack_morphism_models::blocks::*;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+
+///
+/// Encodes your values to `private_metadata` of views and decodes them back:
+/// values are serialized to JSON, optionally compressed, encoded using URL-safe base64
+/// and optionally signed with HMAC-SHA256 to detect tampering.
+///
+/// Encoded values are prefixed with their format (`j` for JSON, `z` for compressed JSON),
+/// so the codec decodes both compressed and uncompressed values.
+///
+#[derive(Debug, Clone)]
+pub struct SlackPrivateMetadataCodec {
+    compression: bool,
+    signing_key: Option<hmac::Key>,
+    max_len: usize,
+}
+
+impl SlackPrivateMetadataCodec {
+    const JSON_FORMAT: &'static str = "j";
+    const COMPRESSED_JSON_FORMAT: &'static str = "z";
+    const SEPARATOR: char = '.';
+    ///
+    /// Limits decompressed values, so crafted payloads can't exhaust memory
+    ///
+    const MAX_DECOMPRESSED_LEN: usize = 1024 * 1024;
+
+    pub fn new() -> Self {
+        Self {
+            compression: false,
+            signing_key: None,
+            max_len: SLACK_VIEW_PRIVATE_METADATA_MAX_LENGTH,
+        }
+    }
+
+    pub fn with_compression(self) -> Self {
+        Self {
+            compression: true,
+            ..self
+        }
+    }
+
+    ///
+    /// Signs encoded values and requires valid signatures to decode them
+    ///
+    pub fn with_signing_secret(self, secret: &str) -> Self {
+        Self {
+            signing_key: Some(hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes())),
+            ..self
+        }
+    }
+
+    ///
+    /// Limits the length of encoded values (Slack's limit of 3000 characters by default)
+    ///
+    pub fn with_max_len(self, max_len: usize) -> Self {
+        Self { max_len, ..self }
+    }
+
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<String, SlackPrivateMetadataError> {
+        let json = serde_json::to_vec(value).map_err(|err| {
+            SlackPrivateMetadataError::EncodeError(SlackPrivateMetadataEncodeError::new(
+                err.to_string(),
+            ))
+        })?;
+
+        let (format, data) = if self.compression {
+            (Self::COMPRESSED_JSON_FORMAT, Self::compress(&json)?)
+        } else {
+            (Self::JSON_FORMAT, json)
+        };
+
+        let payload = format!(
+            "{}{}{}",
+            format,
+            Self::SEPARATOR,
+            base64::encode_config(&data, base64::URL_SAFE_NO_PAD)
+        );
+
+        let encoded = match &self.signing_key {
+            Some(key) => {
+                let signature = hmac::sign(key, payload.as_bytes());
+                format!(
+                    "{}{}{}",
+                    payload,
+                    Self::SEPARATOR,
+                    base64::encode_config(signature.as_ref(), base64::URL_SAFE_NO_PAD)
+                )
+            }
+            None => payload,
+        };
+
+        if encoded.chars().count() > self.max_len {
+            Err(SlackPrivateMetadataError::TooLongError(
+                SlackPrivateMetadataTooLongError::new(encoded.chars().count(), self.max_len),
+            ))
+        } else {
+            Ok(encoded)
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(
+        &self,
+        private_metadata: &str,
+    ) -> Result<T, SlackPrivateMetadataError> {
+        let mut parts = private_metadata.splitn(3, Self::SEPARATOR);
+        let format = parts.next().unwrap_or_default();
+        let data = parts
+            .next()
+            .ok_or_else(|| Self::decode_error("unknown private metadata format"))?;
+
+        if let Some(key) = &self.signing_key {
+            let signature = parts
+                .next()
+                .and_then(|signature| {
+                    base64::decode_config(signature, base64::URL_SAFE_NO_PAD).ok()
+                })
+                .ok_or_else(Self::wrong_signature_error)?;
+
+            let payload_len = format.len() + data.len() + 1;
+            hmac::verify(
+                key,
+                &private_metadata.as_bytes()[..payload_len],
+                signature.as_slice(),
+            )
+            .map_err(|_| Self::wrong_signature_error())?;
+        }
+
+        let data = base64::decode_config(data, base64::URL_SAFE_NO_PAD)
+            .map_err(|err| Self::decode_error(&err.to_string()))?;
+
+        let json = match format {
+            Self::JSON_FORMAT => data,
+            Self::COMPRESSED_JSON_FORMAT => Self::decompress(&data)?,
+            _ => return Err(Self::decode_error("unknown private metadata format")),
+        };
+
+        serde_json::from_slice(&json).map_err(|err| Self::decode_error(&err.to_string()))
+    }
+
+    ///
+    /// Decodes `private_metadata` of a modal or a home tab view, if it has one
+    ///
+    pub fn decode_view<T: DeserializeOwned>(
+        &self,
+        view: &SlackView,
+    ) -> Result<Option<T>, SlackPrivateMetadataError> {
+        let private_metadata = match view {
+            SlackView::Modal(modal_view) => modal_view.private_metadata.as_ref(),
+            SlackView::Home(home_view) => home_view.private_metadata.as_ref(),
+            SlackView::Other(_) => None,
+        };
+
+        private_metadata
+            .map(|private_metadata| self.decode(private_metadata))
+            .transpose()
+    }
+
+    fn compress(data: &[u8]) -> Result<Vec<u8>, SlackPrivateMetadataError> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(data)
+            .and_then(|_| encoder.finish())
+            .map_err(|err| {
+                SlackPrivateMetadataError::EncodeError(SlackPrivateMetadataEncodeError::new(
+                    err.to_string(),
+                ))
+            })
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>, SlackPrivateMetadataError> {
+        let mut decompressed = Vec::new();
+        // Reading one byte more than the limit to detect exceeding it
+        DeflateDecoder::new(data)
+            .take(Self::MAX_DECOMPRESSED_LEN as u64 + 1)
+            .read_to_end(&mut decompressed)
+            .map_err(|err| Self::decode_error(&err.to_string()))?;
+
+        if decompressed.len() > Self::MAX_DECOMPRESSED_LEN {
+            Err(Self::decode_error(&format!(
+                "decompressed private metadata exceeds {} bytes",
+                Self::MAX_DECOMPRESSED_LEN
+            )))
+        } else {
+            Ok(decompressed)
+        }
+    }
+
+    fn wrong_signature_error() -> SlackPrivateMetadataError {
+        SlackPrivateMetadataError::WrongSignatureError(
+            SlackPrivateMetadataWrongSignatureError::new(),
+        )
+    }
+
+    fn decode_error(message: &str) -> SlackPrivateMetadataError {
+        SlackPrivateMetadataError::DecodeError(SlackPrivateMetadataDecodeError::new(message.into()))
+    }
+}
+
+impl Default for SlackPrivateMetadataCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub enum SlackPrivateMetadataError {
+    EncodeError(SlackPrivateMetadataEncodeError),
+    TooLongError(SlackPrivateMetadataTooLongError),
+    DecodeError(SlackPrivateMetadataDecodeError),
+    WrongSignatureError(SlackPrivateMetadataWrongSignatureError),
+}
+
+impl Display for SlackPrivateMetadataError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match *self {
+            SlackPrivateMetadataError::EncodeError(ref err) => err.fmt(f),
+            SlackPrivateMetadataError::TooLongError(ref err) => err.fmt(f),
+            SlackPrivateMetadataError::DecodeError(ref err) => err.fmt(f),
+            SlackPrivateMetadataError::WrongSignatureError(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for SlackPrivateMetadataError {
+    fn cause(&self) -> Option<&dyn Error> {
+        match *self {
+            SlackPrivateMetadataError::EncodeError(ref err) => Some(err),
+            SlackPrivateMetadataError::TooLongError(ref err) => Some(err),
+            SlackPrivateMetadataError::DecodeError(ref err) => Some(err),
+            SlackPrivateMetadataError::WrongSignatureError(ref err) => Some(err),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackPrivateMetadataEncodeError {
+    pub message: String,
+}
+
+impl Display for SlackPrivateMetadataEncodeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack private metadata encode error: {}", self.message)
+    }
+}
+
+impl Error for SlackPrivateMetadataEncodeError {}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackPrivateMetadataTooLongError {
+    pub len: usize,
+    pub max_len: usize,
+}
+
+impl Display for SlackPrivateMetadataTooLongError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Slack private metadata is too long: {}/{} characters",
+            self.len, self.max_len
+        )
+    }
+}
+
+impl Error for SlackPrivateMetadataTooLongError {}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackPrivateMetadataDecodeError {
+    pub message: String,
+}
+
+impl Display for SlackPrivateMetadataDecodeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack private metadata decode error: {}", self.message)
+    }
+}
+
+impl Error for SlackPrivateMetadataDecodeError {}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackPrivateMetadataWrongSignatureError {}
+
+impl Display for SlackPrivateMetadataWrongSignatureError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack private metadata signature is absent or wrong")
+    }
+}
+
+impl Error for SlackPrivateMetadataWrongSignatureError {}
+
+#[test]
+fn check_private_metadata_codec() {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct FlowState {
+        step: usize,
+        title: String,
+    }
+
+    let state = FlowState {
+        step: 2,
+        title: "Broken build ".repeat(100),
+    };
+
+    let codec = SlackPrivateMetadataCodec::new()
+        .with_compression()
+        .with_signing_secret("test-secret");
+
+    let encoded = codec.encode(&state).unwrap();
+    assert!(encoded.len() < SLACK_VIEW_PRIVATE_METADATA_MAX_LENGTH);
+    assert_eq!(codec.decode::<FlowState>(&encoded).unwrap(), state);
+
+    let tampered = encoded.replacen("z.", "j.", 1);
+    assert!(matches!(
+        codec.decode::<FlowState>(&tampered),
+        Err(SlackPrivateMetadataError::WrongSignatureError(_))
+    ));
+
+    assert!(matches!(
+        SlackPrivateMetadataCodec::new().encode(&"x".repeat(3000)),
+        Err(SlackPrivateMetadataError::TooLongError(_))
+    ));
+}
+
+#[test]
+fn check_private_metadata_decompression_limit() {
+    let codec = SlackPrivateMetadataCodec::new()
+        .with_compression()
+        .with_max_len(usize::MAX);
+
+    let encoded = codec
+        .encode(&"0".repeat(SlackPrivateMetadataCodec::MAX_DECOMPRESSED_LEN))
+        .unwrap();
+    assert!(encoded.len() < SLACK_VIEW_PRIVATE_METADATA_MAX_LENGTH);
+    assert!(matches!(
+        codec.decode::<String>(&encoded),
+        Err(SlackPrivateMetadataError::DecodeError(_))
+    ));
+
+    let encoded = codec.encode(&"0".repeat(1000)).unwrap();
+    assert_eq!(codec.decode::<String>(&encoded).unwrap(), "0".repeat(1000));
+}
@@ -0,0 +1,174 @@
+use crate::command_args::*;
+use crate::errors::*;
+use crate::listener::SlackClientEventsUserStateStorage;
+use crate::router::*;
+use crate::{SlackClient, SlackClientHttpConnector};
+use futures::future::{BoxFuture, FutureExt};
+use rvstruct::ValueStruct;
+use slack_morphism_models::events::*;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+pub type SlackCommandRouteResult =
+    Result<SlackCommandEventResponse, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+///
+/// Routes slash commands to handlers by command names (e.g. `/deploy`).
+/// The first matching route wins. Commands without a matching route go to the fallback handler,
+/// or produce an error if there is no fallback (the same as commands filtered by middlewares).
+///
+pub struct SlackCommandEventsRouter<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    middlewares: Vec<SlackRouteMiddleware<SlackCommandEvent, SCHC>>,
+    command_routes: SlackRoutes<SlackCommandEvent, SlackCommandRouteResult, SCHC>,
+    fallback: Option<SlackRouteHandler<SlackCommandEvent, SlackCommandRouteResult, SCHC>>,
+}
+
+impl<SCHC> SlackCommandEventsRouter<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            middlewares: Vec::new(),
+            command_routes: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    pub fn with_middleware<H, HF>(mut self, middleware: H) -> Self
+    where
+        H: Fn(
+                SlackCommandEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = Option<SlackCommandEvent>> + 'static + Send,
+    {
+        self.middlewares.push(box_route_handler(middleware));
+        self
+    }
+
+    pub fn on_command<M, H, HF>(mut self, command: M, handler: H) -> Self
+    where
+        M: Into<SlackRouteMatcher>,
+        H: Fn(
+                SlackCommandEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = SlackCommandRouteResult> + 'static + Send,
+    {
+        self.command_routes
+            .push((command.into(), box_route_handler(handler)));
+        self
+    }
+
+    ///
+    /// Routes the command by the name of `spec` and parses its text before calling the handler.
+    /// Parsing errors and help requests are responded with the usage and help generated from `spec`.
+    ///
+    pub fn on_command_with_args<H, HF>(mut self, spec: SlackCommandSpec, handler: H) -> Self
+    where
+        H: Fn(
+                SlackCommandEvent,
+                SlackCommandArgs,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = SlackCommandRouteResult> + 'static + Send,
+    {
+        let matcher = SlackRouteMatcher::from(spec.name.clone());
+        let shared_spec = Arc::new(spec);
+        self.command_routes.push((
+            matcher,
+            Arc::new(
+                move |event, client, user_state_storage| match shared_spec.parse(&event) {
+                    Ok(args) => handler(event, args, client, user_state_storage).boxed(),
+                    Err(err) => {
+                        let error_response = shared_spec.error_response(&event, &err);
+                        async move { Ok(error_response) }.boxed()
+                    }
+                },
+            ),
+        ));
+        self
+    }
+
+    pub fn with_fallback<H, HF>(mut self, handler: H) -> Self
+    where
+        H: Fn(
+                SlackCommandEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = SlackCommandRouteResult> + 'static + Send,
+    {
+        self.fallback = Some(box_route_handler(handler));
+        self
+    }
+
+    pub async fn route(
+        &self,
+        event: SlackCommandEvent,
+        client: Arc<SlackClient<SCHC>>,
+        user_state_storage: Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) -> SlackCommandRouteResult {
+        match apply_route_middlewares(&self.middlewares, event, &client, &user_state_storage).await
+        {
+            Some(command_event) => {
+                match find_route(&self.command_routes, command_event.command.value())
+                    .or(self.fallback.as_ref())
+                {
+                    Some(handler) => handler(command_event, client, user_state_storage).await,
+                    None => Err(SlackClientError::SystemError(SlackClientSystemError::new(
+                        format!(
+                            "No route found for Slack command: {}",
+                            command_event.command.value()
+                        ),
+                    ))
+                    .into()),
+                }
+            }
+            None => Err(SlackClientError::SystemError(SlackClientSystemError::new(
+                "Slack command has been filtered out by a router middleware".into(),
+            ))
+            .into()),
+        }
+    }
+
+    ///
+    /// Converts the router to a handler function for `command_events_service_fn`
+    ///
+    pub fn into_handler(
+        self,
+    ) -> impl Fn(
+        SlackCommandEvent,
+        Arc<SlackClient<SCHC>>,
+        Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) -> BoxFuture<'static, SlackCommandRouteResult>
+           + 'static
+           + Send
+           + Sync
+           + Clone {
+        let router = Arc::new(self);
+        move |event, client, user_state_storage| {
+            let thread_router = router.clone();
+            async move { thread_router.route(event, client, user_state_storage).await }.boxed()
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use crate::api::*;
+use crate::errors::*;
+use crate::router::*;
+use crate::{ClientResult, SlackApiToken, SlackClient, SlackClientHttpConnector};
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use log::*;
+use slack_morphism_models::blocks::*;
+use slack_morphism_models::events::*;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+///
+/// Publishes App Home tabs rendered per user, skipping publishes of unchanged views.
+///
+/// Views are published when users open their home tabs (register the publisher with
+/// `SlackPushEventsRouter::on_home_tab`) and when you republish them with `publish` or `republish`.
+/// Publishes carry the `hash` of the last known view, so a view updated concurrently isn't overwritten
+/// (`SlackHomeTabPublishResult::HashConflict`).
+///
+pub struct SlackHomeTabPublisher<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    renderer: SlackHomeTabRenderer<SCHC>,
+    token_resolver: Option<SlackTokenResolver>,
+    published_views: SlackHomeTabPublishedViews,
+}
+
+pub type SlackHomeTabRenderer<SCHC> = Arc<
+    dyn Fn(SlackUserId, Arc<SlackClient<SCHC>>) -> BoxFuture<'static, ClientResult<SlackHomeView>>
+        + Send
+        + Sync,
+>;
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackHomeTabPublishResult {
+    Published(SlackStatefulView),
+    Unchanged,
+    HashConflict,
+}
+
+impl<SCHC> SlackHomeTabPublisher<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    pub fn new<R, RF>(renderer: R) -> Self
+    where
+        R: Fn(SlackUserId, Arc<SlackClient<SCHC>>) -> RF + Send + Sync + 'static,
+        RF: Future<Output = ClientResult<SlackHomeView>> + Send + 'static,
+    {
+        Self {
+            renderer: Arc::new(move |user_id, client| renderer(user_id, client).boxed()),
+            token_resolver: None,
+            published_views: SlackHomeTabPublishedViews::new(),
+        }
+    }
+
+    ///
+    /// A token to publish views when users open their home tabs
+    ///
+    pub fn with_token(self, token: SlackApiToken) -> Self {
+        self.with_token_resolver(move |_| Some(token.clone()))
+    }
+
+    ///
+    /// Tokens to publish views when users open their home tabs for apps installed to multiple workspaces
+    ///
+    pub fn with_token_resolver<F>(self, token_resolver: F) -> Self
+    where
+        F: Fn(&SlackTeamId) -> Option<SlackApiToken> + Send + Sync + 'static,
+    {
+        Self {
+            token_resolver: Some(Arc::new(token_resolver)),
+            ..self
+        }
+    }
+
+    ///
+    /// Renders and publishes the home tab of a user unless it is unchanged since the last publish
+    ///
+    pub async fn publish(
+        &self,
+        client: &Arc<SlackClient<SCHC>>,
+        token: &SlackApiToken,
+        user_id: SlackUserId,
+    ) -> ClientResult<SlackHomeTabPublishResult> {
+        let view_hash = self.published_views.view_hash(&user_id);
+        self.publish_view(client, token, user_id, view_hash, false)
+            .await
+    }
+
+    ///
+    /// Publishes home tabs of users one by one, use `SlackHomeTabPublisherExt::republish_throttled`
+    /// from `slack-morphism-hyper` to keep it within Slack rate limits
+    ///
+    pub fn republish<'a>(
+        &'a self,
+        client: &'a Arc<SlackClient<SCHC>>,
+        token: &'a SlackApiToken,
+        user_ids: Vec<SlackUserId>,
+    ) -> BoxStream<'a, (SlackUserId, ClientResult<SlackHomeTabPublishResult>)> {
+        futures::stream::iter(user_ids)
+            .then(move |user_id| async move {
+                let result = self.publish(client, token, user_id.clone()).await;
+                (user_id, result)
+            })
+            .boxed()
+    }
+
+    ///
+    /// Forgets the last published view of a user, so the next publish isn't skipped
+    ///
+    pub fn invalidate(&self, user_id: &SlackUserId) {
+        self.published_views.invalidate(user_id)
+    }
+
+    async fn publish_view(
+        &self,
+        client: &Arc<SlackClient<SCHC>>,
+        token: &SlackApiToken,
+        user_id: SlackUserId,
+        view_hash: Option<String>,
+        force: bool,
+    ) -> ClientResult<SlackHomeTabPublishResult> {
+        let view = SlackView::Home((self.renderer)(user_id.clone(), client.clone()).await?);
+        let content_hash = SlackHomeTabPublishedViews::content_hash(&view)?;
+
+        if !force && self.published_views.is_unchanged(&user_id, content_hash) {
+            return Ok(SlackHomeTabPublishResult::Unchanged);
+        }
+
+        let session = client.open_session(token);
+        match session
+            .views_publish(
+                &SlackApiViewsPublishRequest::new(user_id.clone(), view).opt_hash(view_hash),
+            )
+            .await
+        {
+            Ok(response) => {
+                self.published_views.update(
+                    user_id,
+                    content_hash,
+                    response.view.state_params.hash.clone(),
+                );
+                Ok(SlackHomeTabPublishResult::Published(response.view))
+            }
+            Err(err) => match err.downcast_ref::<SlackClientError>() {
+                Some(SlackClientError::ApiError(api_error))
+                    if api_error.code == "hash_conflict" =>
+                {
+                    self.published_views.invalidate(&user_id);
+                    Ok(SlackHomeTabPublishResult::HashConflict)
+                }
+                _ => Err(err),
+            },
+        }
+    }
+
+    pub(crate) async fn on_app_home_opened(
+        &self,
+        event: SlackEventCallback,
+        client: Arc<SlackClient<SCHC>>,
+    ) {
+        let team_id = event.team_id;
+        let app_home_opened = match event.event {
+            SlackEventCallbackBody::AppHomeOpened(app_home_opened)
+                if app_home_opened.tab == "home" =>
+            {
+                app_home_opened
+            }
+            _ => return,
+        };
+
+        let token = match self
+            .token_resolver
+            .as_ref()
+            .and_then(|token_resolver| token_resolver(&team_id))
+        {
+            Some(token) => token,
+            None => {
+                error!("No token to publish home tab for team: {}", team_id);
+                return;
+            }
+        };
+
+        // Users without published views (or with views published by others) get a new view anyway
+        let (view_hash, force) = match app_home_opened.view {
+            Some(view) => {
+                let force = self
+                    .published_views
+                    .view_hash(&app_home_opened.user)
+                    .as_ref()
+                    != Some(&view.state_params.hash);
+                (Some(view.state_params.hash), force)
+            }
+            None => (None, true),
+        };
+
+        match self
+            .publish_view(&client, &token, app_home_opened.user, view_hash, force)
+            .await
+        {
+            Ok(result) => debug!("Home tab publish result: {:?}", result),
+            Err(err) => error!("Home tab publish error: {}", err),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SlackHomeTabPublishedView {
+    content_hash: u64,
+    view_hash: String,
+}
+
+#[derive(Debug)]
+struct SlackHomeTabPublishedViews {
+    views: Mutex<HashMap<SlackUserId, SlackHomeTabPublishedView>>,
+}
+
+impl SlackHomeTabPublishedViews {
+    fn new() -> Self {
+        Self {
+            views: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn content_hash(view: &SlackView) -> ClientResult<u64> {
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(view)?.hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+
+    fn is_unchanged(&self, user_id: &SlackUserId, content_hash: u64) -> bool {
+        self.views
+            .lock()
+            .unwrap()
+            .get(user_id)
+            .iter()
+            .any(|published_view| published_view.content_hash == content_hash)
+    }
+
+    fn view_hash(&self, user_id: &SlackUserId) -> Option<String> {
+        self.views
+            .lock()
+            .unwrap()
+            .get(user_id)
+            .map(|published_view| published_view.view_hash.clone())
+    }
+
+    fn update(&self, user_id: SlackUserId, content_hash: u64, view_hash: String) {
+        self.views.lock().unwrap().insert(
+            user_id,
+            SlackHomeTabPublishedView {
+                content_hash,
+                view_hash,
+            },
+        );
+    }
+
+    fn invalidate(&self, user_id: &SlackUserId) {
+        self.views.lock().unwrap().remove(user_id);
+    }
+}
+
+#[test]
+fn check_home_tab_change_detection() {
+    let published_views = SlackHomeTabPublishedViews::new();
+    let user_id: SlackUserId = "U1".into();
+
+    let view = SlackView::Home(SlackHomeView::new(vec![SlackDividerBlock::new().into()]));
+    let updated_view = SlackView::Home(SlackHomeView::new(vec![]));
+
+    let content_hash = SlackHomeTabPublishedViews::content_hash(&view).unwrap();
+    assert!(!published_views.is_unchanged(&user_id, content_hash));
+
+    published_views.update(user_id.clone(), content_hash, "hash-1".into());
+    assert!(published_views.is_unchanged(&user_id, content_hash));
+    assert!(!published_views.is_unchanged(
+        &user_id,
+        SlackHomeTabPublishedViews::content_hash(&updated_view).unwrap()
+    ));
+    assert_eq!(published_views.view_hash(&user_id), Some("hash-1".into()));
+
+    published_views.invalidate(&user_id);
+    assert!(!published_views.is_unchanged(&user_id, content_hash));
+}
@@ -0,0 +1,437 @@
+use crate::listener::SlackClientEventsUserStateStorage;
+use crate::router::*;
+use crate::{SlackClient, SlackClientHttpConnector};
+use futures::future::{BoxFuture, FutureExt};
+use log::*;
+use rvstruct::ValueStruct;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use slack_morphism_models::blocks::*;
+use slack_morphism_models::events::*;
+use slack_morphism_models::SlackCallbackId;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+///
+/// Routes interaction events to handlers by block `action_id`s, view and shortcut `callback_id`s.
+/// The first matching route wins. Events without a matching route go to the fallback handler.
+/// Route handlers may return `()` or responses for Slack (e.g. `SlackViewSubmissionResponse` for view submissions).
+///
+pub struct SlackInteractionEventsRouter<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    middlewares: Vec<SlackRouteMiddleware<SlackInteractionEvent, SCHC>>,
+    block_action_routes:
+        SlackRoutes<SlackInteractionBlockActionsEvent, SlackInteractionEventResponse, SCHC>,
+    view_submission_routes:
+        SlackRoutes<SlackInteractionViewSubmissionEvent, SlackInteractionEventResponse, SCHC>,
+    view_closed_routes:
+        SlackRoutes<SlackInteractionViewClosedEvent, SlackInteractionEventResponse, SCHC>,
+    shortcut_routes:
+        SlackRoutes<SlackInteractionShortcutEvent, SlackInteractionEventResponse, SCHC>,
+    message_action_routes:
+        SlackRoutes<SlackInteractionMessageActionEvent, SlackInteractionEventResponse, SCHC>,
+    block_suggestion_routes:
+        SlackRoutes<SlackInteractionBlockSuggestionEvent, SlackInteractionEventResponse, SCHC>,
+    fallback: Option<SlackRouteHandler<SlackInteractionEvent, SlackInteractionEventResponse, SCHC>>,
+}
+
+impl<SCHC> SlackInteractionEventsRouter<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            middlewares: Vec::new(),
+            block_action_routes: Vec::new(),
+            view_submission_routes: Vec::new(),
+            view_closed_routes: Vec::new(),
+            shortcut_routes: Vec::new(),
+            message_action_routes: Vec::new(),
+            block_suggestion_routes: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    pub fn with_middleware<H, HF>(mut self, middleware: H) -> Self
+    where
+        H: Fn(
+                SlackInteractionEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = Option<SlackInteractionEvent>> + 'static + Send,
+    {
+        self.middlewares.push(box_route_handler(middleware));
+        self
+    }
+
+    ///
+    /// Block actions are matched if any of the actions in the event has a matching `action_id`
+    ///
+    pub fn on_block_action<M, H, HF, R>(mut self, action_id: M, handler: H) -> Self
+    where
+        M: Into<SlackRouteMatcher>,
+        H: Fn(
+                SlackInteractionBlockActionsEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = R> + 'static + Send,
+        R: Into<SlackInteractionEventResponse> + 'static,
+    {
+        self.block_action_routes.push((
+            action_id.into(),
+            Self::box_interaction_route_handler(handler),
+        ));
+        self
+    }
+
+    pub fn on_view_submission<M, H, HF, R>(mut self, callback_id: M, handler: H) -> Self
+    where
+        M: Into<SlackRouteMatcher>,
+        H: Fn(
+                SlackInteractionViewSubmissionEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = R> + 'static + Send,
+        R: Into<SlackInteractionEventResponse> + 'static,
+    {
+        self.view_submission_routes.push((
+            callback_id.into(),
+            Self::box_interaction_route_handler(handler),
+        ));
+        self
+    }
+
+    pub fn on_view_closed<M, H, HF, R>(mut self, callback_id: M, handler: H) -> Self
+    where
+        M: Into<SlackRouteMatcher>,
+        H: Fn(
+                SlackInteractionViewClosedEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = R> + 'static + Send,
+        R: Into<SlackInteractionEventResponse> + 'static,
+    {
+        self.view_closed_routes.push((
+            callback_id.into(),
+            Self::box_interaction_route_handler(handler),
+        ));
+        self
+    }
+
+    pub fn on_shortcut<M, H, HF, R>(mut self, callback_id: M, handler: H) -> Self
+    where
+        M: Into<SlackRouteMatcher>,
+        H: Fn(
+                SlackInteractionShortcutEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = R> + 'static + Send,
+        R: Into<SlackInteractionEventResponse> + 'static,
+    {
+        self.shortcut_routes.push((
+            callback_id.into(),
+            Self::box_interaction_route_handler(handler),
+        ));
+        self
+    }
+
+    pub fn on_message_action<M, H, HF, R>(mut self, callback_id: M, handler: H) -> Self
+    where
+        M: Into<SlackRouteMatcher>,
+        H: Fn(
+                SlackInteractionMessageActionEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = R> + 'static + Send,
+        R: Into<SlackInteractionEventResponse> + 'static,
+    {
+        self.message_action_routes.push((
+            callback_id.into(),
+            Self::box_interaction_route_handler(handler),
+        ));
+        self
+    }
+
+    ///
+    /// Block suggestions of external selects are matched by `action_id`
+    ///
+    pub fn on_block_suggestion<M, H, HF, R>(mut self, action_id: M, handler: H) -> Self
+    where
+        M: Into<SlackRouteMatcher>,
+        H: Fn(
+                SlackInteractionBlockSuggestionEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = R> + 'static + Send,
+        R: Into<SlackInteractionEventResponse> + 'static,
+    {
+        self.block_suggestion_routes.push((
+            action_id.into(),
+            Self::box_interaction_route_handler(handler),
+        ));
+        self
+    }
+
+    ///
+    /// Routes view submissions of the flow steps and its back navigation actions to the flow
+    ///
+    pub fn on_modal_flow<S>(self, modal_flow: Arc<SlackModalFlow<S, SCHC>>) -> Self
+    where
+        S: Serialize + DeserializeOwned + Send + Sync + 'static,
+    {
+        let callback_id_matcher = modal_flow.callback_id_matcher();
+        let back_action_id = modal_flow.back_action_id();
+        let submission_modal_flow = modal_flow.clone();
+
+        self.on_view_submission(
+            callback_id_matcher,
+            move |event, client, user_state_storage| {
+                let modal_flow = submission_modal_flow.clone();
+                async move {
+                    modal_flow
+                        .on_view_submission(event, client, user_state_storage)
+                        .await
+                }
+            },
+        )
+        .on_block_action(back_action_id, move |event, client, _| {
+            let modal_flow = modal_flow.clone();
+            async move { modal_flow.on_back_action(event, client).await }
+        })
+    }
+
+    pub fn with_fallback<H, HF, R>(mut self, handler: H) -> Self
+    where
+        H: Fn(
+                SlackInteractionEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = R> + 'static + Send,
+        R: Into<SlackInteractionEventResponse> + 'static,
+    {
+        self.fallback = Some(Self::box_interaction_route_handler(handler));
+        self
+    }
+
+    fn box_interaction_route_handler<E, H, HF, R>(
+        handler: H,
+    ) -> SlackRouteHandler<E, SlackInteractionEventResponse, SCHC>
+    where
+        H: Fn(E, Arc<SlackClient<SCHC>>, Arc<RwLock<SlackClientEventsUserStateStorage>>) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = R> + 'static + Send,
+        R: Into<SlackInteractionEventResponse> + 'static,
+    {
+        box_route_handler(move |event, client, user_state_storage| {
+            handler(event, client, user_state_storage).map(|response| response.into())
+        })
+    }
+
+    fn view_callback_id(view: &SlackView) -> Option<&SlackCallbackId> {
+        match view {
+            SlackView::Home(home_view) => home_view.callback_id.as_ref(),
+            SlackView::Modal(modal_view) => modal_view.callback_id.as_ref(),
+            SlackView::Other(_) => None,
+        }
+    }
+
+    pub async fn route(
+        &self,
+        event: SlackInteractionEvent,
+        client: Arc<SlackClient<SCHC>>,
+        user_state_storage: Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) -> SlackInteractionEventResponse {
+        let routed_event =
+            match apply_route_middlewares(&self.middlewares, event, &client, &user_state_storage)
+                .await
+            {
+                Some(routed_event) => routed_event,
+                None => return SlackInteractionEventResponse::Empty,
+            };
+
+        match routed_event {
+            SlackInteractionEvent::BlockActions(block_actions_event) => {
+                let found_handler =
+                    block_actions_event
+                        .actions
+                        .iter()
+                        .flatten()
+                        .find_map(|action| {
+                            find_route(&self.block_action_routes, action.action_id.value())
+                        });
+                match found_handler {
+                    Some(handler) => handler(block_actions_event, client, user_state_storage).await,
+                    None => {
+                        self.route_fallback(
+                            SlackInteractionEvent::BlockActions(block_actions_event),
+                            client,
+                            user_state_storage,
+                        )
+                        .await
+                    }
+                }
+            }
+            SlackInteractionEvent::ViewSubmission(view_submission_event) => {
+                let found_handler = Self::view_callback_id(&view_submission_event.view.view)
+                    .and_then(|callback_id| {
+                        find_route(&self.view_submission_routes, callback_id.value())
+                    });
+                match found_handler {
+                    Some(handler) => {
+                        handler(view_submission_event, client, user_state_storage).await
+                    }
+                    None => {
+                        self.route_fallback(
+                            SlackInteractionEvent::ViewSubmission(view_submission_event),
+                            client,
+                            user_state_storage,
+                        )
+                        .await
+                    }
+                }
+            }
+            SlackInteractionEvent::ViewClosed(view_closed_event) => {
+                let found_handler =
+                    Self::view_callback_id(&view_closed_event.view.view).and_then(|callback_id| {
+                        find_route(&self.view_closed_routes, callback_id.value())
+                    });
+                match found_handler {
+                    Some(handler) => handler(view_closed_event, client, user_state_storage).await,
+                    None => {
+                        self.route_fallback(
+                            SlackInteractionEvent::ViewClosed(view_closed_event),
+                            client,
+                            user_state_storage,
+                        )
+                        .await
+                    }
+                }
+            }
+            SlackInteractionEvent::Shortcut(shortcut_event) => {
+                match find_route(&self.shortcut_routes, shortcut_event.callback_id.value()) {
+                    Some(handler) => handler(shortcut_event, client, user_state_storage).await,
+                    None => {
+                        self.route_fallback(
+                            SlackInteractionEvent::Shortcut(shortcut_event),
+                            client,
+                            user_state_storage,
+                        )
+                        .await
+                    }
+                }
+            }
+            SlackInteractionEvent::MessageAction(message_action_event) => {
+                match find_route(
+                    &self.message_action_routes,
+                    message_action_event.callback_id.value(),
+                ) {
+                    Some(handler) => {
+                        handler(message_action_event, client, user_state_storage).await
+                    }
+                    None => {
+                        self.route_fallback(
+                            SlackInteractionEvent::MessageAction(message_action_event),
+                            client,
+                            user_state_storage,
+                        )
+                        .await
+                    }
+                }
+            }
+            SlackInteractionEvent::BlockSuggestion(block_suggestion_event) => {
+                match find_route(
+                    &self.block_suggestion_routes,
+                    block_suggestion_event.action_id.value(),
+                ) {
+                    Some(handler) => {
+                        handler(block_suggestion_event, client, user_state_storage).await
+                    }
+                    None => {
+                        self.route_fallback(
+                            SlackInteractionEvent::BlockSuggestion(block_suggestion_event),
+                            client,
+                            user_state_storage,
+                        )
+                        .await
+                    }
+                }
+            }
+            other_event => {
+                self.route_fallback(other_event, client, user_state_storage)
+                    .await
+            }
+        }
+    }
+
+    async fn route_fallback(
+        &self,
+        event: SlackInteractionEvent,
+        client: Arc<SlackClient<SCHC>>,
+        user_state_storage: Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) -> SlackInteractionEventResponse {
+        match &self.fallback {
+            Some(fallback) => fallback(event, client, user_state_storage).await,
+            None => {
+                debug!("No route found for Slack interaction event: {:?}", event);
+                SlackInteractionEventResponse::Empty
+            }
+        }
+    }
+
+    ///
+    /// Converts the router to a handler function for `interaction_events_service_fn`
+    ///
+    pub fn into_handler(
+        self,
+    ) -> impl Fn(
+        SlackInteractionEvent,
+        Arc<SlackClient<SCHC>>,
+        Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) -> BoxFuture<'static, SlackInteractionEventResponse>
+           + 'static
+           + Send
+           + Sync
+           + Clone {
+        let router = Arc::new(self);
+        move |event, client, user_state_storage| {
+            let thread_router = router.clone();
+            async move { thread_router.route(event, client, user_state_storage).await }.boxed()
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//!
+//! Declarative routing of Slack events to handlers by event type, action ids, callback ids
+//! and commands. Routers produce handler functions for the existing listener routes.
+//!
+
+use crate::listener::SlackClientEventsUserStateStorage;
+use crate::{SlackApiToken, SlackClient, SlackClientHttpConnector};
+use futures::future::{BoxFuture, FutureExt};
+use regex::Regex;
+use slack_morphism_models::*;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+mod command;
+mod home_tab;
+mod interaction;
+mod modal_flow;
+mod push;
+
+pub use command::*;
+pub use home_tab::*;
+pub use interaction::*;
+pub use modal_flow::*;
+pub use push::*;
+
+#[derive(Debug, Clone)]
+pub enum SlackRouteMatcher {
+    Exact(String),
+    Regex(Regex),
+}
+
+impl SlackRouteMatcher {
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            SlackRouteMatcher::Exact(expected) => expected == value,
+            SlackRouteMatcher::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+impl From<&str> for SlackRouteMatcher {
+    fn from(value: &str) -> Self {
+        SlackRouteMatcher::Exact(value.into())
+    }
+}
+
+impl From<String> for SlackRouteMatcher {
+    fn from(value: String) -> Self {
+        SlackRouteMatcher::Exact(value)
+    }
+}
+
+impl From<Regex> for SlackRouteMatcher {
+    fn from(regex: Regex) -> Self {
+        SlackRouteMatcher::Regex(regex)
+    }
+}
+
+impl From<SlackActionId> for SlackRouteMatcher {
+    fn from(action_id: SlackActionId) -> Self {
+        SlackRouteMatcher::Exact(action_id.0)
+    }
+}
+
+impl From<SlackCallbackId> for SlackRouteMatcher {
+    fn from(callback_id: SlackCallbackId) -> Self {
+        SlackRouteMatcher::Exact(callback_id.0)
+    }
+}
+
+impl From<SlackCommandId> for SlackRouteMatcher {
+    fn from(command: SlackCommandId) -> Self {
+        SlackRouteMatcher::Exact(command.0)
+    }
+}
+
+pub type SlackRouteHandler<E, R, SCHC> = Arc<
+    dyn Fn(
+            E,
+            Arc<SlackClient<SCHC>>,
+            Arc<RwLock<SlackClientEventsUserStateStorage>>,
+        ) -> BoxFuture<'static, R>
+        + Send
+        + Sync,
+>;
+
+///
+/// Middlewares are executed in the registration order before routing.
+/// A middleware may pass the event (possibly modified) further or filter it out returning `None`.
+///
+pub type SlackRouteMiddleware<E, SCHC> = SlackRouteHandler<E, Option<E>, SCHC>;
+
+///
+/// Resolves tokens of workspaces for handlers calling Slack Web API on their own
+///
+pub type SlackTokenResolver = Arc<dyn Fn(&SlackTeamId) -> Option<SlackApiToken> + Send + Sync>;
+
+pub(crate) fn box_route_handler<E, R, SCHC, H, HF>(handler: H) -> SlackRouteHandler<E, R, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+    H: Fn(E, Arc<SlackClient<SCHC>>, Arc<RwLock<SlackClientEventsUserStateStorage>>) -> HF
+        + 'static
+        + Send
+        + Sync,
+    HF: Future<Output = R> + 'static + Send,
+{
+    Arc::new(move |event, client, user_state_storage| {
+        handler(event, client, user_state_storage).boxed()
+    })
+}
+
+pub(crate) async fn apply_route_middlewares<E, SCHC>(
+    middlewares: &[SlackRouteMiddleware<E, SCHC>],
+    event: E,
+    client: &Arc<SlackClient<SCHC>>,
+    user_state_storage: &Arc<RwLock<SlackClientEventsUserStateStorage>>,
+) -> Option<E>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    let mut current_event = event;
+    for middleware in middlewares {
+        current_event =
+            middleware(current_event, client.clone(), user_state_storage.clone()).await?;
+    }
+    Some(current_event)
+}
+
+pub(crate) type SlackRoutes<E, R, SCHC> = Vec<(SlackRouteMatcher, SlackRouteHandler<E, R, SCHC>)>;
+
+pub(crate) fn find_route<'r, E, R, SCHC>(
+    routes: &'r [(SlackRouteMatcher, SlackRouteHandler<E, R, SCHC>)],
+    value: &str,
+) -> Option<&'r SlackRouteHandler<E, R, SCHC>>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    routes
+        .iter()
+        .find(|(matcher, _)| matcher.matches(value))
+        .map(|(_, handler)| handler)
+}
+
+#[test]
+fn check_route_matchers() {
+    let exact: SlackRouteMatcher = "approve-button".into();
+    assert!(exact.matches("approve-button"));
+    assert!(!exact.matches("approve-button-2"));
+
+    let regex: SlackRouteMatcher = Regex::new("^approve-.*$").unwrap().into();
+    assert!(regex.matches("approve-button-2"));
+    assert!(!regex.matches("reject-button"));
+}
@@ -0,0 +1,592 @@
+use crate::api::*;
+use crate::errors::*;
+use crate::listener::SlackClientEventsUserStateStorage;
+use crate::private_metadata::*;
+use crate::router::*;
+use crate::{
+    ClientResult, SlackApiToken, SlackClient, SlackClientHttpConnector, SlackClientSession,
+};
+use log::*;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use slack_morphism_models::blocks::*;
+use slack_morphism_models::events::*;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+const SLACK_VIEWS_STACK_MAX_SIZE: usize = 3;
+
+///
+/// Multi-step modals: each step renders a modal view from the flow state and handles its submission
+/// returning a transition to another step, back to the previous one, validation errors or the completion.
+///
+/// The flow state, the current step and the navigation history are carried in `private_metadata` of views
+/// (encoded with `SlackPrivateMetadataCodec`), and `callback_id`s of views are `<flow_id>/<step_id>`.
+/// Register a flow with `SlackInteractionEventsRouter::on_modal_flow` and open it with `open`.
+///
+/// Submissions the flow can't handle (e.g. with tampered metadata or unknown steps) are answered
+/// with the error text shown on the first input block of the submitted view.
+///
+pub struct SlackModalFlow<S, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    flow_id: String,
+    codec: SlackPrivateMetadataCodec,
+    navigation: SlackModalFlowNavigation,
+    back_button_text: String,
+    error_text: String,
+    token_resolver: Option<SlackTokenResolver>,
+    steps: Vec<SlackModalFlowStep<S, SCHC>>,
+}
+
+///
+/// How transitions to next steps are shown
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SlackModalFlowNavigation {
+    ///
+    /// Replaces the current view, so the flow has no Slack limits on the number of steps.
+    /// Views of steps with a history get a back button (it requires a token to update views).
+    ///
+    Update,
+    ///
+    /// Pushes views to the views stack, Slack shows its own back navigation.
+    /// Slack limits the stack to 3 views, so further steps replace the top view
+    /// and go back replacing it too.
+    /// Going back from a pushed view closes it and updates the previous view with the state
+    /// (it requires a token, otherwise the previous view is shown as it was).
+    ///
+    Push,
+}
+
+pub type SlackModalFlowRenderer<S> = Arc<dyn Fn(&S) -> SlackModalView + Send + Sync>;
+
+struct SlackModalFlowStep<S, SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    step_id: String,
+    render: SlackModalFlowRenderer<S>,
+    submit: SlackRouteHandler<SlackModalFlowSubmission<S>, SlackModalFlowTransition<S>, SCHC>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SlackModalFlowSubmission<S> {
+    pub state: S,
+    pub event: SlackInteractionViewSubmissionEvent,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackModalFlowTransition<S> {
+    ///
+    /// Shows a step with the state according to the flow navigation
+    ///
+    GoTo(String, S),
+    ///
+    /// Shows the current step again with the updated state
+    ///
+    Stay(S),
+    ///
+    /// Shows the previous step with the state
+    /// (for pushed views, closes the current view and updates the previous one)
+    ///
+    Back(S),
+    ///
+    /// Shows validation errors for blocks of the current step
+    ///
+    Errors(HashMap<SlackBlockId, String>),
+    ///
+    /// Closes all views of the flow
+    ///
+    Complete,
+}
+
+///
+/// The flow state with its navigation carried in `private_metadata` of views
+///
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SlackModalFlowMetadata<S> {
+    #[serde(rename = "s")]
+    pub step_id: String,
+    #[serde(rename = "h", default)]
+    pub history: Vec<String>,
+    #[serde(rename = "d")]
+    pub state: S,
+}
+
+impl<S> SlackModalFlowMetadata<S> {
+    pub fn new(step_id: String, state: S) -> Self {
+        Self {
+            step_id,
+            history: Vec::new(),
+            state,
+        }
+    }
+
+    pub fn with_history(self, history: Vec<String>) -> Self {
+        Self { history, ..self }
+    }
+
+    pub fn next<T>(self, step_id: String, state: T) -> SlackModalFlowMetadata<T> {
+        let mut history = self.history;
+        history.push(self.step_id);
+        SlackModalFlowMetadata {
+            step_id,
+            history,
+            state,
+        }
+    }
+
+    pub fn with_state<T>(self, state: T) -> SlackModalFlowMetadata<T> {
+        SlackModalFlowMetadata {
+            step_id: self.step_id,
+            history: self.history,
+            state,
+        }
+    }
+
+    ///
+    /// Returns the previous step with the state or `None` if there are no previous steps
+    ///
+    pub fn back<T>(self, state: T) -> Option<SlackModalFlowMetadata<T>> {
+        let mut history = self.history;
+        history.pop().map(|step_id| SlackModalFlowMetadata {
+            step_id,
+            history,
+            state,
+        })
+    }
+}
+
+impl<S, SCHC> SlackModalFlow<S, SCHC>
+where
+    S: Serialize + DeserializeOwned + Send + Sync + 'static,
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    pub fn new(flow_id: &str, codec: SlackPrivateMetadataCodec) -> Self {
+        Self {
+            flow_id: flow_id.into(),
+            codec,
+            navigation: SlackModalFlowNavigation::Update,
+            back_button_text: "Back".into(),
+            error_text: "Something went wrong, please try again".into(),
+            token_resolver: None,
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn with_navigation(self, navigation: SlackModalFlowNavigation) -> Self {
+        Self { navigation, ..self }
+    }
+
+    pub fn with_back_button_text(self, back_button_text: &str) -> Self {
+        Self {
+            back_button_text: back_button_text.into(),
+            ..self
+        }
+    }
+
+    ///
+    /// The error shown for submissions the flow can't handle
+    ///
+    pub fn with_error_text(self, error_text: &str) -> Self {
+        Self {
+            error_text: error_text.into(),
+            ..self
+        }
+    }
+
+    ///
+    /// A token to update views on back navigation, including previous views of the views stack
+    ///
+    pub fn with_token(self, token: SlackApiToken) -> Self {
+        self.with_token_resolver(move |_| Some(token.clone()))
+    }
+
+    ///
+    /// Tokens to update views on back navigation for apps installed to multiple workspaces
+    ///
+    pub fn with_token_resolver<F>(self, token_resolver: F) -> Self
+    where
+        F: Fn(&SlackTeamId) -> Option<SlackApiToken> + Send + Sync + 'static,
+    {
+        Self {
+            token_resolver: Some(Arc::new(token_resolver)),
+            ..self
+        }
+    }
+
+    ///
+    /// Adds a step. The first added step is the initial one.
+    ///
+    pub fn with_step<R, H, HF>(mut self, step_id: &str, render: R, submit: H) -> Self
+    where
+        R: Fn(&S) -> SlackModalView + Send + Sync + 'static,
+        H: Fn(
+                SlackModalFlowSubmission<S>,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = SlackModalFlowTransition<S>> + 'static + Send,
+    {
+        self.steps.push(SlackModalFlowStep {
+            step_id: step_id.into(),
+            render: Arc::new(render),
+            submit: box_route_handler(submit),
+        });
+        self
+    }
+
+    pub fn flow_id(&self) -> &str {
+        &self.flow_id
+    }
+
+    ///
+    /// Opens the initial step of the flow with the state
+    ///
+    pub async fn open(
+        &self,
+        session: &SlackClientSession<'_, SCHC>,
+        trigger_id: SlackTriggerId,
+        state: S,
+    ) -> ClientResult<SlackApiViewsOpenResponse> {
+        let initial_step = self.steps.first().ok_or_else(|| {
+            SlackClientError::SystemError(SlackClientSystemError::new(format!(
+                "Modal flow {} has no steps",
+                self.flow_id
+            )))
+        })?;
+
+        let view = self.render_step(SlackModalFlowMetadata::new(
+            initial_step.step_id.clone(),
+            state,
+        ))?;
+
+        session
+            .views_open(&SlackApiViewsOpenRequest::new(trigger_id, view))
+            .await
+    }
+
+    pub(crate) fn callback_id_matcher(&self) -> SlackRouteMatcher {
+        Regex::new(&format!("^{}/", regex::escape(&self.flow_id)))
+            .unwrap()
+            .into()
+    }
+
+    pub(crate) fn back_action_id(&self) -> SlackActionId {
+        format!("{}/back", self.flow_id).into()
+    }
+
+    fn find_step(&self, step_id: &str) -> Option<&SlackModalFlowStep<S, SCHC>> {
+        self.steps.iter().find(|step| step.step_id == step_id)
+    }
+
+    fn render_step(
+        &self,
+        metadata: SlackModalFlowMetadata<S>,
+    ) -> Result<SlackView, Box<dyn std::error::Error + Send + Sync>> {
+        let step = self.find_step(&metadata.step_id).ok_or_else(|| {
+            SlackClientError::SystemError(SlackClientSystemError::new(format!(
+                "Modal flow {} has no step {}",
+                self.flow_id, metadata.step_id
+            )))
+        })?;
+
+        let mut view = (step.render)(&metadata.state)
+            .with_callback_id(format!("{}/{}", self.flow_id, step.step_id).into())
+            .with_private_metadata(self.codec.encode(&metadata)?);
+
+        if self.navigation == SlackModalFlowNavigation::Update
+            && self.token_resolver.is_some()
+            && !metadata.history.is_empty()
+        {
+            view.blocks.push(
+                SlackActionsBlock::new(vec![SlackBlockButtonElement::new(
+                    self.back_action_id(),
+                    self.back_button_text.as_str().into(),
+                )
+                .into()])
+                .into(),
+            );
+        }
+
+        Ok(SlackView::Modal(view))
+    }
+
+    pub(crate) async fn on_view_submission(
+        &self,
+        event: SlackInteractionViewSubmissionEvent,
+        client: Arc<SlackClient<SCHC>>,
+        user_state_storage: Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) -> SlackInteractionEventResponse {
+        let failure_response = self.failure_response(&event.view.view);
+
+        let metadata = match self
+            .codec
+            .decode_view::<SlackModalFlowMetadata<S>>(&event.view.view)
+        {
+            Ok(Some(metadata)) => metadata,
+            Ok(None) => {
+                error!("Modal flow {} view has no private metadata", self.flow_id);
+                return failure_response;
+            }
+            Err(err) => {
+                error!(
+                    "Modal flow {} private metadata error: {}",
+                    self.flow_id, err
+                );
+                return failure_response;
+            }
+        };
+
+        let step = match self.find_step(&metadata.step_id) {
+            Some(step) => step,
+            None => {
+                error!(
+                    "Modal flow {} has no step {}",
+                    self.flow_id, metadata.step_id
+                );
+                return failure_response;
+            }
+        };
+
+        let SlackModalFlowMetadata {
+            step_id,
+            history,
+            state,
+        } = metadata;
+        let current_metadata = SlackModalFlowMetadata::new(step_id, ()).with_history(history);
+        let team_id = event.team.id.clone();
+        let previous_view_id = event.view.state_params.previous_view_id.clone();
+
+        let transition = (step.submit)(
+            SlackModalFlowSubmission { state, event },
+            client.clone(),
+            user_state_storage,
+        )
+        .await;
+
+        let response = match transition {
+            SlackModalFlowTransition::Back(state) if self.is_pushed(&current_metadata.history) => {
+                // Closing a pushed view shows the previous one from the views stack
+                self.update_previous_view(
+                    current_metadata.back(state),
+                    previous_view_id,
+                    &team_id,
+                    client,
+                )
+                .await
+                .map(|_| None)
+            }
+            transition => self.transition_response(current_metadata, transition),
+        };
+
+        match response {
+            Ok(response) => response.into(),
+            Err(err) => {
+                error!("Modal flow {} view render error: {}", self.flow_id, err);
+                failure_response
+            }
+        }
+    }
+
+    ///
+    /// Views of steps with longer histories replace the top view of the full stack
+    ///
+    fn is_pushed(&self, history: &[String]) -> bool {
+        self.navigation == SlackModalFlowNavigation::Push
+            && !history.is_empty()
+            && history.len() < SLACK_VIEWS_STACK_MAX_SIZE
+    }
+
+    async fn update_previous_view(
+        &self,
+        previous_metadata: Option<SlackModalFlowMetadata<S>>,
+        previous_view_id: Option<SlackViewId>,
+        team_id: &SlackTeamId,
+        client: Arc<SlackClient<SCHC>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let token = match self
+            .token_resolver
+            .as_ref()
+            .and_then(|token_resolver| token_resolver(team_id))
+        {
+            Some(token) => token,
+            None => {
+                warn!(
+                    "Modal flow {} has no token to update the previous view, it's shown as it was",
+                    self.flow_id
+                );
+                return Ok(());
+            }
+        };
+
+        if let (Some(previous_metadata), Some(previous_view_id)) =
+            (previous_metadata, previous_view_id)
+        {
+            let session = client.open_session(&token);
+            session
+                .views_update(
+                    &SlackApiViewsUpdateRequest::new(self.render_step(previous_metadata)?)
+                        .with_view_id(previous_view_id),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    fn transition_response(
+        &self,
+        current_metadata: SlackModalFlowMetadata<()>,
+        transition: SlackModalFlowTransition<S>,
+    ) -> Result<Option<SlackViewSubmissionResponse>, Box<dyn std::error::Error + Send + Sync>> {
+        match transition {
+            SlackModalFlowTransition::GoTo(step_id, state) => {
+                let next_metadata = current_metadata.next(step_id, state);
+                let push = self.is_pushed(&next_metadata.history);
+                self.render_step(next_metadata).map(|view| {
+                    if push {
+                        Some(SlackViewSubmissionPushResponse::new(view).into())
+                    } else {
+                        Some(SlackViewSubmissionUpdateResponse::new(view).into())
+                    }
+                })
+            }
+            SlackModalFlowTransition::Stay(state) => self
+                .render_step(current_metadata.with_state(state))
+                .map(|view| Some(SlackViewSubmissionUpdateResponse::new(view).into())),
+            SlackModalFlowTransition::Back(state) => match current_metadata.back(state) {
+                Some(previous_metadata) => self
+                    .render_step(previous_metadata)
+                    .map(|view| Some(SlackViewSubmissionUpdateResponse::new(view).into())),
+                None => Ok(None),
+            },
+            SlackModalFlowTransition::Errors(errors) => {
+                Ok(Some(SlackViewSubmissionErrorsResponse::new(errors).into()))
+            }
+            SlackModalFlowTransition::Complete => {
+                Ok(Some(SlackViewSubmissionClearResponse::new().into()))
+            }
+        }
+    }
+
+    ///
+    /// Shows the error text on the first input block of the view.
+    /// Slack accepts errors only for input blocks, so views without them are just closed.
+    ///
+    fn failure_response(&self, view: &SlackView) -> SlackInteractionEventResponse {
+        let input_block_id = match view {
+            SlackView::Modal(modal_view) => {
+                modal_view.blocks.iter().find_map(|block| match block {
+                    SlackBlock::Input(input_block) => input_block.block_id.clone(),
+                    _ => None,
+                })
+            }
+            _ => None,
+        };
+
+        match input_block_id {
+            Some(block_id) => {
+                SlackViewSubmissionResponse::from(SlackViewSubmissionErrorsResponse::new(
+                    vec![(block_id, self.error_text.clone())]
+                        .into_iter()
+                        .collect(),
+                ))
+                .into()
+            }
+            None => SlackInteractionEventResponse::Empty,
+        }
+    }
+
+    pub(crate) async fn on_back_action(
+        &self,
+        event: SlackInteractionBlockActionsEvent,
+        client: Arc<SlackClient<SCHC>>,
+    ) {
+        if let Err(err) = self.navigate_back(event, client).await {
+            match err.downcast_ref::<SlackClientError>() {
+                // The view has been already updated by another action
+                Some(SlackClientError::ApiError(api_error))
+                    if api_error.code == "hash_conflict" =>
+                {
+                    debug!("Modal flow {} view hash conflict on back", self.flow_id);
+                }
+                _ => error!("Modal flow {} back navigation error: {}", self.flow_id, err),
+            }
+        }
+    }
+
+    async fn navigate_back(
+        &self,
+        event: SlackInteractionBlockActionsEvent,
+        client: Arc<SlackClient<SCHC>>,
+    ) -> ClientResult<()> {
+        let team_id = event.team.id;
+        let view = match event.view {
+            Some(view) => view,
+            None => return Ok(()),
+        };
+
+        let previous_metadata = self
+            .codec
+            .decode_view::<SlackModalFlowMetadata<S>>(&view.view)?
+            .and_then(|metadata| {
+                let SlackModalFlowMetadata {
+                    step_id,
+                    history,
+                    state,
+                } = metadata;
+                SlackModalFlowMetadata::new(step_id, ())
+                    .with_history(history)
+                    .back(state)
+            });
+
+        let token = self
+            .token_resolver
+            .as_ref()
+            .and_then(|token_resolver| token_resolver(&team_id))
+            .ok_or_else(|| {
+                SlackClientError::SystemError(SlackClientSystemError::new(format!(
+                    "No token to update views of modal flow {}",
+                    self.flow_id
+                )))
+            })?;
+
+        if let Some(previous_metadata) = previous_metadata {
+            let session = client.open_session(&token);
+            session
+                .views_update(
+                    &SlackApiViewsUpdateRequest::new(self.render_step(previous_metadata)?)
+                        .with_view_id(view.state_params.id)
+                        .with_hash(view.state_params.hash),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn check_modal_flow_metadata_navigation() {
+    let metadata = SlackModalFlowMetadata::new("project".to_string(), 1)
+        .next("details".into(), 2)
+        .next("confirm".into(), 3);
+
+    assert_eq!(metadata.history, vec!["project", "details"]);
+
+    let previous = metadata.back(4).unwrap();
+    assert_eq!(previous.step_id, "details");
+    assert_eq!(previous.history, vec!["project"]);
+    assert_eq!(previous.state, 4);
+
+    let initial = previous.back(5).unwrap();
+    assert!(initial.back(6).is_none());
+}
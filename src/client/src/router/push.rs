@@ -0,0 +1,158 @@
+use crate::listener::SlackClientEventsUserStateStorage;
+use crate::router::*;
+use crate::{SlackClient, SlackClientHttpConnector};
+use futures::future::{BoxFuture, FutureExt};
+use log::*;
+use slack_morphism_models::events::*;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+///
+/// Routes push events to handlers by the event type of `SlackEventCallbackBody`
+/// (e.g. `message`, `app_mention`). The first matching route wins.
+/// Push events without a matching route (including non-callback events) go to the fallback handler.
+///
+pub struct SlackPushEventsRouter<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    middlewares: Vec<SlackRouteMiddleware<SlackPushEvent, SCHC>>,
+    event_routes: SlackRoutes<SlackEventCallback, (), SCHC>,
+    fallback: Option<SlackRouteHandler<SlackPushEvent, (), SCHC>>,
+}
+
+impl<SCHC> SlackPushEventsRouter<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            middlewares: Vec::new(),
+            event_routes: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    pub fn with_middleware<H, HF>(mut self, middleware: H) -> Self
+    where
+        H: Fn(
+                SlackPushEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = Option<SlackPushEvent>> + 'static + Send,
+    {
+        self.middlewares.push(box_route_handler(middleware));
+        self
+    }
+
+    pub fn on_event<M, H, HF>(mut self, event_type: M, handler: H) -> Self
+    where
+        M: Into<SlackRouteMatcher>,
+        H: Fn(
+                SlackEventCallback,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = ()> + 'static + Send,
+    {
+        self.event_routes
+            .push((event_type.into(), box_route_handler(handler)));
+        self
+    }
+
+    ///
+    /// Publishes home tabs using the publisher when users open them
+    ///
+    pub fn on_home_tab(self, home_tab_publisher: Arc<SlackHomeTabPublisher<SCHC>>) -> Self {
+        self.on_event("app_home_opened", move |event, client, _| {
+            let home_tab_publisher = home_tab_publisher.clone();
+            async move { home_tab_publisher.on_app_home_opened(event, client).await }
+        })
+    }
+
+    pub fn with_fallback<H, HF>(mut self, handler: H) -> Self
+    where
+        H: Fn(
+                SlackPushEvent,
+                Arc<SlackClient<SCHC>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> HF
+            + 'static
+            + Send
+            + Sync,
+        HF: Future<Output = ()> + 'static + Send,
+    {
+        self.fallback = Some(box_route_handler(handler));
+        self
+    }
+
+    pub async fn route(
+        &self,
+        event: SlackPushEvent,
+        client: Arc<SlackClient<SCHC>>,
+        user_state_storage: Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) {
+        match apply_route_middlewares(&self.middlewares, event, &client, &user_state_storage).await
+        {
+            Some(SlackPushEvent::EventCallback(event_callback)) => {
+                let event_type = event_callback.event.event_type_name();
+                match find_route(&self.event_routes, event_type) {
+                    Some(handler) => handler(event_callback, client, user_state_storage).await,
+                    None => {
+                        self.route_fallback(
+                            SlackPushEvent::EventCallback(event_callback),
+                            client,
+                            user_state_storage,
+                        )
+                        .await
+                    }
+                }
+            }
+            Some(other_event) => {
+                self.route_fallback(other_event, client, user_state_storage)
+                    .await
+            }
+            None => {}
+        }
+    }
+
+    async fn route_fallback(
+        &self,
+        event: SlackPushEvent,
+        client: Arc<SlackClient<SCHC>>,
+        user_state_storage: Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) {
+        match &self.fallback {
+            Some(fallback) => fallback(event, client, user_state_storage).await,
+            None => debug!("No route found for Slack push event: {:?}", event),
+        }
+    }
+
+    ///
+    /// Converts the router to a handler function for `push_events_service_fn`
+    ///
+    pub fn into_handler(
+        self,
+    ) -> impl Fn(
+        SlackPushEvent,
+        Arc<SlackClient<SCHC>>,
+        Arc<RwLock<SlackClientEventsUserStateStorage>>,
+    ) -> BoxFuture<'static, ()>
+           + 'static
+           + Send
+           + Sync
+           + Clone {
+        let router = Arc::new(self);
+        move |event, client, user_state_storage| {
+            let thread_router = router.clone();
+            async move { thread_router.route(event, client, user_state_storage).await }.boxed()
+        }
+    }
+}
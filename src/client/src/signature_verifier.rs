@@ -10,8 +10,8 @@ pub struct SlackEventSignatureVerifier {
 }
 
 impl SlackEventSignatureVerifier {
-    pub const SLACK_SIGNED_HASH_HEADER: &'static str = "x-slack-signature";
-    pub const SLACK_SIGNED_TIMESTAMP: &'static str = "x-slack-request-timestamp";
+    pub const SLACK_SIGNED_HASH_HEADER: &str = "x-slack-signature";
+    pub const SLACK_SIGNED_TIMESTAMP: &str = "x-slack-request-timestamp";
 
     pub fn new(secret: &str) -> Self {
         let secret_bytes = secret.as_bytes();
@@ -21,7 +21,7 @@ impl SlackEventSignatureVerifier {
         }
     }
 
-    fn sign<'a, 'b>(&'a self, body: &'b str, ts: &'b str) -> String {
+    fn sign(&self, body: &str, ts: &str) -> String {
         let data_to_sign = format!("v0:{}:{}", ts, body);
         format!(
             "v0={}",
@@ -145,8 +145,8 @@ fn check_signature_success() {
 
     let verifier = SlackEventSignatureVerifier::new(&key_str);
 
-    const TEST_BODY: &'static str = "test-body";
-    const TEST_TS: &'static str = "test-ts";
+    const TEST_BODY: &str = "test-body";
+    const TEST_TS: &str = "test-ts";
 
     let hash = verifier.sign(TEST_BODY, TEST_TS);
 
@@ -160,12 +160,10 @@ fn check_signature_success() {
 
 #[test]
 fn test_precoded_data() {
-    const TEST_SECRET: &'static str =
-        "d058b0b8f3f91e4446ad981890c9b6c16b2acc85367e30a2d76b8a95e525c02a";
-    const TEST_HASH: &'static str =
-        "v0=37ca0519af8b621f18b13586fc72488ebb159fc730a5d1718dd823dec69dea95";
-    const TEST_BODY: &'static str = "test-body";
-    const TEST_TS: &'static str = "test-ts";
+    const TEST_SECRET: &str = "d058b0b8f3f91e4446ad981890c9b6c16b2acc85367e30a2d76b8a95e525c02a";
+    const TEST_HASH: &str = "v0=37ca0519af8b621f18b13586fc72488ebb159fc730a5d1718dd823dec69dea95";
+    const TEST_BODY: &str = "test-body";
+    const TEST_TS: &str = "test-ts";
 
     let verifier = SlackEventSignatureVerifier::new(TEST_SECRET);
 
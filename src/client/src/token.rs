@@ -27,13 +27,14 @@ pub enum SlackApiTokenType {
     App,
 }
 
-impl ToString for SlackApiTokenType {
-    fn to_string(&self) -> String {
-        match self {
-            SlackApiTokenType::Bot => "bot".into(),
-            SlackApiTokenType::User => "user".into(),
-            SlackApiTokenType::App => "app".into(),
-        }
+impl std::fmt::Display for SlackApiTokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let as_str = match self {
+            SlackApiTokenType::Bot => "bot",
+            SlackApiTokenType::User => "user",
+            SlackApiTokenType::App => "app",
+        };
+        write!(f, "{}", as_str)
     }
 }
 
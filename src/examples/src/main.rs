@@ -37,6 +37,22 @@ async fn test_client() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let post_chat_resp = session.chat_post_message(&post_chat_req).await?;
     println!("post chat resp: {:#?}", &post_chat_resp);
 
+    let issue_message = IssueNotificationTemplateParams::new(
+        "Broken build".into(),
+        "The build is failing since the last release".into(),
+        "High".into(),
+        "ISSUE-1".into(),
+        config_env_var("SLACK_TEST_USER")?.into(),
+    );
+
+    let post_issue_resp = session
+        .chat_post_message(&SlackApiChatPostMessageRequest::new(
+            "#general".into(),
+            issue_message.render_template(),
+        ))
+        .await?;
+    println!("post issue resp: {:#?}", &post_issue_resp);
+
     let scroller_req: SlackApiUsersListRequest = SlackApiUsersListRequest::new().with_limit(1);
     let scroller = scroller_req.scroller();
 
@@ -97,6 +113,14 @@ async fn test_push_events_function(
     println!("{:#?}", event);
 }
 
+async fn test_app_mention_function(
+    event: SlackEventCallback,
+    _client: Arc<SlackHyperClient>,
+    user_state: SlackUserState<UserStateExample>,
+) {
+    println!("Mentioned: {:#?} with state: {:#?}", event, *user_state);
+}
+
 async fn test_interaction_events_function(
     event: SlackInteractionEvent,
     _client: Arc<SlackHyperClient>,
@@ -166,12 +190,27 @@ async fn test_server(
     let listener_environment = Arc::new(
         SlackClientEventsListenerEnvironment::new(client.clone())
             .with_error_handler(test_error_handler)
+            .with_post_handler_hook(|context, outcome| async move {
+                println!(
+                    "Handled {:?} at {} with {} in {:?}",
+                    context.event_type, context.path, outcome.status_code, outcome.elapsed
+                );
+            })
             .with_user_state(UserStateExample(0)),
     );
 
+    let push_events_router = SlackPushEventsRouter::new()
+        .on_event(
+            "app_mention",
+            with_user_state_fn(&listener_environment, test_app_mention_function)?,
+        )
+        .with_fallback(test_push_events_function)
+        .into_handler();
+
     let make_svc = make_service_fn(move |_| {
         let thread_oauth_config = oauth_listener_config.clone();
         let thread_push_events_config = push_events_config.clone();
+        let thread_push_events_router = push_events_router.clone();
         let thread_interaction_events_config = interactions_events_config.clone();
         let thread_command_events_config = command_events_config.clone();
         let listener = SlackClientEventsHyperListener::new(listener_environment.clone());
@@ -181,7 +220,7 @@ async fn test_server(
                 chain_service_routes_fn(
                     listener.push_events_service_fn(
                         thread_push_events_config,
-                        test_push_events_function,
+                        thread_push_events_router,
                     ),
                     chain_service_routes_fn(
                         listener.interaction_events_service_fn(
@@ -78,3 +78,20 @@ impl SlackBlocksTemplate for SlackHomeTabBlocksTemplateExample {
         ]
     }
 }
+
+#[derive(Debug, Clone, Builder, SlackMessageTemplate)]
+#[slack(text = "New issue: {title}")]
+pub struct IssueNotificationTemplateParams {
+    #[slack(header)]
+    pub title: String,
+    #[slack(section)]
+    pub description: String,
+    #[slack(field = "Priority")]
+    pub priority: String,
+    #[slack(field = "Assignee", with = "SlackTextFormat::to_slack_format")]
+    pub assignee: Option<SlackUserId>,
+    #[slack(button(text = "Open issue", action_id = "open-issue", style = "primary"))]
+    pub issue_id: String,
+    #[slack(context = "Reported by", with = "SlackTextFormat::to_slack_format")]
+    pub reporter: SlackUserId,
+}
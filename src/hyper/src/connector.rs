@@ -40,7 +40,7 @@ impl SlackClientHyperConnector {
                     .into_owned()
                     .collect()
             })
-            .unwrap_or_else(HashMap::new)
+            .unwrap_or_default()
     }
 
     pub(crate) fn hyper_redirect_to(
@@ -57,11 +57,12 @@ impl SlackClientHyperConnector {
         request_builder: hyper::http::request::Builder,
         token: Option<&SlackApiToken>,
     ) -> hyper::http::request::Builder {
-        if token.is_none() {
-            request_builder
-        } else {
-            let token_header_value = format!("Bearer {}", token.unwrap().token_value.value());
-            request_builder.header(hyper::header::AUTHORIZATION, token_header_value)
+        match token {
+            Some(token) => {
+                let token_header_value = format!("Bearer {}", token.token_value.value());
+                request_builder.header(hyper::header::AUTHORIZATION, token_header_value)
+            }
+            None => request_builder,
         }
     }
 
@@ -126,16 +127,16 @@ impl SlackClientHyperConnector {
             {
                 let slack_message: SlackEnvelopeMessage =
                     serde_json::from_str(http_body_str.as_str())?;
-                if slack_message.error.is_none() {
-                    let decoded_body = serde_json::from_str(http_body_str.as_str())?;
-                    Ok(decoded_body)
-                } else {
+                if let Some(slack_error) = slack_message.error {
                     Err(SlackClientError::ApiError(
-                        SlackClientApiError::new(slack_message.error.unwrap())
+                        SlackClientApiError::new(slack_error)
                             .opt_warnings(slack_message.warnings)
                             .with_http_response_body(http_body_str),
                     )
                     .into())
+                } else {
+                    let decoded_body = serde_json::from_str(http_body_str.as_str())?;
+                    Ok(decoded_body)
                 }
             }
             StatusCode::OK => serde_json::from_str("{}").map_err(|e| e.into()),
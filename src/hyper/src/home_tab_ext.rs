@@ -0,0 +1,50 @@
+use futures::stream::BoxStream;
+use slack_morphism::router::{SlackHomeTabPublishResult, SlackHomeTabPublisher};
+use slack_morphism::{ClientResult, SlackApiToken, SlackClient, SlackClientHttpConnector};
+use slack_morphism_models::SlackUserId;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub trait SlackHomeTabPublisherExt<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    ///
+    /// Publishes home tabs of users one by one waiting for `throttle_duration` after each `views.publish` call
+    /// (`views.publish` is a Tier 4 method, so 100 publishes per minute are within its rate limits).
+    /// Unchanged views aren't published, so they don't wait.
+    ///
+    fn republish_throttled<'a>(
+        &'a self,
+        client: &'a Arc<SlackClient<SCHC>>,
+        token: &'a SlackApiToken,
+        user_ids: Vec<SlackUserId>,
+        throttle_duration: Duration,
+    ) -> BoxStream<'a, (SlackUserId, ClientResult<SlackHomeTabPublishResult>)>;
+}
+
+impl<SCHC> SlackHomeTabPublisherExt<SCHC> for SlackHomeTabPublisher<SCHC>
+where
+    SCHC: SlackClientHttpConnector + Send + Sync + 'static,
+{
+    fn republish_throttled<'a>(
+        &'a self,
+        client: &'a Arc<SlackClient<SCHC>>,
+        token: &'a SlackApiToken,
+        user_ids: Vec<SlackUserId>,
+        throttle_duration: Duration,
+    ) -> BoxStream<'a, (SlackUserId, ClientResult<SlackHomeTabPublishResult>)> {
+        Box::pin(futures::stream::unfold(
+            (user_ids.into_iter(), false),
+            move |(mut user_ids, published)| async move {
+                let user_id = user_ids.next()?;
+                if published {
+                    tokio::time::sleep(throttle_duration).await;
+                }
+                let result = self.publish(client, token, user_id.clone()).await;
+                let published = !matches!(result, Ok(SlackHomeTabPublishResult::Unchanged));
+                Some(((user_id, result), (user_ids, published)))
+            },
+        ))
+    }
+}
@@ -1,14 +1,17 @@
 #![allow(clippy::new_without_default)]
+#![allow(clippy::type_complexity)]
 
 pub use crate::connector::SlackClientHyperConnector;
 use slack_morphism::SlackClient;
 
 pub mod connector;
+pub mod home_tab_ext;
 pub mod listener;
 pub mod scroller_ext;
 
 pub type SlackHyperClient = SlackClient<SlackClientHyperConnector>;
 
+pub use home_tab_ext::SlackHomeTabPublisherExt;
 pub use listener::chain_service_routes_fn;
 pub use listener::SlackClientEventsHyperListener;
 pub use scroller_ext::SlackApiResponseScrollerExt;
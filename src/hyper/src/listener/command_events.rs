@@ -1,11 +1,14 @@
 use crate::listener::SlackClientEventsHyperListener;
 
 use crate::connector::SlackClientHyperConnector;
+use crate::listener::SlackLazyHandlersExecutor;
 use slack_morphism::errors::*;
 use slack_morphism::listener::*;
 use slack_morphism::signature_verifier::SlackEventSignatureVerifier;
 use slack_morphism::SlackClient;
 
+use rvstruct::ValueStruct;
+
 use futures::future::{BoxFuture, FutureExt, TryFutureExt};
 use hyper::body::*;
 use hyper::{Method, Request, Response, StatusCode};
@@ -15,6 +18,55 @@ use std::future::Future;
 use std::sync::{Arc, RwLock};
 
 impl SlackClientEventsHyperListener {
+    fn command_event_context(
+        context: SlackClientEventsListenerContext,
+        command_event: &SlackCommandEvent,
+    ) -> SlackClientEventsListenerContext {
+        context
+            .with_team_id(command_event.team_id.clone())
+            .with_event_type(command_event.command.value().clone())
+    }
+
+    fn decode_command_event(
+        body: &str,
+    ) -> Result<SlackCommandEvent, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let body_params: HashMap<String, String> = url::form_urlencoded::parse(body.as_bytes())
+            .into_owned()
+            .collect();
+
+        match (
+            body_params.get("team_id"),
+            body_params.get("channel_id"),
+            body_params.get("user_id"),
+            body_params.get("command"),
+            body_params.get("text"),
+            body_params.get("response_url"),
+            body_params.get("trigger_id"),
+        ) {
+            (
+                Some(team_id),
+                Some(channel_id),
+                Some(user_id),
+                Some(command),
+                text,
+                Some(response_url),
+                Some(trigger_id),
+            ) => Ok(SlackCommandEvent::new(
+                team_id.into(),
+                channel_id.into(),
+                user_id.into(),
+                command.into(),
+                response_url.clone(),
+                trigger_id.into(),
+            )
+            .opt_text(text.cloned())),
+            _ => Err(SlackClientError::SystemError(SlackClientSystemError::new(
+                "Absent payload in the request from Slack".into(),
+            ))
+            .into()),
+        }
+    }
+
     pub fn command_events_service_fn<'a, D, F, I, IF>(
         &self,
         config: Arc<SlackCommandEventsListenerConfig>,
@@ -55,103 +107,194 @@ impl SlackClientEventsHyperListener {
         let signature_verifier: Arc<SlackEventSignatureVerifier> = Arc::new(
             SlackEventSignatureVerifier::new(&config.events_signing_secret),
         );
-        let client = self.environment.client.clone();
-        let error_handler = self.environment.error_handler.clone();
-        let user_state_storage = self.environment.user_state_storage.clone();
+        let environment = self.environment.clone();
 
         move |req: Request<Body>, chain: D| {
             let cfg = config.clone();
             let serv = command_service_fn.clone();
             let sign_verifier = signature_verifier.clone();
-            let sc = client.clone();
-            let thread_error_handler = error_handler.clone();
-            let thread_user_state_storage = user_state_storage.clone();
+            let thread_environment = environment.clone();
 
             async move {
                 match (req.method(), req.uri().path()) {
                     (&Method::POST, url) if url == cfg.events_path => {
+                        let context = SlackClientEventsListenerContext::new(url.into());
                         SlackClientHyperConnector::decode_signed_response(req, &sign_verifier)
-                            .map_ok(|body| {
-                                let body_params: HashMap<String, String> =
-                                    url::form_urlencoded::parse(body.as_bytes())
-                                        .into_owned()
-                                        .collect();
-
-                                match (
-                                    body_params.get("team_id"),
-                                    body_params.get("channel_id"),
-                                    body_params.get("user_id"),
-                                    body_params.get("command"),
-                                    body_params.get("text"),
-                                    body_params.get("response_url"),
-                                    body_params.get("trigger_id"),
-                                ) {
-                                    (
-                                        Some(team_id),
-                                        Some(channel_id),
-                                        Some(user_id),
-                                        Some(command),
-                                        text,
-                                        Some(response_url),
-                                        Some(trigger_id),
-                                    ) => Ok(SlackCommandEvent::new(
-                                        team_id.into(),
-                                        channel_id.into(),
-                                        user_id.into(),
-                                        command.into(),
-                                        response_url.clone(),
-                                        trigger_id.into(),
-                                    )
-                                    .opt_text(text.cloned())),
-                                    _ => Err(SlackClientError::SystemError(
-                                        SlackClientSystemError::new(
-                                            "Absent payload in the request from Slack".into(),
-                                        ),
-                                    ))
-                                    .map_err(|e| e.into()),
-                                }
-                            })
+                            .map_ok(|body| Self::decode_command_event(&body))
                             .and_then(|event| async move {
                                 match event {
                                     Ok(command_event) => {
-                                        match serv(
-                                            command_event,
-                                            sc.clone(),
-                                            thread_user_state_storage.clone(),
+                                        thread_environment
+                                            .client
+                                            .response_url_tracker()
+                                            .register(&command_event.response_url);
+                                        let event_context =
+                                            Self::command_event_context(context, &command_event);
+                                        Self::with_handler_hooks(
+                                            &thread_environment,
+                                            &event_context,
+                                            async {
+                                                match serv(
+                                                    command_event,
+                                                    thread_environment.client.clone(),
+                                                    thread_environment.user_state_storage.clone(),
+                                                )
+                                                .await
+                                                {
+                                                    Ok(cresp) => Response::builder()
+                                                        .status(StatusCode::OK)
+                                                        .header(
+                                                            "content-type",
+                                                            "application/json; charset=utf-8",
+                                                        )
+                                                        .body(
+                                                            serde_json::to_string(&cresp)
+                                                                .unwrap()
+                                                                .into(),
+                                                        )
+                                                        .map_err(|e| e.into()),
+                                                    Err(err) => {
+                                                        Self::error_response(
+                                                            &thread_environment,
+                                                            err,
+                                                            event_context.clone(),
+                                                        )
+                                                        .await
+                                                    }
+                                                }
+                                            },
+                                        )
+                                        .await
+                                    }
+                                    Err(command_event_err) => {
+                                        Self::error_response(
+                                            &thread_environment,
+                                            command_event_err,
+                                            context,
                                         )
                                         .await
-                                        {
-                                            Ok(cresp) => Response::builder()
-                                                .status(StatusCode::OK)
-                                                .header(
-                                                    "content-type",
-                                                    "application/json; charset=utf-8",
+                                    }
+                                }
+                            })
+                            .await
+                    }
+                    _ => chain(req).await,
+                }
+            }
+            .boxed()
+        }
+    }
+
+    ///
+    /// Command events route that acknowledges Slack immediately (optionally with an interim
+    /// response from `ack_fn`) and runs `command_service_fn` in background using `executor`.
+    /// Use `SlackCommandEvent::response_url` to respond later from the handler.
+    ///
+    pub fn command_events_lazy_service_fn<'a, D, F, A, I, IF>(
+        &self,
+        config: Arc<SlackCommandEventsListenerConfig>,
+        executor: SlackLazyHandlersExecutor,
+        ack_fn: A,
+        command_service_fn: I,
+    ) -> impl Fn(
+        Request<Body>,
+        D,
+    ) -> BoxFuture<
+        'a,
+        Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>>,
+    >
+           + 'a
+           + Send
+           + Clone
+    where
+        D: Fn(Request<Body>) -> F + 'a + Send + Sync + Clone,
+        F: Future<Output = Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>>>
+            + 'a
+            + Send,
+        A: Fn(&SlackCommandEvent) -> Option<SlackCommandEventResponse>
+            + 'static
+            + Send
+            + Sync
+            + Clone,
+        I: Fn(
+                SlackCommandEvent,
+                Arc<SlackClient<SlackClientHyperConnector>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> IF
+            + 'static
+            + Send
+            + Sync
+            + Clone,
+        IF: Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>>
+            + 'static
+            + Send,
+    {
+        let signature_verifier: Arc<SlackEventSignatureVerifier> = Arc::new(
+            SlackEventSignatureVerifier::new(&config.events_signing_secret),
+        );
+        let environment = self.environment.clone();
+
+        move |req: Request<Body>, chain: D| {
+            let cfg = config.clone();
+            let thread_executor = executor.clone();
+            let ack = ack_fn.clone();
+            let serv = command_service_fn.clone();
+            let sign_verifier = signature_verifier.clone();
+            let thread_environment = environment.clone();
+
+            async move {
+                match (req.method(), req.uri().path()) {
+                    (&Method::POST, url) if url == cfg.events_path => {
+                        let context = SlackClientEventsListenerContext::new(url.into());
+                        SlackClientHyperConnector::decode_signed_response(req, &sign_verifier)
+                            .map_ok(|body| Self::decode_command_event(&body))
+                            .and_then(|event| async move {
+                                match event {
+                                    Ok(command_event) => {
+                                        thread_environment
+                                            .client
+                                            .response_url_tracker()
+                                            .register(&command_event.response_url);
+                                        let event_context =
+                                            Self::command_event_context(context, &command_event);
+                                        let ack_response = ack(&command_event);
+                                        match thread_executor.spawn(
+                                            serv(
+                                                command_event,
+                                                thread_environment.client.clone(),
+                                                thread_environment.user_state_storage.clone(),
+                                            ),
+                                            thread_environment.clone(),
+                                            event_context.clone(),
+                                        ) {
+                                            Ok(()) => match ack_response {
+                                                Some(cresp) => Response::builder()
+                                                    .status(StatusCode::OK)
+                                                    .header(
+                                                        "content-type",
+                                                        "application/json; charset=utf-8",
+                                                    )
+                                                    .body(serde_json::to_string(&cresp)?.into())
+                                                    .map_err(|e| e.into()),
+                                                None => Ok(Response::new(Body::empty())),
+                                            },
+                                            Err(rejected_err) => {
+                                                Self::error_response(
+                                                    &thread_environment,
+                                                    Box::new(rejected_err),
+                                                    event_context,
                                                 )
-                                                .body(serde_json::to_string(&cresp).unwrap().into())
-                                                .map_err(|e| e.into()),
-                                            Err(err) => {
-                                                let status_code = thread_error_handler(
-                                                    err,
-                                                    sc.clone(),
-                                                    thread_user_state_storage.clone(),
-                                                );
-                                                Response::builder()
-                                                    .status(status_code)
-                                                    .body(Body::empty())
-                                                    .map_err(|e| e.into())
+                                                .await
                                             }
                                         }
                                     }
                                     Err(command_event_err) => {
-                                        let status_code = thread_error_handler(
+                                        Self::error_response(
+                                            &thread_environment,
                                             command_event_err,
-                                            sc,
-                                            thread_user_state_storage,
-                                        );
-                                        Response::builder()
-                                            .status(status_code)
-                                            .body(Body::empty())
-                                            .map_err(|e| e.into())
+                                            context,
+                                        )
+                                        .await
                                     }
                                 }
                             })
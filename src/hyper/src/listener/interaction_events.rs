@@ -3,6 +3,7 @@ use crate::listener::SlackClientEventsHyperListener;
 pub use slack_morphism_models::events::*;
 
 use crate::connector::SlackClientHyperConnector;
+use crate::listener::SlackLazyHandlersExecutor;
 
 use slack_morphism::errors::*;
 use slack_morphism::listener::*;
@@ -12,12 +13,70 @@ use slack_morphism::SlackClient;
 use futures::future::{BoxFuture, FutureExt, TryFutureExt};
 use hyper::body::*;
 use hyper::{Method, Request, Response, StatusCode};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::future::Future;
 use std::sync::{Arc, RwLock};
 
 impl SlackClientEventsHyperListener {
-    pub fn interaction_events_service_fn<'a, D, F, I, IF>(
+    fn interaction_event_context(
+        context: SlackClientEventsListenerContext,
+        interaction_event: &SlackInteractionEvent,
+    ) -> SlackClientEventsListenerContext {
+        context
+            .opt_team_id(interaction_event.team().map(|team| team.id.clone()))
+            .with_event_type(interaction_event.event_type_name().into())
+    }
+
+    pub(crate) fn decode_interaction_event(
+        body: &str,
+    ) -> Result<SlackInteractionEvent, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let body_params: HashMap<String, String> = url::form_urlencoded::parse(body.as_bytes())
+            .into_owned()
+            .collect();
+
+        let payload = body_params.get("payload").ok_or_else(|| {
+            SlackClientError::SystemError(SlackClientSystemError::new(
+                "Absent payload in the request from Slack".into(),
+            ))
+        })?;
+
+        serde_json::from_str::<SlackInteractionEvent>(payload).map_err(|e| {
+            SlackClientProtocolError {
+                json_error: e,
+                http_response_body: payload.clone(),
+            }
+            .into()
+        })
+    }
+
+    fn interaction_event_response<'a>(
+        response: SlackInteractionEventResponse,
+    ) -> Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>> {
+        match response {
+            SlackInteractionEventResponse::Empty => Ok(Response::new(Body::empty())),
+            SlackInteractionEventResponse::ViewSubmission(view_submission_response) => {
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header("content-type", "application/json; charset=utf-8")
+                    .body(serde_json::to_string(&view_submission_response)?.into())
+                    .map_err(|e| e.into())
+            }
+            SlackInteractionEventResponse::BlockSuggestion(block_suggestion_response) => {
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header("content-type", "application/json; charset=utf-8")
+                    .body(serde_json::to_string(&block_suggestion_response)?.into())
+                    .map_err(|e| e.into())
+            }
+        }
+    }
+
+    ///
+    /// Interaction events route. Handlers may return `()` or responses for Slack
+    /// like `SlackViewSubmissionResponse` to show validation errors or update views.
+    ///
+    pub fn interaction_events_service_fn<'a, D, F, I, IF, IR>(
         &self,
         config: Arc<SlackInteractionEventsListenerConfig>,
         interaction_service_fn: I,
@@ -45,66 +104,189 @@ impl SlackClientEventsHyperListener {
             + Send
             + Sync
             + Clone,
-        IF: Future<Output = ()> + 'static + Send,
+        IF: Future<Output = IR> + 'static + Send,
+        IR: Into<SlackInteractionEventResponse>,
     {
         let signature_verifier: Arc<SlackEventSignatureVerifier> = Arc::new(
             SlackEventSignatureVerifier::new(&config.events_signing_secret),
         );
-        let client = self.environment.client.clone();
-        let error_handler = self.environment.error_handler.clone();
-        let user_state_storage = self.environment.user_state_storage.clone();
+        let environment = self.environment.clone();
 
         move |req: Request<Body>, chain: D| {
             let cfg = config.clone();
             let serv = interaction_service_fn.clone();
             let sign_verifier = signature_verifier.clone();
-            let sc = client.clone();
-            let thread_error_handler = error_handler.clone();
-            let thread_user_state_storage = user_state_storage.clone();
+            let thread_environment = environment.clone();
 
             async move {
                 match (req.method(), req.uri().path()) {
                     (&Method::POST, url) if url == cfg.events_path => {
+                        let context = SlackClientEventsListenerContext::new(url.into());
                         SlackClientHyperConnector::decode_signed_response(req, &sign_verifier)
-                            .map_ok(|body| {
-                                let body_params: HashMap<String, String> =
-                                    url::form_urlencoded::parse(body.as_bytes())
-                                        .into_owned()
-                                        .collect();
-
-                                let payload = body_params
-                                    .get("payload")
-                                    .ok_or_else( || SlackClientError::SystemError(
-                                        SlackClientSystemError::new(
-                                            "Absent payload in the request from Slack".into(),
-                                        ),
-                                    ))
-                                    .map_err(|e| e.into());
-
-                                payload.and_then(|payload_value| {
-                                    serde_json::from_str::<SlackInteractionEvent>(payload_value)
-                                        .map_err(|e| SlackClientProtocolError{ json_error: e, http_response_body: payload_value.clone() }.into())
-                                })
-                            })
+                            .map_ok(|body| Self::decode_interaction_event(&body))
                             .and_then(|event| async move {
                                 match event {
-                                    Ok(view_submission_event@SlackInteractionEvent::ViewSubmission(_)) => {
-                                        serv(view_submission_event, sc, thread_user_state_storage).await;
-                                        Response::builder()
-                                            .status(StatusCode::OK)
-                                            .body("".into())
-                                            .map_err(|e| e.into())
+                                    Ok(interaction_event) => {
+                                        if let Some(response_url) = interaction_event.response_url()
+                                        {
+                                            thread_environment
+                                                .client
+                                                .response_url_tracker()
+                                                .register(response_url);
+                                        }
+                                        let event_context = Self::interaction_event_context(
+                                            context,
+                                            &interaction_event,
+                                        );
+                                        Self::with_handler_hooks(
+                                            &thread_environment,
+                                            &event_context,
+                                            async {
+                                                let response: SlackInteractionEventResponse = serv(
+                                                    interaction_event,
+                                                    thread_environment.client.clone(),
+                                                    thread_environment.user_state_storage.clone(),
+                                                )
+                                                .await
+                                                .into();
+                                                Self::interaction_event_response(response)
+                                            },
+                                        )
+                                        .await
+                                    }
+                                    Err(event_err) => {
+                                        Self::error_response(
+                                            &thread_environment,
+                                            event_err,
+                                            context,
+                                        )
+                                        .await
                                     }
+                                }
+                            })
+                            .await
+                    }
+                    _ => chain(req).await,
+                }
+            }
+            .boxed()
+        }
+    }
+
+    ///
+    /// Interaction events route that acknowledges Slack immediately (optionally with
+    /// a response body from `ack_fn`, such as a `response_action` for view submissions)
+    /// and runs `interaction_service_fn` in background using `executor`.
+    ///
+    pub fn interaction_events_lazy_service_fn<'a, D, F, A, AR, I, IF>(
+        &self,
+        config: Arc<SlackInteractionEventsListenerConfig>,
+        executor: SlackLazyHandlersExecutor,
+        ack_fn: A,
+        interaction_service_fn: I,
+    ) -> impl Fn(
+        Request<Body>,
+        D,
+    ) -> BoxFuture<
+        'a,
+        Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>>,
+    >
+           + 'a
+           + Send
+           + Clone
+    where
+        D: Fn(Request<Body>) -> F + 'a + Send + Sync + Clone,
+        F: Future<Output = Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>>>
+            + 'a
+            + Send,
+        A: Fn(&SlackInteractionEvent) -> Option<AR> + 'static + Send + Sync + Clone,
+        AR: Serialize + 'static,
+        I: Fn(
+                SlackInteractionEvent,
+                Arc<SlackClient<SlackClientHyperConnector>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> IF
+            + 'static
+            + Send
+            + Sync
+            + Clone,
+        IF: Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>>
+            + 'static
+            + Send,
+    {
+        let signature_verifier: Arc<SlackEventSignatureVerifier> = Arc::new(
+            SlackEventSignatureVerifier::new(&config.events_signing_secret),
+        );
+        let environment = self.environment.clone();
+
+        move |req: Request<Body>, chain: D| {
+            let cfg = config.clone();
+            let thread_executor = executor.clone();
+            let ack = ack_fn.clone();
+            let serv = interaction_service_fn.clone();
+            let sign_verifier = signature_verifier.clone();
+            let thread_environment = environment.clone();
+
+            async move {
+                match (req.method(), req.uri().path()) {
+                    (&Method::POST, url) if url == cfg.events_path => {
+                        let context = SlackClientEventsListenerContext::new(url.into());
+                        SlackClientHyperConnector::decode_signed_response(req, &sign_verifier)
+                            .map_ok(|body| Self::decode_interaction_event(&body))
+                            .and_then(|event| async move {
+                                match event {
                                     Ok(interaction_event) => {
-                                        serv(interaction_event, sc, thread_user_state_storage).await;
-                                        Ok(Response::new(Body::empty()))
+                                        if let Some(response_url) = interaction_event.response_url()
+                                        {
+                                            thread_environment
+                                                .client
+                                                .response_url_tracker()
+                                                .register(response_url);
+                                        }
+                                        let event_context = Self::interaction_event_context(
+                                            context,
+                                            &interaction_event,
+                                        );
+                                        let ack_response = ack(&interaction_event)
+                                            .map(|resp| serde_json::to_string(&resp))
+                                            .transpose()?;
+                                        match thread_executor.spawn(
+                                            serv(
+                                                interaction_event,
+                                                thread_environment.client.clone(),
+                                                thread_environment.user_state_storage.clone(),
+                                            ),
+                                            thread_environment.clone(),
+                                            event_context.clone(),
+                                        ) {
+                                            Ok(()) => match ack_response {
+                                                Some(resp_body) => Response::builder()
+                                                    .status(StatusCode::OK)
+                                                    .header(
+                                                        "content-type",
+                                                        "application/json; charset=utf-8",
+                                                    )
+                                                    .body(resp_body.into())
+                                                    .map_err(|e| e.into()),
+                                                None => Ok(Response::new(Body::empty())),
+                                            },
+                                            Err(rejected_err) => {
+                                                Self::error_response(
+                                                    &thread_environment,
+                                                    Box::new(rejected_err),
+                                                    event_context,
+                                                )
+                                                .await
+                                            }
+                                        }
                                     }
                                     Err(event_err) => {
-                                        let status_code = thread_error_handler(event_err, sc, thread_user_state_storage);
-                                        Response::builder()
-                                            .status(status_code)
-                                            .body(Body::empty())
-                                            .map_err(|e| e.into())
+                                        Self::error_response(
+                                            &thread_environment,
+                                            event_err,
+                                            context,
+                                        )
+                                        .await
                                     }
                                 }
                             })
@@ -117,3 +299,207 @@ impl SlackClientEventsHyperListener {
         }
     }
 }
+
+#[tokio::test]
+async fn check_interaction_event_response_bodies() {
+    let empty_response = SlackClientEventsHyperListener::interaction_event_response(
+        SlackInteractionEventResponse::Empty,
+    )
+    .unwrap();
+    assert_eq!(empty_response.status(), StatusCode::OK);
+    assert!(to_bytes(empty_response.into_body())
+        .await
+        .unwrap()
+        .is_empty());
+
+    let errors_response = SlackClientEventsHyperListener::interaction_event_response(
+        SlackViewSubmissionResponse::from(SlackViewSubmissionErrorsResponse::new(
+            vec![("title-block".into(), "Title is required".into())]
+                .into_iter()
+                .collect(),
+        ))
+        .into(),
+    )
+    .unwrap();
+    assert_eq!(
+        errors_response.headers().get("content-type").unwrap(),
+        "application/json; charset=utf-8"
+    );
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(
+            &to_bytes(errors_response.into_body()).await.unwrap()
+        )
+        .unwrap(),
+        serde_json::json!({
+            "response_action": "errors",
+            "errors": { "title-block": "Title is required" }
+        })
+    );
+
+    let options_response = SlackClientEventsHyperListener::interaction_event_response(
+        SlackBlockSuggestionResponse::from(SlackBlockSuggestionOptionsResponse::new(vec![])).into(),
+    )
+    .unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(
+            &to_bytes(options_response.into_body()).await.unwrap()
+        )
+        .unwrap(),
+        serde_json::json!({ "options": [] })
+    );
+}
+
+#[tokio::test]
+async fn check_modal_flow_transitions() {
+    use crate::SlackHyperClient;
+    use slack_morphism::private_metadata::*;
+    use slack_morphism::router::*;
+    use slack_morphism_models::blocks::*;
+
+    #[derive(Debug, PartialEq, Clone, Serialize, serde::Deserialize)]
+    struct TestState {
+        transition: String,
+        target: String,
+    }
+
+    let codec = SlackPrivateMetadataCodec::new();
+    let mut modal_flow = SlackModalFlow::new("test-flow", codec.clone())
+        .with_navigation(SlackModalFlowNavigation::Push)
+        .with_error_text("Please reopen the form");
+    for step_id in &["s1", "s2", "s3", "s4"] {
+        modal_flow = modal_flow.with_step(
+            step_id,
+            |_state: &TestState| SlackModalView::new("Step".into(), vec![]),
+            |submission: SlackModalFlowSubmission<TestState>, _client, _user_state| async move {
+                let state = submission.state;
+                match state.transition.as_str() {
+                    "goto" => SlackModalFlowTransition::GoTo(state.target.clone(), state),
+                    "stay" => SlackModalFlowTransition::Stay(TestState {
+                        target: "updated".into(),
+                        ..state
+                    }),
+                    "back" => SlackModalFlowTransition::Back(state),
+                    "errors" => SlackModalFlowTransition::Errors(
+                        vec![("title-block".into(), "Title is required".into())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    _ => SlackModalFlowTransition::Complete,
+                }
+            },
+        );
+    }
+
+    let router = SlackInteractionEventsRouter::new().on_modal_flow(Arc::new(modal_flow));
+    let client = Arc::new(SlackHyperClient::new(SlackClientHyperConnector::new()));
+    let user_state_storage = Arc::new(RwLock::new(SlackClientEventsUserStateStorage::new()));
+
+    let submit =
+        |step_id: &str, history: &[&str], transition: &str, private_metadata: Option<&str>| {
+            let metadata = SlackModalFlowMetadata::new(
+                step_id.to_string(),
+                TestState {
+                    transition: transition.into(),
+                    target: "s4".into(),
+                },
+            )
+            .with_history(history.iter().map(|step| step.to_string()).collect());
+            let private_metadata = private_metadata
+                .map(|private_metadata| private_metadata.to_string())
+                .unwrap_or_else(|| codec.encode(&metadata).unwrap());
+            let event: SlackInteractionEvent = serde_json::from_value(serde_json::json!({
+                "type": "view_submission",
+                "team": { "id": "T1" },
+                "user": { "id": "U1" },
+                "view": {
+                    "id": "V1",
+                    "team_id": "T1",
+                    "hash": "h1",
+                    "type": "modal",
+                    "title": { "type": "plain_text", "text": "Step" },
+                    "blocks": [{
+                        "type": "input",
+                        "block_id": "title-block",
+                        "label": { "type": "plain_text", "text": "Title" },
+                        "element": {
+                            "type": "plain_text_input",
+                            "action_id": "title",
+                            "placeholder": { "type": "plain_text", "text": "Title" }
+                        }
+                    }],
+                    "private_metadata": private_metadata,
+                    "callback_id": format!("test-flow/{}", step_id)
+                }
+            }))
+            .unwrap();
+            router.route(event, client.clone(), user_state_storage.clone())
+        };
+
+    let response_json = |response: SlackInteractionEventResponse| match response {
+        SlackInteractionEventResponse::ViewSubmission(view_submission_response) => {
+            serde_json::to_value(view_submission_response).unwrap()
+        }
+        _ => serde_json::Value::Null,
+    };
+
+    let response_metadata = |response: &serde_json::Value| {
+        codec
+            .decode::<SlackModalFlowMetadata<TestState>>(
+                response["view"]["private_metadata"].as_str().unwrap(),
+            )
+            .unwrap()
+    };
+
+    let pushed = response_json(submit("s1", &[], "goto", None).await);
+    assert_eq!(pushed["response_action"], "push");
+    assert_eq!(response_metadata(&pushed).step_id, "s4");
+    assert_eq!(response_metadata(&pushed).history, vec!["s1"]);
+
+    // The views stack is full, so the next step replaces the top view
+    let replaced = response_json(submit("s3", &["s1", "s2"], "goto", None).await);
+    assert_eq!(replaced["response_action"], "update");
+    assert_eq!(replaced["view"]["callback_id"], "test-flow/s4");
+    assert_eq!(response_metadata(&replaced).history, vec!["s1", "s2", "s3"]);
+
+    let replaced_back = response_json(submit("s4", &["s1", "s2", "s3"], "back", None).await);
+    assert_eq!(replaced_back["response_action"], "update");
+    assert_eq!(response_metadata(&replaced_back).step_id, "s3");
+    assert_eq!(response_metadata(&replaced_back).history, vec!["s1", "s2"]);
+
+    // Pushed views are closed, the flow has no token to update the previous view
+    assert_eq!(
+        submit("s3", &["s1", "s2"], "back", None).await,
+        SlackInteractionEventResponse::Empty
+    );
+
+    let stayed = response_json(submit("s2", &["s1"], "stay", None).await);
+    assert_eq!(stayed["response_action"], "update");
+    assert_eq!(response_metadata(&stayed).step_id, "s2");
+    assert_eq!(response_metadata(&stayed).state.target, "updated");
+
+    assert_eq!(
+        response_json(submit("s2", &["s1"], "errors", None).await),
+        serde_json::json!({
+            "response_action": "errors",
+            "errors": { "title-block": "Title is required" }
+        })
+    );
+
+    assert_eq!(
+        response_json(submit("s2", &["s1"], "complete", None).await),
+        serde_json::json!({ "response_action": "clear" })
+    );
+
+    let failure_json = serde_json::json!({
+        "response_action": "errors",
+        "errors": { "title-block": "Please reopen the form" }
+    });
+    assert_eq!(
+        response_json(submit("s2", &["s1"], "complete", Some("j.broken")).await),
+        failure_json
+    );
+    assert_eq!(
+        response_json(submit("s5", &["s1"], "complete", None).await),
+        failure_json
+    );
+}
@@ -0,0 +1,146 @@
+use crate::connector::SlackClientHyperConnector;
+
+use slack_morphism::listener::*;
+
+use hyper::StatusCode;
+use log::*;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+
+///
+/// Runs lazy (ack-first) event handlers in background with bounded concurrency.
+/// The same executor should be shared between all of the lazy routes and connections.
+/// Handlers aren't queued: when all of the permits are in use, new handlers are rejected.
+///
+#[derive(Debug, Clone)]
+pub struct SlackLazyHandlersExecutor {
+    semaphore: Arc<Semaphore>,
+    max_concurrent_handlers: usize,
+}
+
+impl SlackLazyHandlersExecutor {
+    pub fn new(config: &SlackLazyHandlersConfig) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(config.max_concurrent_handlers)),
+            max_concurrent_handlers: config.max_concurrent_handlers,
+        }
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    pub(crate) fn spawn<HF>(
+        &self,
+        handler_future: HF,
+        environment: Arc<SlackClientEventsListenerEnvironment<SlackClientHyperConnector>>,
+        context: SlackClientEventsListenerContext,
+    ) -> Result<(), SlackLazyHandlerRejectedError>
+    where
+        HF: Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>>
+            + 'static
+            + Send,
+    {
+        let permit = self.semaphore.clone().try_acquire_owned().map_err(|_| {
+            SlackLazyHandlerRejectedError {
+                max_concurrent_handlers: self.max_concurrent_handlers,
+            }
+        })?;
+
+        tokio::spawn(async move {
+            let _permit = permit;
+            environment.run_pre_handler_hooks(&context).await;
+            let started = Instant::now();
+            let status_code = match handler_future.await {
+                Ok(_) => StatusCode::OK,
+                Err(err) => {
+                    error!("Slack lazy handler error: {}", &err);
+                    environment
+                        .handle_error(err, context.clone())
+                        .await
+                        .status()
+                }
+            };
+            environment
+                .run_post_handler_hooks(
+                    &context,
+                    &SlackClientEventsListenerOutcome::new(status_code, started.elapsed()),
+                )
+                .await;
+        });
+
+        Ok(())
+    }
+}
+
+///
+/// Lazy handler wasn't started, because the executor already runs its maximum of concurrent handlers.
+/// Lazy routes report it to the error handler of the listener environment instead of acknowledging the event.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackLazyHandlerRejectedError {
+    pub max_concurrent_handlers: usize,
+}
+
+impl Display for SlackLazyHandlerRejectedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Slack lazy handler rejected: {} concurrent handlers are already running",
+            self.max_concurrent_handlers
+        )
+    }
+}
+
+impl std::error::Error for SlackLazyHandlerRejectedError {}
+
+#[tokio::test]
+async fn check_lazy_handlers_executor_rejects_over_limit() {
+    use crate::SlackHyperClient;
+
+    let environment = Arc::new(SlackClientEventsListenerEnvironment::new(Arc::new(
+        SlackHyperClient::new(SlackClientHyperConnector::new()),
+    )));
+    let executor = SlackLazyHandlersExecutor::new(
+        &SlackLazyHandlersConfig::new().with_max_concurrent_handlers(1),
+    );
+    let context = SlackClientEventsListenerContext::new("/command".into());
+
+    let (release_sender, release_receiver) = tokio::sync::oneshot::channel::<()>();
+    let (done_sender, done_receiver) = tokio::sync::oneshot::channel::<()>();
+
+    assert_eq!(
+        executor.spawn(
+            async move {
+                release_receiver.await.ok();
+                done_sender.send(()).ok();
+                Ok(())
+            },
+            environment.clone(),
+            context.clone(),
+        ),
+        Ok(())
+    );
+    assert_eq!(executor.available_permits(), 0);
+
+    assert_eq!(
+        executor.spawn(async { Ok(()) }, environment.clone(), context.clone()),
+        Err(SlackLazyHandlerRejectedError {
+            max_concurrent_handlers: 1
+        })
+    );
+
+    release_sender.send(()).unwrap();
+    done_receiver.await.unwrap();
+    while executor.available_permits() == 0 {
+        tokio::task::yield_now().await;
+    }
+
+    assert_eq!(
+        executor.spawn(async { Ok(()) }, environment, context),
+        Ok(())
+    );
+}
@@ -6,16 +6,24 @@ use futures::future::{BoxFuture, FutureExt};
 use hyper::{Body, Request, Response};
 
 pub use command_events::*;
+// Kept for compatibility, even though they re-export the same models as `command_events`
+#[allow(unused_imports)]
 pub use interaction_events::*;
+pub use lazy::*;
+#[allow(unused_imports)]
 pub use oauth::*;
+#[allow(unused_imports)]
 pub use push_events::*;
-use slack_morphism::listener::SlackClientEventsListenerEnvironment;
+use slack_morphism::listener::*;
 pub use slack_morphism::signature_verifier::*;
 use std::sync::Arc;
+use std::time::Instant;
 
 mod command_events;
 mod interaction_events;
+mod lazy;
 mod oauth;
+mod options_load;
 mod push_events;
 
 pub struct SlackClientEventsHyperListener {
@@ -28,6 +36,41 @@ impl SlackClientEventsHyperListener {
     ) -> Self {
         Self { environment }
     }
+
+    ///
+    /// Runs the environment pre/post handler hooks around the handling of a request
+    ///
+    pub(crate) async fn with_handler_hooks<'a, HF>(
+        environment: &SlackClientEventsListenerEnvironment<SlackClientHyperConnector>,
+        context: &SlackClientEventsListenerContext,
+        response_future: HF,
+    ) -> Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>>
+    where
+        HF: Future<Output = Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>>>,
+    {
+        environment.run_pre_handler_hooks(context).await;
+        let started = Instant::now();
+        let response = response_future.await;
+        let status_code = match &response {
+            Ok(resp) => resp.status(),
+            Err(_) => hyper::StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        environment
+            .run_post_handler_hooks(
+                context,
+                &SlackClientEventsListenerOutcome::new(status_code, started.elapsed()),
+            )
+            .await;
+        response
+    }
+
+    pub(crate) async fn error_response<'a>(
+        environment: &SlackClientEventsListenerEnvironment<SlackClientHyperConnector>,
+        err: Box<dyn std::error::Error + Send + Sync + 'static>,
+        context: SlackClientEventsListenerContext,
+    ) -> Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>> {
+        Ok(environment.handle_error(err, context).await.map(Body::from))
+    }
 }
 
 pub fn chain_service_routes_fn<'a, R, D, FR, FD>(
@@ -51,3 +94,65 @@ where
 {
     move |req: Request<Body>| route(req, default.clone()).boxed()
 }
+
+#[tokio::test]
+async fn check_user_state_fn_registration() {
+    use crate::SlackHyperClient;
+    use slack_morphism_models::events::SlackEventCallback;
+    use std::sync::RwLock;
+
+    struct CounterState {
+        value: u64,
+    }
+
+    async fn counter_handler(
+        _event: SlackEventCallback,
+        _client: Arc<SlackHyperClient>,
+        counter: SlackUserState<CounterState>,
+    ) -> u64 {
+        counter.value
+    }
+
+    let client = Arc::new(SlackHyperClient::new(SlackClientHyperConnector::new()));
+
+    let unregistered_environment = SlackClientEventsListenerEnvironment::new(client.clone());
+    assert_eq!(
+        with_user_state_fn(&unregistered_environment, counter_handler)
+            .err()
+            .map(|err| err.type_name.ends_with("CounterState")),
+        Some(true)
+    );
+
+    let environment = SlackClientEventsListenerEnvironment::new(client.clone())
+        .with_user_state(CounterState { value: 42 });
+    let handler = with_user_state_fn(&environment, counter_handler).unwrap();
+
+    let event: SlackEventCallback = serde_json::from_value(serde_json::json!({
+        "team_id": "T1",
+        "api_app_id": "A1",
+        "event": { "type": "app_uninstalled" },
+        "event_id": "Ev1",
+        "event_time": 1
+    }))
+    .unwrap();
+
+    assert_eq!(
+        handler(
+            event.clone(),
+            client.clone(),
+            environment.user_state_storage.clone()
+        )
+        .await,
+        42
+    );
+    // Handlers get the state captured when the route was built without reading storages
+    assert_eq!(
+        handler(
+            event,
+            client,
+            Arc::new(RwLock::new(SlackClientEventsUserStateStorage::new()))
+        )
+        .await,
+        42
+    );
+}
@@ -27,16 +27,14 @@ impl SlackClientEventsHyperListener {
             ],
         );
         debug!("Redirecting to Slack OAuth authorize: {}", &full_uri);
-        SlackClientHyperConnector::hyper_redirect_to(&full_uri.to_string())
+        SlackClientHyperConnector::hyper_redirect_to(full_uri.as_ref())
     }
 
     async fn slack_oauth_callback_service<I, IF>(
         req: Request<Body>,
         config: &SlackOAuthListenerConfig,
-        client: Arc<SlackClient<SlackClientHyperConnector>>,
-        user_state_storage: Arc<RwLock<SlackClientEventsUserStateStorage>>,
+        environment: Arc<SlackClientEventsListenerEnvironment<SlackClientHyperConnector>>,
         install_service_fn: I,
-        error_handler: BoxedErrorHandler<SlackClientHyperConnector>,
     ) -> Result<Response<Body>, Box<dyn std::error::Error + Send + Sync>>
     where
         I: Fn(
@@ -50,12 +48,14 @@ impl SlackClientEventsHyperListener {
             + Clone,
         IF: Future<Output = ()> + 'static + Send,
     {
+        let context = SlackClientEventsListenerContext::new(req.uri().path().into());
         let params = SlackClientHyperConnector::parse_query_params(&req);
         debug!("Received Slack OAuth callback: {:?}", &params);
 
         match (params.get("code"), params.get("error")) {
             (Some(code), None) => {
-                let oauth_access_resp = client
+                let oauth_access_resp = environment
+                    .client
                     .oauth2_access(
                         &SlackOAuthV2AccessTokenRequest::from(SlackOAuthV2AccessTokenRequestInit {
                             client_id: config.client_id.clone().into(),
@@ -79,12 +79,23 @@ impl SlackClientEventsHyperListener {
                                 .unwrap_or_else(|| "".into()),
                             &oauth_resp.authed_user.id
                         );
-                        install_service_fn(oauth_resp, client, user_state_storage).await;
-                        SlackClientHyperConnector::hyper_redirect_to(&config.redirect_installed_url)
+                        let event_context = context.with_team_id(oauth_resp.team.id.clone());
+                        Self::with_handler_hooks(&environment, &event_context, async {
+                            install_service_fn(
+                                oauth_resp,
+                                environment.client.clone(),
+                                environment.user_state_storage.clone(),
+                            )
+                            .await;
+                            SlackClientHyperConnector::hyper_redirect_to(
+                                &config.redirect_installed_url,
+                            )
+                        })
+                        .await
                     }
                     Err(err) => {
                         error!("Slack OAuth error: {}", &err);
-                        error_handler(err, client, user_state_storage);
+                        environment.handle_error(err, context).await;
                         SlackClientHyperConnector::hyper_redirect_to(
                             &config.redirect_error_redirect_url,
                         )
@@ -93,13 +104,14 @@ impl SlackClientEventsHyperListener {
             }
             (None, Some(err)) => {
                 info!("Slack OAuth cancelled with the reason: {}", err);
-                error_handler(
-                    Box::new(SlackClientError::ApiError(SlackClientApiError::new(
-                        err.clone(),
-                    ))),
-                    client,
-                    user_state_storage,
-                );
+                environment
+                    .handle_error(
+                        Box::new(SlackClientError::ApiError(SlackClientApiError::new(
+                            err.clone(),
+                        ))),
+                        context,
+                    )
+                    .await;
                 let redirect_error_url = format!(
                     "{}{}",
                     &config.redirect_error_redirect_url,
@@ -109,13 +121,14 @@ impl SlackClientEventsHyperListener {
             }
             _ => {
                 error!("Slack OAuth cancelled with unknown reason");
-                error_handler(
-                    Box::new(SlackClientError::SystemError(SlackClientSystemError::new(
-                        "OAuth cancelled with unknown reason".into(),
-                    ))),
-                    client,
-                    user_state_storage,
-                );
+                environment
+                    .handle_error(
+                        Box::new(SlackClientError::SystemError(SlackClientSystemError::new(
+                            "OAuth cancelled with unknown reason".into(),
+                        ))),
+                        context,
+                    )
+                    .await;
                 SlackClientHyperConnector::hyper_redirect_to(&config.redirect_error_redirect_url)
             }
         }
@@ -151,16 +164,12 @@ impl SlackClientEventsHyperListener {
             + Clone,
         IF: Future<Output = ()> + 'static + Send,
     {
-        let client = self.environment.client.clone();
-        let listener_error_handler = self.environment.error_handler.clone();
-        let user_state_storage = self.environment.user_state_storage.clone();
+        let environment = self.environment.clone();
 
         move |req: Request<Body>, chain: D| {
             let cfg = config.clone();
             let install_fn = install_service_fn.clone();
-            let sc = client.clone();
-            let error_handler = listener_error_handler.clone();
-            let thread_user_state_storage = user_state_storage.clone();
+            let thread_environment = environment.clone();
             async move {
                 match (req.method(), req.uri().path()) {
                     (&Method::GET, url) if url == cfg.install_path => {
@@ -170,10 +179,8 @@ impl SlackClientEventsHyperListener {
                         Self::slack_oauth_callback_service(
                             req,
                             &cfg,
-                            sc,
-                            thread_user_state_storage,
+                            thread_environment,
                             install_fn,
-                            error_handler,
                         )
                         .await
                     }
@@ -0,0 +1,153 @@
+use crate::listener::SlackClientEventsHyperListener;
+
+use crate::connector::SlackClientHyperConnector;
+use slack_morphism::errors::*;
+use slack_morphism::listener::*;
+use slack_morphism::signature_verifier::SlackEventSignatureVerifier;
+use slack_morphism::SlackClient;
+
+use futures::future::{BoxFuture, FutureExt, TryFutureExt};
+use hyper::body::*;
+use hyper::{Method, Request, Response, StatusCode};
+pub use slack_morphism_models::events::*;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+impl SlackClientEventsHyperListener {
+    fn decode_block_suggestion_event(
+        body: &str,
+    ) -> Result<
+        SlackInteractionBlockSuggestionEvent,
+        Box<dyn std::error::Error + Send + Sync + 'static>,
+    > {
+        match Self::decode_interaction_event(body)? {
+            SlackInteractionEvent::BlockSuggestion(block_suggestion_event) => {
+                Ok(block_suggestion_event)
+            }
+            other_event => Err(SlackClientError::SystemError(SlackClientSystemError::new(
+                format!(
+                    "Unexpected interaction event type for options load: {}",
+                    other_event.event_type_name()
+                ),
+            ))
+            .into()),
+        }
+    }
+
+    ///
+    /// Options load route (`block_suggestion` events) for external select elements
+    ///
+    pub fn options_load_service_fn<'a, D, F, I, IF>(
+        &self,
+        config: Arc<SlackOptionsLoadListenerConfig>,
+        options_service_fn: I,
+    ) -> impl Fn(
+        Request<Body>,
+        D,
+    ) -> BoxFuture<
+        'a,
+        Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>>,
+    >
+           + 'a
+           + Send
+           + Clone
+    where
+        D: Fn(Request<Body>) -> F + 'a + Send + Sync + Clone,
+        F: Future<Output = Result<Response<Body>, Box<dyn std::error::Error + Send + Sync + 'a>>>
+            + 'a
+            + Send,
+        I: Fn(
+                SlackInteractionBlockSuggestionEvent,
+                Arc<SlackClient<SlackClientHyperConnector>>,
+                Arc<RwLock<SlackClientEventsUserStateStorage>>,
+            ) -> IF
+            + 'static
+            + Send
+            + Sync
+            + Clone,
+        IF: Future<
+                Output = Result<
+                    SlackBlockSuggestionResponse,
+                    Box<dyn std::error::Error + Send + Sync + 'static>,
+                >,
+            >
+            + 'static
+            + Send,
+    {
+        let signature_verifier: Arc<SlackEventSignatureVerifier> = Arc::new(
+            SlackEventSignatureVerifier::new(&config.events_signing_secret),
+        );
+        let environment = self.environment.clone();
+
+        move |req: Request<Body>, chain: D| {
+            let cfg = config.clone();
+            let serv = options_service_fn.clone();
+            let sign_verifier = signature_verifier.clone();
+            let thread_environment = environment.clone();
+
+            async move {
+                match (req.method(), req.uri().path()) {
+                    (&Method::POST, url) if url == cfg.events_path => {
+                        let context = SlackClientEventsListenerContext::new(url.into());
+                        SlackClientHyperConnector::decode_signed_response(req, &sign_verifier)
+                            .map_ok(|body| Self::decode_block_suggestion_event(&body))
+                            .and_then(|event| async move {
+                                match event {
+                                    Ok(block_suggestion_event) => {
+                                        let event_context = context
+                                            .with_team_id(block_suggestion_event.team.id.clone())
+                                            .with_event_type("block_suggestion".into());
+                                        Self::with_handler_hooks(
+                                            &thread_environment,
+                                            &event_context,
+                                            async {
+                                                match serv(
+                                                    block_suggestion_event,
+                                                    thread_environment.client.clone(),
+                                                    thread_environment.user_state_storage.clone(),
+                                                )
+                                                .await
+                                                {
+                                                    Ok(options_resp) => Response::builder()
+                                                        .status(StatusCode::OK)
+                                                        .header(
+                                                            "content-type",
+                                                            "application/json; charset=utf-8",
+                                                        )
+                                                        .body(
+                                                            serde_json::to_string(&options_resp)?
+                                                                .into(),
+                                                        )
+                                                        .map_err(|e| e.into()),
+                                                    Err(err) => {
+                                                        Self::error_response(
+                                                            &thread_environment,
+                                                            err,
+                                                            event_context.clone(),
+                                                        )
+                                                        .await
+                                                    }
+                                                }
+                                            },
+                                        )
+                                        .await
+                                    }
+                                    Err(event_err) => {
+                                        Self::error_response(
+                                            &thread_environment,
+                                            event_err,
+                                            context,
+                                        )
+                                        .await
+                                    }
+                                }
+                            })
+                            .await
+                    }
+                    _ => chain(req).await,
+                }
+            }
+            .boxed()
+        }
+    }
+}
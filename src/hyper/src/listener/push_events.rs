@@ -15,6 +15,24 @@ use std::future::Future;
 use std::sync::{Arc, RwLock};
 
 impl SlackClientEventsHyperListener {
+    fn push_event_context(
+        context: SlackClientEventsListenerContext,
+        push_event: &SlackPushEvent,
+    ) -> SlackClientEventsListenerContext {
+        match push_event {
+            SlackPushEvent::EventCallback(event_callback) => context
+                .with_team_id(event_callback.team_id.clone())
+                .with_event_type(event_callback.event.event_type_name().into()),
+            SlackPushEvent::AppRateLimited(rate_limited) => context
+                .with_team_id(rate_limited.team_id.clone().into())
+                .with_event_type("app_rate_limited".into()),
+            SlackPushEvent::UrlVerification(_) => {
+                context.with_event_type("url_verification".into())
+            }
+            SlackPushEvent::Other(_) => context,
+        }
+    }
+
     pub fn push_events_service_fn<'a, D, F, I, IF>(
         &self,
         config: Arc<SlackPushEventsListenerConfig>,
@@ -48,20 +66,17 @@ impl SlackClientEventsHyperListener {
         let signature_verifier: Arc<SlackEventSignatureVerifier> = Arc::new(
             SlackEventSignatureVerifier::new(&config.events_signing_secret),
         );
-        let client = self.environment.client.clone();
-        let error_handler = self.environment.error_handler.clone();
-        let user_state_storage = self.environment.user_state_storage.clone();
+        let environment = self.environment.clone();
 
         move |req: Request<Body>, chain: D| {
             let cfg = config.clone();
             let push_serv = push_service_fn.clone();
             let sign_verifier = signature_verifier.clone();
-            let sc = client.clone();
-            let thread_error_handler = error_handler.clone();
-            let thread_user_state_storage = user_state_storage.clone();
+            let thread_environment = environment.clone();
             async move {
                 match (req.method(), req.uri().path()) {
                     (&Method::POST, url) if url == cfg.events_path => {
+                        let context = SlackClientEventsListenerContext::new(url.into());
                         SlackClientHyperConnector::decode_signed_response(req, &sign_verifier)
                             .map_ok(|body| {
                                 serde_json::from_str::<SlackPushEvent>(body.as_str()).map_err(|e| {
@@ -74,40 +89,47 @@ impl SlackClientEventsHyperListener {
                             })
                             .and_then(|event| async move {
                                 match event {
-                                    Ok(SlackPushEvent::UrlVerification(url_ver)) => {
-                                        debug!(
-                                            "Received Slack URL push verification challenge: {}",
-                                            url_ver.challenge
-                                        );
-                                        push_serv(
-                                            SlackPushEvent::UrlVerification(url_ver.clone()),
-                                            sc,
-                                            thread_user_state_storage,
+                                    Ok(push_event) => {
+                                        let event_context = Self::push_event_context(context, &push_event);
+                                        Self::with_handler_hooks(
+                                            &thread_environment,
+                                            &event_context,
+                                            async {
+                                                match push_event {
+                                                    SlackPushEvent::UrlVerification(url_ver) => {
+                                                        debug!(
+                                                            "Received Slack URL push verification challenge: {}",
+                                                            url_ver.challenge
+                                                        );
+                                                        push_serv(
+                                                            SlackPushEvent::UrlVerification(url_ver.clone()),
+                                                            thread_environment.client.clone(),
+                                                            thread_environment.user_state_storage.clone(),
+                                                        )
+                                                        .await;
+                                                        Response::builder()
+                                                            .status(StatusCode::OK)
+                                                            .body(url_ver.challenge.into())
+                                                            .map_err(|e| e.into())
+                                                    }
+                                                    other_push_event => {
+                                                        push_serv(
+                                                            other_push_event,
+                                                            thread_environment.client.clone(),
+                                                            thread_environment.user_state_storage.clone(),
+                                                        )
+                                                        .await;
+                                                        Ok(Response::new(Body::empty()))
+                                                    }
+                                                }
+                                            },
                                         )
-                                        .await;
-                                        Response::builder()
-                                            .status(StatusCode::OK)
-                                            .body(url_ver.challenge.into())
-                                            .map_err(|e| e.into())
+                                        .await
+                                    }
+                                    Err(push_event_err) => {
+                                        Self::error_response(&thread_environment, push_event_err, context)
+                                            .await
                                     }
-                                    other => match other {
-                                        Ok(push_event) => {
-                                            push_serv(push_event, sc, thread_user_state_storage)
-                                                .await;
-                                            Ok(Response::new(Body::empty()))
-                                        }
-                                        Err(err_oush_event) => {
-                                            let status_code = thread_error_handler(
-                                                err_oush_event,
-                                                sc,
-                                                thread_user_state_storage,
-                                            );
-                                            Response::builder()
-                                                .status(status_code)
-                                                .body(Body::empty())
-                                                .map_err(|e| e.into())
-                                        }
-                                    },
                                 }
                             })
                             .await
@@ -0,0 +1,537 @@
+//! # Slack Morphism derive macros
+//!
+//! Derive `SlackMessageTemplate` and `SlackBlocksTemplate` for your structs
+//! to render them to Block Kit declaratively using `#[slack(...)]` attributes.
+//! Use them through the `derive` feature of `slack-morphism-models`:
+//!
+//! ```ignore
+//! use slack_morphism_models::*;
+//!
+//! #[derive(SlackMessageTemplate)]
+//! #[slack(text = "New issue: {title}")]
+//! struct IssueNotification {
+//!     #[slack(header)]
+//!     title: String,
+//!     #[slack(section)]
+//!     description: String,
+//!     #[slack(field = "Priority")]
+//!     priority: String,
+//!     #[slack(field = "Assignee", with = "SlackTextFormat::to_slack_format")]
+//!     assignee: Option<SlackUserId>,
+//!     #[slack(button(text = "Open issue", action_id = "open-issue", style = "primary"))]
+//!     issue_id: String,
+//!     #[slack(context = "Reported by")]
+//!     reporter: String,
+//! }
+//! ```
+//!
+//! Blocks are rendered in the order of fields.
+//! Consecutive `field`, `button` and `context` fields are grouped into
+//! a section with fields, an actions block and a context block accordingly.
+//! `Option` fields are rendered only when they have a value.
+//! Values of `section`, `field` and `context` fields are escaped as mrkdwn texts,
+//! values formatted `with` a function are used as they are.
+//! Placeholders of the `text` are escaped too and can't refer to `Option` fields.
+//!
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr, Path, Type};
+
+///
+/// Implements `SlackMessageTemplate` rendering a message with the blocks from `#[slack(...)]` fields
+/// and an optional fallback text from `#[slack(text = "...")]` on the struct with `{field}` placeholders.
+///
+#[proc_macro_derive(SlackMessageTemplate, attributes(slack))]
+pub fn derive_slack_message_template(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_message_template(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+///
+/// Implements `SlackBlocksTemplate` rendering the blocks from `#[slack(...)]` fields.
+///
+#[proc_macro_derive(SlackBlocksTemplate, attributes(slack))]
+pub fn derive_slack_blocks_template(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_blocks_template(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_message_template(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let template = TemplateStruct::parse(input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let blocks = template.render_blocks();
+    let text = match &template.text {
+        Some(text) => {
+            let (format, placeholders) = parse_text_template(text);
+            let values = placeholders
+                .iter()
+                .map(|(name, spec)| {
+                    let field = template
+                        .fields
+                        .iter()
+                        .find(|field| field.ident == name)
+                        .ok_or_else(|| {
+                            syn::Error::new(
+                                text.span(),
+                                format!("unknown field `{}` in the text template", name),
+                            )
+                        })?;
+                    if field.optional {
+                        return Err(syn::Error::new(
+                            text.span(),
+                            format!(
+                                "`Option` field `{}` can't be used in the text template, because it may have no value",
+                                name
+                            ),
+                        ));
+                    }
+                    let ident = &field.ident;
+                    let value_format = format!("{{:{}}}", spec);
+                    Ok(quote! {
+                        ::slack_morphism_models::blocks::SlackMrkdwnBuilder::escape(
+                            &::std::format!(#value_format, self.#ident)
+                        )
+                    })
+                })
+                .collect::<syn::Result<Vec<TokenStream2>>>()?;
+            quote! {
+                ::std::option::Option::Some(::std::format!(#format, #(#values),*))
+            }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::slack_morphism_models::SlackMessageTemplate for #ident #ty_generics #where_clause {
+            fn render_template(&self) -> ::slack_morphism_models::SlackMessageContent {
+                ::slack_morphism_models::SlackMessageContent::new()
+                    .opt_text(#text)
+                    .with_blocks(#blocks)
+            }
+        }
+    })
+}
+
+fn expand_blocks_template(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let template = TemplateStruct::parse(input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let blocks = template.render_blocks();
+
+    Ok(quote! {
+        impl #impl_generics ::slack_morphism_models::SlackBlocksTemplate for #ident #ty_generics #where_clause {
+            fn render_template(&self) -> ::std::vec::Vec<::slack_morphism_models::blocks::SlackBlock> {
+                #blocks
+            }
+        }
+    })
+}
+
+struct TemplateStruct {
+    text: Option<LitStr>,
+    fields: Vec<TemplateField>,
+}
+
+struct TemplateField {
+    ident: Ident,
+    optional: bool,
+    kind: Option<TemplateFieldKind>,
+    with: Option<Path>,
+}
+
+enum TemplateFieldKind {
+    Header,
+    Section(Option<LitStr>),
+    Field(LitStr),
+    Context(Option<LitStr>),
+    Button {
+        text: LitStr,
+        action_id: LitStr,
+        style: Option<LitStr>,
+    },
+    Blocks,
+}
+
+#[derive(PartialEq)]
+enum TemplateGroup {
+    Fields,
+    Buttons,
+    Context,
+}
+
+impl TemplateFieldKind {
+    fn group(&self) -> Option<TemplateGroup> {
+        match self {
+            TemplateFieldKind::Field(_) => Some(TemplateGroup::Fields),
+            TemplateFieldKind::Button { .. } => Some(TemplateGroup::Buttons),
+            TemplateFieldKind::Context(_) => Some(TemplateGroup::Context),
+            _ => None,
+        }
+    }
+}
+
+impl TemplateStruct {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut text = None;
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("slack"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("text") {
+                    text = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported slack template attribute, expected `text`"))
+                }
+            })?;
+        }
+
+        let named_fields = match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => &fields.named,
+                _ => {
+                    return Err(syn::Error::new(
+                        input.span(),
+                        "slack templates are supported only for structs with named fields",
+                    ))
+                }
+            },
+            _ => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "slack templates are supported only for structs",
+                ))
+            }
+        };
+
+        let fields = named_fields
+            .iter()
+            .map(|field| {
+                let mut template_field = TemplateField {
+                    ident: field.ident.clone().unwrap(),
+                    optional: is_option(&field.ty),
+                    kind: None,
+                    with: None,
+                };
+                for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("slack")) {
+                    attr.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("with") {
+                            let path: LitStr = meta.value()?.parse()?;
+                            template_field.with = Some(path.parse()?);
+                            return Ok(());
+                        }
+
+                        let kind = if meta.path.is_ident("header") {
+                            TemplateFieldKind::Header
+                        } else if meta.path.is_ident("section") {
+                            TemplateFieldKind::Section(parse_optional_label(&meta)?)
+                        } else if meta.path.is_ident("field") {
+                            TemplateFieldKind::Field(meta.value()?.parse()?)
+                        } else if meta.path.is_ident("context") {
+                            TemplateFieldKind::Context(parse_optional_label(&meta)?)
+                        } else if meta.path.is_ident("button") {
+                            parse_button(&meta)?
+                        } else if meta.path.is_ident("blocks") {
+                            TemplateFieldKind::Blocks
+                        } else {
+                            return Err(meta.error(
+                                "unsupported slack template attribute, expected one of `header`, `section`, `field`, `context`, `button`, `blocks` or `with`",
+                            ));
+                        };
+
+                        if template_field.kind.is_some() {
+                            Err(meta.error("a field can be rendered only once"))
+                        } else {
+                            template_field.kind = Some(kind);
+                            Ok(())
+                        }
+                    })?;
+                }
+
+                if template_field.with.is_some() && template_field.kind.is_none() {
+                    Err(syn::Error::new(
+                        field.span(),
+                        "`with` requires a way to render the field (e.g. `section` or `field`)",
+                    ))
+                } else {
+                    Ok(template_field)
+                }
+            })
+            .collect::<syn::Result<Vec<TemplateField>>>()?;
+
+        Ok(Self { text, fields })
+    }
+
+    fn render_blocks(&self) -> TokenStream2 {
+        let mut statements: Vec<TokenStream2> = Vec::new();
+        let mut group: Vec<&TemplateField> = Vec::new();
+
+        for field in self.fields.iter().filter(|field| field.kind.is_some()) {
+            let field_group = field.kind.as_ref().and_then(|kind| kind.group());
+            let current_group = group
+                .first()
+                .and_then(|first| first.kind.as_ref())
+                .and_then(|kind| kind.group());
+
+            if !group.is_empty() && field_group != current_group {
+                statements.push(render_group(&group));
+                group.clear();
+            }
+
+            if field_group.is_some() {
+                group.push(field);
+            } else {
+                statements.push(field.render());
+            }
+        }
+
+        if !group.is_empty() {
+            statements.push(render_group(&group));
+        }
+
+        quote! {{
+            let mut blocks: ::std::vec::Vec<::slack_morphism_models::blocks::SlackBlock> = ::std::vec::Vec::new();
+            #(#statements)*
+            blocks
+        }}
+    }
+}
+
+impl TemplateField {
+    ///
+    /// Renders a statement for this field: `value` is a reference to the field value
+    /// (unwrapped for `Option` fields) and `text` is its text.
+    /// Texts of mrkdwn fields are escaped, unless they're formatted `with` a function.
+    ///
+    fn with_value(&self, body: TokenStream2) -> TokenStream2 {
+        let ident = &self.ident;
+        let text = match (&self.kind, &self.with) {
+            (Some(TemplateFieldKind::Blocks), _) => quote! { ::std::string::String::new() },
+            (_, Some(with)) => quote! { #with(value) },
+            (
+                Some(
+                    TemplateFieldKind::Section(_)
+                    | TemplateFieldKind::Field(_)
+                    | TemplateFieldKind::Context(_),
+                ),
+                None,
+            ) => quote! {
+                ::slack_morphism_models::blocks::SlackMrkdwnBuilder::escape(
+                    &::std::string::ToString::to_string(value)
+                )
+            },
+            (_, None) => quote! { ::std::string::ToString::to_string(value) },
+        };
+
+        if self.optional {
+            quote! {
+                if let ::std::option::Option::Some(value) = &self.#ident {
+                    #[allow(unused_variables)]
+                    let text: ::std::string::String = #text;
+                    #body
+                }
+            }
+        } else {
+            quote! {{
+                let value = &self.#ident;
+                #[allow(unused_variables)]
+                let text: ::std::string::String = #text;
+                #body
+            }}
+        }
+    }
+
+    fn render(&self) -> TokenStream2 {
+        let body = match self.kind.as_ref() {
+            Some(TemplateFieldKind::Header) => quote! {
+                blocks.push(
+                    ::slack_morphism_models::blocks::SlackHeaderBlock::new(text.into()).into()
+                );
+            },
+            Some(TemplateFieldKind::Section(label)) => {
+                let text = labeled_text(label.as_ref(), "*{}*\n{}");
+                quote! {
+                    blocks.push(
+                        ::slack_morphism_models::blocks::SlackSectionBlock::new()
+                            .with_text(
+                                ::slack_morphism_models::blocks::SlackBlockMarkDownText::new(#text).into()
+                            )
+                            .into()
+                    );
+                }
+            }
+            Some(TemplateFieldKind::Blocks) => quote! {
+                blocks.extend(::slack_morphism_models::SlackBlocksTemplate::render_template(value));
+            },
+            Some(TemplateFieldKind::Field(label)) => {
+                let text = labeled_text(Some(label), "*{}*\n{}");
+                quote! {
+                    fields.push(
+                        ::slack_morphism_models::blocks::SlackBlockMarkDownText::new(#text).into()
+                    );
+                }
+            }
+            Some(TemplateFieldKind::Context(label)) => {
+                let text = labeled_text(label.as_ref(), "{}: {}");
+                quote! {
+                    elements.push(
+                        ::slack_morphism_models::blocks::SlackBlockMarkDownText::new(#text).into()
+                    );
+                }
+            }
+            Some(TemplateFieldKind::Button {
+                text: button_text,
+                action_id,
+                style,
+            }) => {
+                let style = style.iter();
+                quote! {
+                    elements.push(
+                        ::slack_morphism_models::blocks::SlackBlockButtonElement::new(
+                            #action_id.into(),
+                            #button_text.into(),
+                        )
+                        .with_value(text)
+                        #(.with_style(#style.into()))*
+                        .into()
+                    );
+                }
+            }
+            None => quote! {},
+        };
+
+        self.with_value(body)
+    }
+}
+
+fn render_group(group: &[&TemplateField]) -> TokenStream2 {
+    let items: Vec<TokenStream2> = group.iter().map(|field| field.render()).collect();
+    match group
+        .first()
+        .and_then(|first| first.kind.as_ref())
+        .and_then(|kind| kind.group())
+    {
+        Some(TemplateGroup::Fields) => quote! {{
+            let mut fields: ::std::vec::Vec<::slack_morphism_models::blocks::SlackBlockText> = ::std::vec::Vec::new();
+            #(#items)*
+            // Slack allows up to 10 fields in a section
+            for section_fields in fields.chunks(10) {
+                blocks.push(
+                    ::slack_morphism_models::blocks::SlackSectionBlock::new()
+                        .with_fields(section_fields.to_vec())
+                        .into()
+                );
+            }
+        }},
+        Some(TemplateGroup::Buttons) => quote! {{
+            let mut elements: ::std::vec::Vec<::slack_morphism_models::blocks::SlackActionBlockElement> = ::std::vec::Vec::new();
+            #(#items)*
+            if !elements.is_empty() {
+                blocks.push(::slack_morphism_models::blocks::SlackActionsBlock::new(elements).into());
+            }
+        }},
+        Some(TemplateGroup::Context) => quote! {{
+            let mut elements: ::std::vec::Vec<::slack_morphism_models::blocks::SlackContextBlockElement> = ::std::vec::Vec::new();
+            #(#items)*
+            if !elements.is_empty() {
+                blocks.push(::slack_morphism_models::blocks::SlackContextBlock::new(elements).into());
+            }
+        }},
+        None => quote! { #(#items)* },
+    }
+}
+
+fn labeled_text(label: Option<&LitStr>, format: &str) -> TokenStream2 {
+    match label {
+        Some(label) => quote! { ::std::format!(#format, #label, text) },
+        None => quote! { text },
+    }
+}
+
+fn parse_optional_label(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Option<LitStr>> {
+    if meta.input.peek(syn::Token![=]) {
+        Ok(Some(meta.value()?.parse()?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn parse_button(meta: &syn::meta::ParseNestedMeta) -> syn::Result<TemplateFieldKind> {
+    let mut text = None;
+    let mut action_id = None;
+    let mut style = None;
+
+    meta.parse_nested_meta(|button_meta| {
+        if button_meta.path.is_ident("text") {
+            text = Some(button_meta.value()?.parse()?);
+        } else if button_meta.path.is_ident("action_id") {
+            action_id = Some(button_meta.value()?.parse()?);
+        } else if button_meta.path.is_ident("style") {
+            style = Some(button_meta.value()?.parse()?);
+        } else {
+            return Err(button_meta
+                .error("unsupported button attribute, expected `text`, `action_id` or `style`"));
+        }
+        Ok(())
+    })?;
+
+    match (text, action_id) {
+        (Some(text), Some(action_id)) => Ok(TemplateFieldKind::Button {
+            text,
+            action_id,
+            style,
+        }),
+        _ => Err(meta.error("a button requires `text` and `action_id`")),
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .iter()
+            .any(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+///
+/// Replaces `{name}` and `{name:spec}` placeholders in a format string with positional `{}`
+/// and returns the names and format specs of placeholders in their order
+///
+fn parse_text_template(text: &LitStr) -> (String, Vec<(String, String)>) {
+    let value = text.value();
+    let mut format = String::new();
+    let mut placeholders: Vec<(String, String)> = Vec::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '{' => {
+                let placeholder: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let (name, spec) = placeholder.split_once(':').unwrap_or((&placeholder, ""));
+                placeholders.push((name.trim().to_string(), spec.to_string()));
+                format.push_str("{}");
+            }
+            _ => format.push(c),
+        }
+    }
+
+    (format, placeholders)
+}
@@ -12,19 +12,20 @@ pub enum SlackDateTimeFormats {
     TimeSecs,
 }
 
-impl ToString for SlackDateTimeFormats {
-    fn to_string(&self) -> String {
-        match self {
-            SlackDateTimeFormats::DateNum => "{date_num}".into(),
-            SlackDateTimeFormats::Date => "{date}".into(),
-            SlackDateTimeFormats::DateShort => "{date_short}".into(),
-            SlackDateTimeFormats::DateLong => "{date_long}".into(),
-            SlackDateTimeFormats::DatePretty => "{date_pretty}".into(),
-            SlackDateTimeFormats::DateShortPretty => "{date_short_pretty}".into(),
-            SlackDateTimeFormats::DateLongPretty => "{date_long_pretty}".into(),
-            SlackDateTimeFormats::Time => "{time}".into(),
-            SlackDateTimeFormats::TimeSecs => "{time_secs}".into(),
-        }
+impl std::fmt::Display for SlackDateTimeFormats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let as_str = match self {
+            SlackDateTimeFormats::DateNum => "{date_num}",
+            SlackDateTimeFormats::Date => "{date}",
+            SlackDateTimeFormats::DateShort => "{date_short}",
+            SlackDateTimeFormats::DateLong => "{date_long}",
+            SlackDateTimeFormats::DatePretty => "{date_pretty}",
+            SlackDateTimeFormats::DateShortPretty => "{date_short_pretty}",
+            SlackDateTimeFormats::DateLongPretty => "{date_long_pretty}",
+            SlackDateTimeFormats::Time => "{time}",
+            SlackDateTimeFormats::TimeSecs => "{time_secs}",
+        };
+        write!(f, "{}", as_str)
     }
 }
 
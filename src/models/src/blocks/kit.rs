@@ -3,14 +3,15 @@ use rvstruct::ValueStruct;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
+use crate::blocks::SlackRichTextBlock;
 use crate::common::*;
 
 #[skip_serializing_none]
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, ValueStruct)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackBlockId(pub String);
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackBlock {
     #[serde(rename = "section")]
     Section(SlackSectionBlock),
@@ -26,11 +27,22 @@ pub enum SlackBlock {
     Input(SlackInputBlock),
     #[serde(rename = "file")]
     File(SlackFileBlock),
-
-    // This block is still undocumented, so we don't define any structure yet we can return it back,
     #[serde(rename = "rich_text")]
-    RichText(serde_json::Value),
-}
+    RichText(SlackRichTextBlock),
+    #[serde(rename = "header")]
+    Header(SlackHeaderBlock),
+    #[serde(rename = "video")]
+    Video(SlackVideoBlock),
+    #[serde(rename = "call")]
+    Call(SlackCallBlock),
+    ///
+    /// Blocks that aren't modelled (yet) are preserved as JSON to be able to return them back.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl_slack_tagged_with_other!(SlackBlock, "type");
 
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
@@ -108,6 +120,7 @@ pub struct SlackInputBlock {
     pub element: SlackInputBlockElement,
     pub hint: Option<SlackBlockPlainText>,
     pub optional: Option<bool>,
+    pub dispatch_action: Option<bool>,
 }
 
 impl From<SlackInputBlock> for SlackBlock {
@@ -133,8 +146,70 @@ impl From<SlackFileBlock> for SlackBlock {
     }
 }
 
+///
+/// Header with a large plain text.
+/// https://api.slack.com/reference/block-kit/blocks#header
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackHeaderBlock {
+    pub block_id: Option<SlackBlockId>,
+    pub text: SlackBlockPlainTextOnly,
+}
+
+impl From<SlackHeaderBlock> for SlackBlock {
+    fn from(block: SlackHeaderBlock) -> Self {
+        SlackBlock::Header(block)
+    }
+}
+
+///
+/// Embedded video player. Videos are available only for apps with `links.embed:write` scope.
+/// https://api.slack.com/reference/block-kit/blocks#video
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackVideoBlock {
+    pub block_id: Option<SlackBlockId>,
+    pub alt_text: String,
+    pub title: SlackBlockPlainTextOnly,
+    pub thumbnail_url: String,
+    pub video_url: String,
+    pub title_url: Option<String>,
+    pub description: Option<SlackBlockPlainTextOnly>,
+    pub author_name: Option<String>,
+    pub provider_name: Option<String>,
+    pub provider_icon_url: Option<String>,
+}
+
+impl From<SlackVideoBlock> for SlackBlock {
+    fn from(block: SlackVideoBlock) -> Self {
+        SlackBlock::Video(block)
+    }
+}
+
+///
+/// Call started by the Calls API.
+/// Calls are created using the Calls API, so the call details are preserved as JSON.
+/// https://api.slack.com/apis/calls
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackCallBlock {
+    pub block_id: Option<SlackBlockId>,
+    pub call_id: String,
+    pub api_decoration_available: Option<bool>,
+    pub call: Option<serde_json::Value>,
+}
+
+impl From<SlackCallBlock> for SlackBlock {
+    fn from(block: SlackCallBlock) -> Self {
+        SlackBlock::Call(block)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackSectionBlockElement {
     #[serde(rename = "image")]
     Image(SlackBlockImageElement),
@@ -170,10 +245,19 @@ pub enum SlackSectionBlockElement {
     RadioButtons(SlackBlockRadioButtonsElement),
     #[serde(rename = "checkboxes")]
     Checkboxes(SlackBlockCheckboxesElement),
+    #[serde(rename = "timepicker")]
+    TimePicker(SlackBlockTimePickerElement),
+    ///
+    /// Elements that aren't modelled (yet) are preserved as JSON to be able to return them back.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
 }
 
+impl_slack_tagged_with_other!(SlackSectionBlockElement, "type");
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackActionBlockElement {
     #[serde(rename = "button")]
     Button(SlackBlockButtonElement),
@@ -187,10 +271,41 @@ pub enum SlackActionBlockElement {
     RadioButtons(SlackBlockRadioButtonsElement),
     #[serde(rename = "checkboxes")]
     Checkboxes(SlackBlockCheckboxesElement),
+    #[serde(rename = "static_select")]
+    StaticSelect(SlackBlockStaticSelectElement),
+    #[serde(rename = "multi_static_select")]
+    MultiStaticSelect(SlackBlockMultiStaticSelectElement),
+    #[serde(rename = "external_select")]
+    ExternalSelect(SlackBlockExternalSelectElement),
+    #[serde(rename = "multi_external_select")]
+    MultiExternalSelect(SlackBlockMultiExternalSelectElement),
+    #[serde(rename = "users_select")]
+    UsersSelect(SlackBlockUsersSelectElement),
+    #[serde(rename = "multi_users_select")]
+    MultiUsersSelect(SlackBlockMultiUsersSelectElement),
+    #[serde(rename = "conversations_select")]
+    ConversationsSelect(SlackBlockConversationsSelectElement),
+    #[serde(rename = "multi_conversations_select")]
+    MultiConversationsSelect(SlackBlockMultiConversationsSelectElement),
+    #[serde(rename = "channels_select")]
+    ChannelsSelect(SlackBlockChannelsSelectElement),
+    #[serde(rename = "multi_channels_select")]
+    MultiChannelsSelect(SlackBlockMultiChannelsSelectElement),
+    #[serde(rename = "timepicker")]
+    TimePicker(SlackBlockTimePickerElement),
+    #[serde(rename = "datetimepicker")]
+    DateTimePicker(SlackBlockDateTimePickerElement),
+    ///
+    /// Elements that aren't modelled (yet) are preserved as JSON to be able to return them back.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
 }
 
+impl_slack_tagged_with_other!(SlackActionBlockElement, "type");
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackContextBlockElement {
     #[serde(rename = "image")]
     Image(SlackBlockImageElement),
@@ -198,10 +313,17 @@ pub enum SlackContextBlockElement {
     Plain(SlackBlockPlainText),
     #[serde(rename = "mrkdwn")]
     MarkDown(SlackBlockMarkDownText),
+    ///
+    /// Elements that aren't modelled (yet) are preserved as JSON to be able to return them back.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
 }
 
+impl_slack_tagged_with_other!(SlackContextBlockElement, "type");
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackInputBlockElement {
     #[serde(rename = "static_select")]
     StaticSelect(SlackBlockStaticSelectElement),
@@ -231,7 +353,24 @@ pub enum SlackInputBlockElement {
     RadioButtons(SlackBlockRadioButtonsElement),
     #[serde(rename = "checkboxes")]
     Checkboxes(SlackBlockCheckboxesElement),
-}
+    #[serde(rename = "timepicker")]
+    TimePicker(SlackBlockTimePickerElement),
+    #[serde(rename = "datetimepicker")]
+    DateTimePicker(SlackBlockDateTimePickerElement),
+    #[serde(rename = "email_text_input")]
+    EmailInput(SlackBlockEmailInputElement),
+    #[serde(rename = "url_text_input")]
+    UrlInput(SlackBlockUrlInputElement),
+    #[serde(rename = "number_input")]
+    NumberInput(SlackBlockNumberInputElement),
+    ///
+    /// Elements that aren't modelled (yet) are preserved as JSON to be able to return them back.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl_slack_tagged_with_other!(SlackInputBlockElement, "type");
 
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
@@ -309,6 +448,7 @@ pub struct SlackBlockStaticSelectElement {
     pub option_groups: Option<Vec<SlackBlockOptionGroup<SlackBlockPlainTextOnly>>>,
     pub initial_option: Option<SlackBlockChoiceItem<SlackBlockPlainTextOnly>>,
     pub confirm: Option<SlackBlockConfirmItem>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockStaticSelectElement> for SlackSectionBlockElement {
@@ -323,6 +463,12 @@ impl From<SlackBlockStaticSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockStaticSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockStaticSelectElement) -> Self {
+        SlackActionBlockElement::StaticSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockMultiStaticSelectElement {
@@ -333,6 +479,7 @@ pub struct SlackBlockMultiStaticSelectElement {
     pub initial_options: Option<Vec<SlackBlockChoiceItem<SlackBlockPlainTextOnly>>>,
     pub confirm: Option<SlackBlockConfirmItem>,
     pub max_selected_items: Option<u64>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockMultiStaticSelectElement> for SlackSectionBlockElement {
@@ -347,6 +494,12 @@ impl From<SlackBlockMultiStaticSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockMultiStaticSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockMultiStaticSelectElement) -> Self {
+        SlackActionBlockElement::MultiStaticSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockExternalSelectElement {
@@ -354,6 +507,7 @@ pub struct SlackBlockExternalSelectElement {
     pub placeholder: SlackBlockPlainTextOnly,
     pub initial_option: Option<SlackBlockChoiceItem<SlackBlockPlainTextOnly>>,
     pub confirm: Option<SlackBlockConfirmItem>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockExternalSelectElement> for SlackSectionBlockElement {
@@ -368,6 +522,12 @@ impl From<SlackBlockExternalSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockExternalSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockExternalSelectElement) -> Self {
+        SlackActionBlockElement::ExternalSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockMultiExternalSelectElement {
@@ -376,6 +536,7 @@ pub struct SlackBlockMultiExternalSelectElement {
     pub initial_options: Option<Vec<SlackBlockChoiceItem<SlackBlockPlainTextOnly>>>,
     pub confirm: Option<SlackBlockConfirmItem>,
     pub max_selected_items: Option<u64>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockMultiExternalSelectElement> for SlackSectionBlockElement {
@@ -390,6 +551,12 @@ impl From<SlackBlockMultiExternalSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockMultiExternalSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockMultiExternalSelectElement) -> Self {
+        SlackActionBlockElement::MultiExternalSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockUsersSelectElement {
@@ -397,6 +564,7 @@ pub struct SlackBlockUsersSelectElement {
     pub placeholder: SlackBlockPlainTextOnly,
     pub initial_user: Option<String>,
     pub confirm: Option<SlackBlockConfirmItem>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockUsersSelectElement> for SlackSectionBlockElement {
@@ -411,6 +579,12 @@ impl From<SlackBlockUsersSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockUsersSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockUsersSelectElement) -> Self {
+        SlackActionBlockElement::UsersSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockMultiUsersSelectElement {
@@ -419,6 +593,7 @@ pub struct SlackBlockMultiUsersSelectElement {
     pub initial_users: Option<Vec<String>>,
     pub confirm: Option<SlackBlockConfirmItem>,
     pub max_selected_items: Option<u64>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockMultiUsersSelectElement> for SlackSectionBlockElement {
@@ -433,6 +608,12 @@ impl From<SlackBlockMultiUsersSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockMultiUsersSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockMultiUsersSelectElement) -> Self {
+        SlackActionBlockElement::MultiUsersSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockConversationsSelectElement {
@@ -442,6 +623,7 @@ pub struct SlackBlockConversationsSelectElement {
     pub default_to_current_conversation: Option<bool>,
     pub confirm: Option<SlackBlockConfirmItem>,
     pub response_url_enabled: Option<bool>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockConversationsSelectElement> for SlackSectionBlockElement {
@@ -456,6 +638,12 @@ impl From<SlackBlockConversationsSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockConversationsSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockConversationsSelectElement) -> Self {
+        SlackActionBlockElement::ConversationsSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockMultiConversationsSelectElement {
@@ -465,6 +653,7 @@ pub struct SlackBlockMultiConversationsSelectElement {
     pub default_to_current_conversation: Option<bool>,
     pub confirm: Option<SlackBlockConfirmItem>,
     pub max_selected_items: Option<u64>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockMultiConversationsSelectElement> for SlackSectionBlockElement {
@@ -479,6 +668,12 @@ impl From<SlackBlockMultiConversationsSelectElement> for SlackInputBlockElement
     }
 }
 
+impl From<SlackBlockMultiConversationsSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockMultiConversationsSelectElement) -> Self {
+        SlackActionBlockElement::MultiConversationsSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockChannelsSelectElement {
@@ -487,6 +682,7 @@ pub struct SlackBlockChannelsSelectElement {
     pub initial_channel: Option<SlackChannelId>,
     pub confirm: Option<SlackBlockConfirmItem>,
     pub response_url_enabled: Option<bool>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockChannelsSelectElement> for SlackSectionBlockElement {
@@ -501,6 +697,12 @@ impl From<SlackBlockChannelsSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockChannelsSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockChannelsSelectElement) -> Self {
+        SlackActionBlockElement::ChannelsSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockMultiChannelsSelectElement {
@@ -509,6 +711,7 @@ pub struct SlackBlockMultiChannelsSelectElement {
     pub initial_channels: Option<Vec<SlackChannelId>>,
     pub confirm: Option<SlackBlockConfirmItem>,
     pub max_selected_items: Option<u64>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockMultiChannelsSelectElement> for SlackSectionBlockElement {
@@ -523,6 +726,12 @@ impl From<SlackBlockMultiChannelsSelectElement> for SlackInputBlockElement {
     }
 }
 
+impl From<SlackBlockMultiChannelsSelectElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockMultiChannelsSelectElement) -> Self {
+        SlackActionBlockElement::MultiChannelsSelect(element)
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackBlockOverflowElement {
@@ -550,6 +759,7 @@ pub struct SlackBlockDatePickerElement {
     pub placeholder: SlackBlockPlainTextOnly,
     pub initial_date: Option<String>,
     pub confirm: Option<SlackBlockConfirmItem>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockDatePickerElement> for SlackSectionBlockElement {
@@ -579,6 +789,8 @@ pub struct SlackBlockPlainTextInputElement {
     pub multiline: Option<bool>,
     pub min_length: Option<u64>,
     pub max_length: Option<u64>,
+    pub dispatch_action_config: Option<SlackDispatchActionConfig>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockPlainTextInputElement> for SlackSectionBlockElement {
@@ -606,6 +818,7 @@ pub struct SlackBlockRadioButtonsElement {
     pub options: Vec<SlackBlockChoiceItem<SlackBlockText>>,
     pub initial_option: Option<SlackBlockChoiceItem<SlackBlockText>>,
     pub confirm: Option<SlackBlockConfirmItem>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockRadioButtonsElement> for SlackSectionBlockElement {
@@ -633,6 +846,7 @@ pub struct SlackBlockCheckboxesElement {
     pub options: Vec<SlackBlockChoiceItem<SlackBlockText>>,
     pub initial_options: Option<Vec<SlackBlockChoiceItem<SlackBlockText>>>,
     pub confirm: Option<SlackBlockConfirmItem>,
+    pub focus_on_load: Option<bool>,
 }
 
 impl From<SlackBlockCheckboxesElement> for SlackSectionBlockElement {
@@ -653,6 +867,138 @@ impl From<SlackBlockCheckboxesElement> for SlackActionBlockElement {
     }
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackBlockTimePickerElement {
+    pub action_id: SlackActionId,
+    pub placeholder: Option<SlackBlockPlainTextOnly>,
+    pub initial_time: Option<String>,
+    pub timezone: Option<String>,
+    pub confirm: Option<SlackBlockConfirmItem>,
+    pub focus_on_load: Option<bool>,
+}
+
+impl From<SlackBlockTimePickerElement> for SlackSectionBlockElement {
+    fn from(element: SlackBlockTimePickerElement) -> Self {
+        SlackSectionBlockElement::TimePicker(element)
+    }
+}
+
+impl From<SlackBlockTimePickerElement> for SlackInputBlockElement {
+    fn from(element: SlackBlockTimePickerElement) -> Self {
+        SlackInputBlockElement::TimePicker(element)
+    }
+}
+
+impl From<SlackBlockTimePickerElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockTimePickerElement) -> Self {
+        SlackActionBlockElement::TimePicker(element)
+    }
+}
+
+///
+/// Date and time picker, the selected value is a UNIX timestamp.
+/// https://api.slack.com/reference/block-kit/block-elements#datetimepicker
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackBlockDateTimePickerElement {
+    pub action_id: SlackActionId,
+    pub initial_date_time: Option<SlackDateTime>,
+    pub confirm: Option<SlackBlockConfirmItem>,
+    pub focus_on_load: Option<bool>,
+}
+
+impl From<SlackBlockDateTimePickerElement> for SlackInputBlockElement {
+    fn from(element: SlackBlockDateTimePickerElement) -> Self {
+        SlackInputBlockElement::DateTimePicker(element)
+    }
+}
+
+impl From<SlackBlockDateTimePickerElement> for SlackActionBlockElement {
+    fn from(element: SlackBlockDateTimePickerElement) -> Self {
+        SlackActionBlockElement::DateTimePicker(element)
+    }
+}
+
+///
+/// Email input, Slack validates entered values as email addresses.
+/// https://api.slack.com/reference/block-kit/block-elements#email
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackBlockEmailInputElement {
+    pub action_id: SlackActionId,
+    pub placeholder: Option<SlackBlockPlainTextOnly>,
+    pub initial_value: Option<EmailAddress>,
+    pub dispatch_action_config: Option<SlackDispatchActionConfig>,
+    pub focus_on_load: Option<bool>,
+}
+
+impl From<SlackBlockEmailInputElement> for SlackInputBlockElement {
+    fn from(element: SlackBlockEmailInputElement) -> Self {
+        SlackInputBlockElement::EmailInput(element)
+    }
+}
+
+///
+/// URL input, Slack validates entered values as URLs.
+/// https://api.slack.com/reference/block-kit/block-elements#url
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackBlockUrlInputElement {
+    pub action_id: SlackActionId,
+    pub placeholder: Option<SlackBlockPlainTextOnly>,
+    pub initial_value: Option<String>,
+    pub dispatch_action_config: Option<SlackDispatchActionConfig>,
+    pub focus_on_load: Option<bool>,
+}
+
+impl From<SlackBlockUrlInputElement> for SlackInputBlockElement {
+    fn from(element: SlackBlockUrlInputElement) -> Self {
+        SlackInputBlockElement::UrlInput(element)
+    }
+}
+
+///
+/// Number input, integer-only unless `is_decimal_allowed`.
+/// Numbers are passed as strings to keep their exact decimal representation.
+/// https://api.slack.com/reference/block-kit/block-elements#number
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackBlockNumberInputElement {
+    pub action_id: SlackActionId,
+    pub is_decimal_allowed: bool,
+    pub placeholder: Option<SlackBlockPlainTextOnly>,
+    pub initial_value: Option<String>,
+    pub min_value: Option<String>,
+    pub max_value: Option<String>,
+    pub dispatch_action_config: Option<SlackDispatchActionConfig>,
+    pub focus_on_load: Option<bool>,
+}
+
+impl From<SlackBlockNumberInputElement> for SlackInputBlockElement {
+    fn from(element: SlackBlockNumberInputElement) -> Self {
+        SlackInputBlockElement::NumberInput(element)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackDispatchActionConfig {
+    pub trigger_actions_on: Option<Vec<SlackDispatchActionTrigger>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum SlackDispatchActionTrigger {
+    #[serde(rename = "on_enter_pressed")]
+    OnEnterPressed,
+    #[serde(rename = "on_character_entered")]
+    OnCharacterEntered,
+}
+
 /**
 * 'plain_text' type of https://api.slack.com/reference/block-kit/composition-objects#text
 */
@@ -692,6 +1038,12 @@ pub struct SlackBlockPlainTextOnly {
     value: SlackBlockPlainText,
 }
 
+impl SlackBlockPlainTextOnly {
+    pub fn text(&self) -> &str {
+        &self.value.text
+    }
+}
+
 impl SlackBlockPlainText {
     pub fn as_block_text(&self) -> SlackBlockText {
         SlackBlockText::Plain(self.clone())
@@ -779,3 +1131,164 @@ impl From<&str> for SlackBlockPlainTextOnly {
         }
     }
 }
+
+#[test]
+fn check_unknown_blocks_and_elements() {
+    let block_json = serde_json::json!({
+        "type": "brand_new_block",
+        "block_id": "b1",
+        "elements": [{ "type": "brand_new_element" }]
+    });
+    let block: SlackBlock = serde_json::from_value(block_json.clone()).unwrap();
+    assert_eq!(block, SlackBlock::Other(block_json.clone()));
+    assert_eq!(serde_json::to_value(&block).unwrap(), block_json);
+
+    let actions_json = serde_json::json!({
+        "type": "actions",
+        "elements": [
+            { "type": "brand_new_element", "action_id": "a1" },
+            { "type": "button", "action_id": "a2", "text": { "type": "plain_text", "text": "Ok" } }
+        ]
+    });
+    let actions: SlackBlock = serde_json::from_value(actions_json.clone()).unwrap();
+    match &actions {
+        SlackBlock::Actions(actions_block) => {
+            assert_eq!(
+                actions_block.elements[0],
+                SlackActionBlockElement::Other(
+                    serde_json::json!({ "type": "brand_new_element", "action_id": "a1" })
+                )
+            );
+            assert!(matches!(
+                actions_block.elements[1],
+                SlackActionBlockElement::Button(_)
+            ));
+        }
+        other => panic!("Unexpected block: {:?}", other),
+    }
+    assert_eq!(serde_json::to_value(&actions).unwrap(), actions_json);
+
+    assert!(serde_json::from_value::<SlackBlock>(serde_json::json!({
+        "type": "actions",
+        "elements": [{ "type": "button", "action_id": "a2" }]
+    }))
+    .is_err());
+}
+
+#[test]
+fn check_header_video_and_call_blocks() {
+    let blocks_json = serde_json::json!([
+        {
+            "type": "header",
+            "block_id": "h1",
+            "text": { "type": "plain_text", "text": "Release notes", "emoji": true }
+        },
+        {
+            "type": "video",
+            "block_id": "v1",
+            "alt_text": "Demo",
+            "title": { "type": "plain_text", "text": "Product demo" },
+            "title_url": "https://example.com/demo",
+            "thumbnail_url": "https://example.com/demo.png",
+            "video_url": "https://example.com/embed/demo",
+            "description": { "type": "plain_text", "text": "New features" },
+            "author_name": "Team",
+            "provider_name": "Example",
+            "provider_icon_url": "https://example.com/icon.png"
+        },
+        {
+            "type": "call",
+            "block_id": "c1",
+            "call_id": "R1",
+            "api_decoration_available": false,
+            "call": { "v1": { "id": "R1", "app_id": "A1", "join_url": "https://example.com/join" } }
+        }
+    ]);
+
+    let blocks: Vec<SlackBlock> = serde_json::from_value(blocks_json.clone()).unwrap();
+    match &blocks[0] {
+        SlackBlock::Header(header) => assert_eq!(header.text.text(), "Release notes"),
+        other => panic!("Unexpected block: {:?}", other),
+    }
+    match &blocks[1] {
+        SlackBlock::Video(video) => {
+            assert_eq!(video.title.text(), "Product demo");
+            assert_eq!(video.video_url, "https://example.com/embed/demo");
+        }
+        other => panic!("Unexpected block: {:?}", other),
+    }
+    match &blocks[2] {
+        SlackBlock::Call(call) => assert_eq!(call.call_id, "R1"),
+        other => panic!("Unexpected block: {:?}", other),
+    }
+    assert_eq!(serde_json::to_value(&blocks).unwrap(), blocks_json);
+}
+
+#[test]
+fn check_new_input_elements() {
+    let elements_json = serde_json::json!([
+        {
+            "type": "email_text_input",
+            "action_id": "email",
+            "placeholder": { "type": "plain_text", "text": "Email" },
+            "initial_value": "user@example.com",
+            "dispatch_action_config": { "trigger_actions_on": ["on_enter_pressed"] }
+        },
+        {
+            "type": "url_text_input",
+            "action_id": "url",
+            "initial_value": "https://example.com",
+            "focus_on_load": true
+        },
+        {
+            "type": "number_input",
+            "action_id": "amount",
+            "is_decimal_allowed": true,
+            "initial_value": "0.10",
+            "min_value": "0",
+            "max_value": "100.5"
+        },
+        {
+            "type": "datetimepicker",
+            "action_id": "due",
+            "initial_date_time": 1628633820
+        }
+    ]);
+
+    let elements: Vec<SlackInputBlockElement> =
+        serde_json::from_value(elements_json.clone()).unwrap();
+    match &elements[0] {
+        SlackInputBlockElement::EmailInput(email) => assert_eq!(
+            email.initial_value,
+            Some(EmailAddress("user@example.com".into()))
+        ),
+        other => panic!("Unexpected element: {:?}", other),
+    }
+    match &elements[1] {
+        SlackInputBlockElement::UrlInput(url) => {
+            assert_eq!(url.initial_value, Some("https://example.com".into()))
+        }
+        other => panic!("Unexpected element: {:?}", other),
+    }
+    match &elements[2] {
+        SlackInputBlockElement::NumberInput(number) => {
+            assert!(number.is_decimal_allowed);
+            assert_eq!(number.initial_value, Some("0.10".into()));
+        }
+        other => panic!("Unexpected element: {:?}", other),
+    }
+    match &elements[3] {
+        SlackInputBlockElement::DateTimePicker(picker) => assert_eq!(
+            picker
+                .initial_date_time
+                .as_ref()
+                .map(|date| date.0.timestamp()),
+            Some(1628633820)
+        ),
+        other => panic!("Unexpected element: {:?}", other),
+    }
+    assert_eq!(serde_json::to_value(&elements).unwrap(), elements_json);
+
+    let action: SlackActionBlockElement = serde_json::from_value(elements_json[3].clone()).unwrap();
+    assert!(matches!(action, SlackActionBlockElement::DateTimePicker(_)));
+}
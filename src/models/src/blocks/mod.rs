@@ -1,9 +1,20 @@
 mod datetime;
 mod dsl;
 mod kit;
+mod mrkdwn;
+mod mrkdwn_document;
+mod preview;
+mod rich_text;
+mod validation;
 mod view;
 
 pub use datetime::*;
+#[allow(unused_imports)]
 pub use dsl::*;
 pub use kit::*;
+pub use mrkdwn::*;
+pub use mrkdwn_document::*;
+pub use preview::*;
+pub use rich_text::*;
+pub use validation::*;
 pub use view::*;
@@ -0,0 +1,409 @@
+//!
+//! Building mrkdwn texts with escaping of provided texts
+//!
+
+use crate::blocks::*;
+use crate::common::*;
+use chrono::{DateTime, TimeZone};
+
+///
+/// Builds mrkdwn texts: provided texts are escaped (`&`, `<`, `>` and style markers), and styles are applied
+/// without whitespaces next to their markers, so Slack doesn't ignore them.
+/// Block elements (code blocks, quotes and lists) start and end with new lines.
+///
+/// ```
+/// use slack_morphism_models::*;
+/// use slack_morphism_models::blocks::*;
+///
+/// let text: String = SlackMrkdwnBuilder::new()
+///     .text("Build ")
+///     .bold("#42 <main>")
+///     .text(" failed for ")
+///     .user(&"U1".into())
+///     .build();
+///
+/// assert_eq!(text, "Build *#42 &lt;main&gt;* failed for <@U1>");
+/// ```
+///
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SlackMrkdwnBuilder {
+    mrkdwn: String,
+}
+
+impl SlackMrkdwnBuilder {
+    const ZERO_WIDTH_JOINER: char = '\u{200d}';
+
+    pub fn new() -> Self {
+        Self {
+            mrkdwn: String::new(),
+        }
+    }
+
+    pub fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    ///
+    /// Escapes `&`, `<` and `>`, and surrounds style markers (`*`, `_`, `~` and `` ` ``) Slack would apply
+    /// (an opening marker at a word boundary with a matching closing one) with zero-width joiners,
+    /// so Slack shows them as they are. Other markers, URLs and `:emoji:` codes are kept as they are.
+    ///
+    pub fn escape_text(text: &str) -> String {
+        let chars: Vec<char> = Self::escape(text).chars().collect();
+        let protected = Self::protected_ranges(&chars);
+        let mut neutralised = vec![false; chars.len()];
+
+        // The nearest closing markers of each kind on the same line, starting from each index
+        let mut closing_markers: Vec<Vec<(char, usize)>> = vec![Vec::new(); chars.len()];
+        for index in (0..chars.len()).rev() {
+            let c = chars[index];
+            if c == '\n' {
+                continue;
+            }
+            let mut closing = closing_markers.get(index + 1).cloned().unwrap_or_default();
+            let closing_marker = Self::is_style_marker(c)
+                && !protected[index]
+                && index > 0
+                && !chars[index - 1].is_whitespace()
+                && chars
+                    .get(index + 1)
+                    .is_none_or(|next| !next.is_alphanumeric());
+            if closing_marker {
+                closing.retain(|(marker, _)| *marker != c);
+                closing.push((c, index));
+            }
+            closing_markers[index] = closing;
+        }
+
+        for (index, c) in chars.iter().enumerate() {
+            let opening = Self::is_style_marker(*c)
+                && !protected[index]
+                && !neutralised[index]
+                && (index == 0 || !chars[index - 1].is_alphanumeric())
+                && chars
+                    .get(index + 1)
+                    .is_some_and(|next| !next.is_whitespace());
+            if !opening {
+                continue;
+            }
+
+            let closing = closing_markers
+                .get(index + 2)
+                .and_then(|closing| closing.iter().find(|(marker, _)| marker == c))
+                .map(|(_, closing_index)| *closing_index);
+
+            if let Some(closing_index) = closing {
+                neutralised[index] = true;
+                neutralised[closing_index] = true;
+            }
+        }
+
+        let mut escaped = String::with_capacity(chars.len());
+        for (c, neutralised) in chars.into_iter().zip(neutralised) {
+            if neutralised {
+                escaped.push(Self::ZERO_WIDTH_JOINER);
+                escaped.push(c);
+                escaped.push(Self::ZERO_WIDTH_JOINER);
+            } else {
+                escaped.push(c);
+            }
+        }
+        escaped
+    }
+
+    pub fn text(self, text: &str) -> Self {
+        self.raw(&Self::escape_text(text))
+    }
+
+    ///
+    /// Appends already formatted mrkdwn as is
+    ///
+    pub fn raw(mut self, mrkdwn: &str) -> Self {
+        self.mrkdwn.push_str(mrkdwn);
+        self
+    }
+
+    pub fn new_line(self) -> Self {
+        self.raw("\n")
+    }
+
+    pub fn bold(self, text: &str) -> Self {
+        self.bold_with(|mrkdwn| mrkdwn.text(text))
+    }
+
+    pub fn bold_with<F>(self, build: F) -> Self
+    where
+        F: FnOnce(SlackMrkdwnBuilder) -> SlackMrkdwnBuilder,
+    {
+        self.styled("*", build(Self::new()).build())
+    }
+
+    pub fn italic(self, text: &str) -> Self {
+        self.italic_with(|mrkdwn| mrkdwn.text(text))
+    }
+
+    pub fn italic_with<F>(self, build: F) -> Self
+    where
+        F: FnOnce(SlackMrkdwnBuilder) -> SlackMrkdwnBuilder,
+    {
+        self.styled("_", build(Self::new()).build())
+    }
+
+    pub fn strike(self, text: &str) -> Self {
+        self.strike_with(|mrkdwn| mrkdwn.text(text))
+    }
+
+    pub fn strike_with<F>(self, build: F) -> Self
+    where
+        F: FnOnce(SlackMrkdwnBuilder) -> SlackMrkdwnBuilder,
+    {
+        self.styled("~", build(Self::new()).build())
+    }
+
+    ///
+    /// Inline code. Slack has no escaping for backticks in code, so they're replaced with `ˋ`.
+    ///
+    pub fn code(self, text: &str) -> Self {
+        self.styled("`", Self::escape(&text.replace('`', "ˋ")))
+    }
+
+    pub fn code_block(self, text: &str) -> Self {
+        let code = Self::escape(&text.replace("```", "ˋˋˋ"));
+        self.block(&format!("```\n{}\n```", code.trim_matches('\n')))
+    }
+
+    pub fn quote(self, text: &str) -> Self {
+        self.quote_with(|mrkdwn| mrkdwn.text(text))
+    }
+
+    pub fn quote_with<F>(self, build: F) -> Self
+    where
+        F: FnOnce(SlackMrkdwnBuilder) -> SlackMrkdwnBuilder,
+    {
+        let quote = build(Self::new())
+            .build()
+            .split('\n')
+            .map(|line| format!("> {}", line))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.block(&quote)
+    }
+
+    pub fn bullet_list<I, S>(self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let list = items
+            .into_iter()
+            .map(|item| format!("• {}", Self::escape_text(item.as_ref())))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.block(&list)
+    }
+
+    pub fn numbered_list<I, S>(self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let list = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| format!("{}. {}", index + 1, Self::escape_text(item.as_ref())))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.block(&list)
+    }
+
+    pub fn url(self, url: &str) -> Self {
+        let link = format!("<{}>", Self::escape_url(url));
+        self.raw(&link)
+    }
+
+    pub fn link(self, url: &str, text: &str) -> Self {
+        let link = format!("<{}|{}>", Self::escape_url(url), Self::escape(text));
+        self.raw(&link)
+    }
+
+    pub fn user(self, user_id: &SlackUserId) -> Self {
+        self.raw(&user_id.to_slack_format())
+    }
+
+    pub fn channel(self, channel_id: &SlackChannelId) -> Self {
+        self.raw(&channel_id.to_slack_format())
+    }
+
+    pub fn user_group(self, user_group_id: &SlackUserGroupId) -> Self {
+        self.raw(&user_group_id.to_slack_format())
+    }
+
+    pub fn mention_here(self) -> Self {
+        self.raw("<!here>")
+    }
+
+    pub fn mention_channel(self) -> Self {
+        self.raw("<!channel>")
+    }
+
+    pub fn mention_everyone(self) -> Self {
+        self.raw("<!everyone>")
+    }
+
+    ///
+    /// A date formatted by Slack for users in their time zones using `fmt_slack_date`
+    ///
+    pub fn date<TZ: TimeZone>(
+        self,
+        date: DateTime<TZ>,
+        token_string: &str,
+        link: Option<&String>,
+    ) -> Self
+    where
+        <TZ as chrono::offset::TimeZone>::Offset: std::fmt::Display,
+    {
+        self.raw(&fmt_slack_date(date, token_string, link))
+    }
+
+    pub fn emoji(self, name: &str) -> Self {
+        let emoji = format!(":{}:", name.trim_matches(':'));
+        self.raw(&emoji)
+    }
+
+    pub fn build(self) -> String {
+        self.mrkdwn.trim_end_matches('\n').into()
+    }
+
+    pub(crate) fn escape_url(url: &str) -> String {
+        Self::escape(url).replace('|', "%7C")
+    }
+
+    fn is_style_marker(c: char) -> bool {
+        matches!(c, '*' | '_' | '~' | '`')
+    }
+
+    ///
+    /// Marks URLs (up to a whitespace or an escaped `<`/`>`) and `:emoji:` codes, where Slack doesn't apply styles
+    ///
+    fn protected_ranges(chars: &[char]) -> Vec<bool> {
+        let mut protected = vec![false; chars.len()];
+        let mut index = 0;
+        while index < chars.len() {
+            let rest: String = chars[index..chars.len().min(index + 8)].iter().collect();
+            let url = (index == 0 || !chars[index - 1].is_alphanumeric())
+                && ["http://", "https://", "mailto:"]
+                    .iter()
+                    .any(|scheme| rest.starts_with(scheme));
+
+            let end = if url {
+                (index..chars.len())
+                    .find(|end| {
+                        let rest: String = chars[*end..chars.len().min(end + 4)].iter().collect();
+                        chars[*end].is_whitespace() || rest == "&lt;" || rest == "&gt;"
+                    })
+                    .unwrap_or(chars.len())
+            } else if chars[index] == ':' {
+                chars[index + 1..]
+                    .iter()
+                    .position(|c| !(c.is_ascii_alphanumeric() || "_+-'".contains(*c)))
+                    .filter(|length| *length > 0 && chars[index + 1 + length] == ':')
+                    .map_or(index, |length| index + length + 2)
+            } else {
+                index
+            };
+
+            if end > index {
+                protected[index..end].iter_mut().for_each(|c| *c = true);
+                index = end;
+            } else {
+                index += 1;
+            }
+        }
+        protected
+    }
+
+    fn styled(self, marker: &str, mrkdwn: String) -> Self {
+        let core = mrkdwn.trim();
+        if core.is_empty() {
+            self.raw(&mrkdwn)
+        } else {
+            // Slack doesn't apply styles with whitespaces next to the markers
+            let styled = mrkdwn.replacen(core, &format!("{}{}{}", marker, core, marker), 1);
+            self.raw(&styled)
+        }
+    }
+
+    fn block(mut self, mrkdwn: &str) -> Self {
+        if !self.mrkdwn.is_empty() && !self.mrkdwn.ends_with('\n') {
+            self.mrkdwn.push('\n');
+        }
+        self.mrkdwn.push_str(mrkdwn);
+        self.mrkdwn.push('\n');
+        self
+    }
+}
+
+impl From<SlackMrkdwnBuilder> for SlackBlockMarkDownText {
+    fn from(builder: SlackMrkdwnBuilder) -> Self {
+        SlackBlockMarkDownText::new(builder.build())
+    }
+}
+
+impl From<SlackMrkdwnBuilder> for SlackBlockText {
+    fn from(builder: SlackMrkdwnBuilder) -> Self {
+        SlackBlockText::MarkDown(builder.into())
+    }
+}
+
+#[test]
+fn check_mrkdwn_builder() {
+    let text = SlackMrkdwnBuilder::new()
+        .bold_with(|mrkdwn| mrkdwn.text("Deploy ").italic("R&D "))
+        .text("to ")
+        .channel(&"C1".into())
+        .quote("a < b\nb > c")
+        .bullet_list(vec!["first", "*second*"])
+        .text("by ")
+        .user_group(&"S1".into())
+        .text(" ")
+        .link("https://example.com/?a=1&b=2", "docs | notes")
+        .build();
+
+    assert_eq!(
+        text,
+        "*Deploy _R&amp;D_* to <#C1>\n\
+         > a &lt; b\n\
+         > b &gt; c\n\
+         • first\n\
+         • \u{200d}*\u{200d}second\u{200d}*\u{200d}\n\
+         by <!subteam^S1> <https://example.com/?a=1&amp;b=2|docs | notes>"
+    );
+}
+
+#[test]
+fn check_mrkdwn_builder_style_markers() {
+    let text = SlackMrkdwnBuilder::new()
+        .bold("2 * 3")
+        .text(" is _not_ `code`")
+        .numbered_list(vec!["~draft~"])
+        .build();
+
+    assert_eq!(
+        text,
+        "*2 * 3* is \u{200d}_\u{200d}not\u{200d}_\u{200d} \u{200d}`\u{200d}code\u{200d}`\u{200d}\n\
+         1. \u{200d}~\u{200d}draft\u{200d}~\u{200d}"
+    );
+
+    assert_eq!(
+        SlackMrkdwnBuilder::escape_text(
+            "See https://x.com/a_b_c/*x* :white_check_mark: snake_case_name a_b *c*d"
+        ),
+        "See https://x.com/a_b_c/*x* :white_check_mark: snake_case_name a_b *c*d"
+    );
+    assert_eq!(
+        SlackMrkdwnBuilder::escape_text("_<https://x.com>_ and *:+1: done*\n*not\nbold*"),
+        "\u{200d}_\u{200d}&lt;https://x.com&gt;\u{200d}_\u{200d} and \u{200d}*\u{200d}:+1: done\u{200d}*\u{200d}\n*not\nbold*"
+    );
+}
@@ -0,0 +1,777 @@
+//!
+//! Parsing mrkdwn texts (such as `text` of messages) and rendering them to plain text, HTML and Markdown
+//!
+
+use crate::common::*;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+
+///
+/// A parsed mrkdwn text: paragraphs, quotes and code blocks of inline elements.
+/// https://api.slack.com/reference/surfaces/formatting
+///
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackMrkdwnDocument {
+    pub blocks: Vec<SlackMrkdwnBlock>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackMrkdwnBlock {
+    Paragraph(Vec<SlackMrkdwnInline>),
+    Quote(Vec<SlackMrkdwnInline>),
+    CodeBlock(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackMrkdwnInline {
+    Text(String),
+    Bold(Vec<SlackMrkdwnInline>),
+    Italic(Vec<SlackMrkdwnInline>),
+    Strike(Vec<SlackMrkdwnInline>),
+    Code(String),
+    Link(SlackMrkdwnLink),
+    User(SlackMrkdwnUser),
+    Channel(SlackMrkdwnChannel),
+    UserGroup(SlackMrkdwnUserGroup),
+    /// `here`, `channel` or `everyone`
+    Broadcast(String),
+    Date(SlackMrkdwnDate),
+    Emoji(String),
+    LineBreak,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackMrkdwnLink {
+    pub url: String,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackMrkdwnUser {
+    pub user_id: SlackUserId,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackMrkdwnChannel {
+    pub channel_id: SlackChannelId,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackMrkdwnUserGroup {
+    pub usergroup_id: SlackUserGroupId,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Builder)]
+pub struct SlackMrkdwnDate {
+    pub timestamp: SlackDateTime,
+    pub format: String,
+    pub url: Option<String>,
+    pub fallback: Option<String>,
+}
+
+impl SlackMrkdwnDocument {
+    pub fn parse(mrkdwn: &str) -> Self {
+        let mut blocks = Vec::new();
+        let segments: Vec<&str> = mrkdwn.split("```").collect();
+        for (index, segment) in segments.iter().enumerate() {
+            if index % 2 == 0 {
+                parse_mrkdwn_lines(segment, &mut blocks);
+            } else if index < segments.len() - 1 {
+                blocks.push(SlackMrkdwnBlock::CodeBlock(unescape_mrkdwn(
+                    segment.trim_matches('\n'),
+                )));
+            } else {
+                // Code blocks that aren't closed are texts
+                parse_mrkdwn_lines(&format!("```{}", segment), &mut blocks);
+            }
+        }
+        Self { blocks }
+    }
+
+    pub fn to_plain_text(&self) -> String {
+        SlackMrkdwnRenderer::new().to_plain_text(self)
+    }
+
+    pub fn to_html(&self) -> String {
+        SlackMrkdwnRenderer::new().to_html(self)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        SlackMrkdwnRenderer::new().to_markdown(self)
+    }
+}
+
+///
+/// Renders parsed mrkdwn to plain text, HTML or Markdown (CommonMark with `~~` for strikethrough).
+///
+/// Names of mentioned users, channels and user groups are resolved with the provided callbacks,
+/// otherwise their labels from mrkdwn or their IDs are used. Dates are rendered in UTC.
+///
+#[derive(Default)]
+pub struct SlackMrkdwnRenderer<'a> {
+    user_names: Option<Box<SlackMrkdwnNameResolver<'a, SlackUserId>>>,
+    channel_names: Option<Box<SlackMrkdwnNameResolver<'a, SlackChannelId>>>,
+    user_group_names: Option<Box<SlackMrkdwnNameResolver<'a, SlackUserGroupId>>>,
+}
+
+pub type SlackMrkdwnNameResolver<'a, ID> = dyn Fn(&ID) -> Option<String> + 'a;
+
+enum SlackMrkdwnRenderFormat {
+    PlainText,
+    AnsiText,
+    Html,
+    Markdown,
+}
+
+impl<'a> SlackMrkdwnRenderer<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_user_names<F>(self, user_names: F) -> Self
+    where
+        F: Fn(&SlackUserId) -> Option<String> + 'a,
+    {
+        Self {
+            user_names: Some(Box::new(user_names)),
+            ..self
+        }
+    }
+
+    pub fn with_channel_names<F>(self, channel_names: F) -> Self
+    where
+        F: Fn(&SlackChannelId) -> Option<String> + 'a,
+    {
+        Self {
+            channel_names: Some(Box::new(channel_names)),
+            ..self
+        }
+    }
+
+    pub fn with_user_group_names<F>(self, user_group_names: F) -> Self
+    where
+        F: Fn(&SlackUserGroupId) -> Option<String> + 'a,
+    {
+        Self {
+            user_group_names: Some(Box::new(user_group_names)),
+            ..self
+        }
+    }
+
+    pub fn to_plain_text(&self, document: &SlackMrkdwnDocument) -> String {
+        self.render_text_blocks(document, &SlackMrkdwnRenderFormat::PlainText)
+    }
+
+    ///
+    /// Plain text with styles and colors for terminals using ANSI escape codes
+    ///
+    pub fn to_ansi_text(&self, document: &SlackMrkdwnDocument) -> String {
+        self.render_text_blocks(document, &SlackMrkdwnRenderFormat::AnsiText)
+    }
+
+    pub fn to_html(&self, document: &SlackMrkdwnDocument) -> String {
+        let format = SlackMrkdwnRenderFormat::Html;
+        document
+            .blocks
+            .iter()
+            .map(|block| match block {
+                SlackMrkdwnBlock::Paragraph(inlines) => {
+                    format!("<p>{}</p>", self.render_inlines(inlines, &format))
+                }
+                SlackMrkdwnBlock::Quote(inlines) => format!(
+                    "<blockquote>{}</blockquote>",
+                    self.render_inlines(inlines, &format)
+                ),
+                SlackMrkdwnBlock::CodeBlock(code) => {
+                    format!("<pre><code>{}</code></pre>", escape_html(code))
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn to_markdown(&self, document: &SlackMrkdwnDocument) -> String {
+        let format = SlackMrkdwnRenderFormat::Markdown;
+        document
+            .blocks
+            .iter()
+            .map(|block| match block {
+                SlackMrkdwnBlock::Paragraph(inlines) => self.render_inlines(inlines, &format),
+                SlackMrkdwnBlock::Quote(inlines) => self
+                    .render_inlines(inlines, &format)
+                    .split('\n')
+                    .map(|line| format!("> {}", line))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                SlackMrkdwnBlock::CodeBlock(code) => {
+                    let fence = "`".repeat(std::cmp::max(3, longest_backticks_run(code) + 1));
+                    format!("{}\n{}\n{}", fence, code, fence)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    fn render_text_blocks(
+        &self,
+        document: &SlackMrkdwnDocument,
+        format: &SlackMrkdwnRenderFormat,
+    ) -> String {
+        let ansi = matches!(format, SlackMrkdwnRenderFormat::AnsiText);
+        document
+            .blocks
+            .iter()
+            .map(|block| match block {
+                SlackMrkdwnBlock::Paragraph(inlines) => self.render_inlines(inlines, format),
+                SlackMrkdwnBlock::Quote(inlines) if ansi => self
+                    .render_inlines(inlines, format)
+                    .split('\n')
+                    .map(|line| format!("\x1b[2m│\x1b[22m {}", line))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                SlackMrkdwnBlock::Quote(inlines) => self.render_inlines(inlines, format),
+                SlackMrkdwnBlock::CodeBlock(code) if ansi => format!("\x1b[36m{}\x1b[39m", code),
+                SlackMrkdwnBlock::CodeBlock(code) => code.clone(),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn render_inlines(
+        &self,
+        inlines: &[SlackMrkdwnInline],
+        format: &SlackMrkdwnRenderFormat,
+    ) -> String {
+        inlines
+            .iter()
+            .map(|inline| self.render_inline(inline, format))
+            .collect()
+    }
+
+    fn render_inline(
+        &self,
+        inline: &SlackMrkdwnInline,
+        format: &SlackMrkdwnRenderFormat,
+    ) -> String {
+        match inline {
+            SlackMrkdwnInline::Text(text) => render_text(text, format),
+            SlackMrkdwnInline::Bold(inlines) => {
+                self.render_styled(inlines, format, "strong", "**", ("\x1b[1m", "\x1b[22m"))
+            }
+            SlackMrkdwnInline::Italic(inlines) => {
+                self.render_styled(inlines, format, "em", "_", ("\x1b[3m", "\x1b[23m"))
+            }
+            SlackMrkdwnInline::Strike(inlines) => {
+                self.render_styled(inlines, format, "del", "~~", ("\x1b[9m", "\x1b[29m"))
+            }
+            SlackMrkdwnInline::Code(code) => match format {
+                SlackMrkdwnRenderFormat::PlainText => code.clone(),
+                SlackMrkdwnRenderFormat::AnsiText => format!("\x1b[36m{}\x1b[39m", code),
+                SlackMrkdwnRenderFormat::Html => format!("<code>{}</code>", escape_html(code)),
+                SlackMrkdwnRenderFormat::Markdown => {
+                    let ticks = "`".repeat(longest_backticks_run(code) + 1);
+                    if code.starts_with('`') || code.ends_with('`') {
+                        format!("{} {} {}", ticks, code, ticks)
+                    } else {
+                        format!("{}{}{}", ticks, code, ticks)
+                    }
+                }
+            },
+            SlackMrkdwnInline::Link(link) => {
+                let label = link.label.as_ref().unwrap_or(&link.url);
+                render_link(&link.url, label, format)
+            }
+            SlackMrkdwnInline::User(user) => {
+                let name = self
+                    .user_names
+                    .as_ref()
+                    .and_then(|user_names| user_names(&user.user_id))
+                    .or_else(|| user.label.clone())
+                    .unwrap_or_else(|| user.user_id.value().clone());
+                render_mention(&format!("@{}", name.trim_start_matches('@')), format)
+            }
+            SlackMrkdwnInline::Channel(channel) => {
+                let name = self
+                    .channel_names
+                    .as_ref()
+                    .and_then(|channel_names| channel_names(&channel.channel_id))
+                    .or_else(|| channel.label.clone())
+                    .unwrap_or_else(|| channel.channel_id.value().clone());
+                render_mention(&format!("#{}", name.trim_start_matches('#')), format)
+            }
+            SlackMrkdwnInline::UserGroup(user_group) => {
+                let name = self
+                    .user_group_names
+                    .as_ref()
+                    .and_then(|user_group_names| user_group_names(&user_group.usergroup_id))
+                    .or_else(|| user_group.label.clone())
+                    .unwrap_or_else(|| user_group.usergroup_id.value().clone());
+                render_mention(&format!("@{}", name.trim_start_matches('@')), format)
+            }
+            SlackMrkdwnInline::Broadcast(range) => render_mention(&format!("@{}", range), format),
+            SlackMrkdwnInline::Date(date) => {
+                let text = format_mrkdwn_date(&date.timestamp.0, &date.format);
+                match (format, &date.url) {
+                    (SlackMrkdwnRenderFormat::Html, None) => format!(
+                        "<time datetime=\"{}\">{}</time>",
+                        date.timestamp.0.to_rfc3339(),
+                        escape_html(&text)
+                    ),
+                    (_, Some(url)) => render_link(url, &text, format),
+                    (_, None) => render_text(&text, format),
+                }
+            }
+            SlackMrkdwnInline::Emoji(name) => render_text(&format!(":{}:", name), format),
+            SlackMrkdwnInline::LineBreak => match format {
+                SlackMrkdwnRenderFormat::PlainText | SlackMrkdwnRenderFormat::AnsiText => {
+                    "\n".into()
+                }
+                SlackMrkdwnRenderFormat::Html => "<br>".into(),
+                SlackMrkdwnRenderFormat::Markdown => "\\\n".into(),
+            },
+        }
+    }
+
+    fn render_styled(
+        &self,
+        inlines: &[SlackMrkdwnInline],
+        format: &SlackMrkdwnRenderFormat,
+        html_tag: &str,
+        markdown_marker: &str,
+        (ansi_on, ansi_off): (&str, &str),
+    ) -> String {
+        let rendered = self.render_inlines(inlines, format);
+        match format {
+            SlackMrkdwnRenderFormat::PlainText => rendered,
+            SlackMrkdwnRenderFormat::AnsiText => format!("{}{}{}", ansi_on, rendered, ansi_off),
+            SlackMrkdwnRenderFormat::Html => format!("<{}>{}</{}>", html_tag, rendered, html_tag),
+            SlackMrkdwnRenderFormat::Markdown => {
+                format!("{}{}{}", markdown_marker, rendered, markdown_marker)
+            }
+        }
+    }
+}
+
+fn parse_mrkdwn_lines(mrkdwn: &str, blocks: &mut Vec<SlackMrkdwnBlock>) {
+    let mut current: Option<SlackMrkdwnBlock> = None;
+    let mut lines = mrkdwn.split('\n');
+
+    while let Some(line) = lines.next() {
+        if let Some(quoted) = strip_quote_prefix(line, &["&gt;&gt;&gt;", ">>>"]) {
+            // Everything after `>>>` is quoted
+            blocks.extend(current.take());
+            let quoted = std::iter::once(quoted.trim_start())
+                .chain(lines.by_ref())
+                .collect::<Vec<&str>>()
+                .join("\n");
+            let mut inlines = Vec::new();
+            for (index, line) in quoted.trim_end().split('\n').enumerate() {
+                if index > 0 {
+                    inlines.push(SlackMrkdwnInline::LineBreak);
+                }
+                inlines.extend(parse_mrkdwn_inlines(line));
+            }
+            blocks.push(SlackMrkdwnBlock::Quote(inlines));
+        } else if let Some(quoted) = strip_quote_prefix(line, &["&gt;", ">"]) {
+            let inlines = parse_mrkdwn_inlines(quoted.strip_prefix(' ').unwrap_or(quoted));
+            match &mut current {
+                Some(SlackMrkdwnBlock::Quote(quote)) => {
+                    quote.push(SlackMrkdwnInline::LineBreak);
+                    quote.extend(inlines);
+                }
+                _ => {
+                    blocks.extend(current.take());
+                    current = Some(SlackMrkdwnBlock::Quote(inlines));
+                }
+            }
+        } else if line.trim().is_empty() {
+            blocks.extend(current.take());
+        } else {
+            let inlines = parse_mrkdwn_inlines(line);
+            match &mut current {
+                Some(SlackMrkdwnBlock::Paragraph(paragraph)) => {
+                    paragraph.push(SlackMrkdwnInline::LineBreak);
+                    paragraph.extend(inlines);
+                }
+                _ => {
+                    blocks.extend(current.take());
+                    current = Some(SlackMrkdwnBlock::Paragraph(inlines));
+                }
+            }
+        }
+    }
+
+    blocks.extend(current.take());
+}
+
+fn strip_quote_prefix<'a>(line: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    prefixes.iter().find_map(|prefix| line.strip_prefix(prefix))
+}
+
+fn parse_mrkdwn_inlines(line: &str) -> Vec<SlackMrkdwnInline> {
+    let chars: Vec<char> = line.chars().collect();
+    parse_mrkdwn_chars(&chars)
+}
+
+fn parse_mrkdwn_chars(chars: &[char]) -> Vec<SlackMrkdwnInline> {
+    let mut inlines = Vec::new();
+    let mut text = String::new();
+    let mut index = 0;
+    // Characters without closing ones till the end, so they aren't searched again
+    let mut unclosed: Vec<char> = Vec::new();
+
+    while index < chars.len() {
+        let ch = chars[index];
+        let at_boundary = index == 0 || !chars[index - 1].is_alphanumeric();
+        let searched = match ch {
+            '<' | '`' | '*' | '_' | '~' | ':' => !unclosed.contains(&ch),
+            _ => false,
+        };
+        let parsed = match ch {
+            '<' if searched => find_char(chars, index + 1, '>').map(|end| {
+                Some((
+                    parse_mrkdwn_token(&collect_chars(&chars[index + 1..end])),
+                    end,
+                ))
+            }),
+            '`' if searched => find_char(chars, index + 1, '`').map(|end| {
+                if end > index + 1 {
+                    let code = unescape_mrkdwn(&collect_chars(&chars[index + 1..end]));
+                    Some((SlackMrkdwnInline::Code(code), end))
+                } else {
+                    None
+                }
+            }),
+            '*' | '_' | '~' if searched && at_boundary && is_opening_marker(chars, index) => {
+                find_closing_marker(chars, index).map(|end| {
+                    let styled = parse_mrkdwn_chars(&chars[index + 1..end]);
+                    let inline = match ch {
+                        '*' => SlackMrkdwnInline::Bold(styled),
+                        '_' => SlackMrkdwnInline::Italic(styled),
+                        _ => SlackMrkdwnInline::Strike(styled),
+                    };
+                    Some((inline, end))
+                })
+            }
+            ':' if searched && at_boundary => find_char(chars, index + 1, ':').map(|end| {
+                if end > index + 1
+                    && chars[index + 1..end]
+                        .iter()
+                        .all(|c| c.is_alphanumeric() || "_+-'".contains(*c))
+                {
+                    let name = collect_chars(&chars[index + 1..end]);
+                    Some((SlackMrkdwnInline::Emoji(name), end))
+                } else {
+                    None
+                }
+            }),
+            _ => Some(None),
+        };
+
+        match parsed {
+            Some(Some((inline, end))) => {
+                if !text.is_empty() {
+                    inlines.push(SlackMrkdwnInline::Text(unescape_mrkdwn(&text)));
+                    text.clear();
+                }
+                inlines.push(inline);
+                index = end + 1;
+            }
+            not_parsed => {
+                if not_parsed.is_none() {
+                    unclosed.push(ch);
+                }
+                text.push(ch);
+                index += 1;
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        inlines.push(SlackMrkdwnInline::Text(unescape_mrkdwn(&text)));
+    }
+    inlines
+}
+
+fn parse_mrkdwn_token(token: &str) -> SlackMrkdwnInline {
+    let (value, label) = match token.split_once('|') {
+        Some((value, label)) => (value, Some(unescape_mrkdwn(label))),
+        None => (token, None),
+    };
+
+    if let Some(user_id) = value.strip_prefix('@') {
+        SlackMrkdwnInline::User(SlackMrkdwnUser::new(user_id.into()).opt_label(label))
+    } else if let Some(channel_id) = value.strip_prefix('#') {
+        SlackMrkdwnInline::Channel(SlackMrkdwnChannel::new(channel_id.into()).opt_label(label))
+    } else if let Some(command) = value.strip_prefix('!') {
+        let mut parts = command.split('^');
+        match (parts.next(), parts.next()) {
+            (Some("subteam"), Some(usergroup_id)) => SlackMrkdwnInline::UserGroup(
+                SlackMrkdwnUserGroup::new(usergroup_id.into()).opt_label(label),
+            ),
+            (Some("date"), Some(timestamp)) => {
+                let format = parts.next();
+                let url = parts.next();
+                match (
+                    timestamp
+                        .parse::<i64>()
+                        .ok()
+                        .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single()),
+                    format,
+                ) {
+                    (Some(timestamp), Some(format)) => SlackMrkdwnInline::Date(
+                        SlackMrkdwnDate::new(SlackDateTime(timestamp), format.into())
+                            .opt_url(url.map(unescape_mrkdwn))
+                            .opt_fallback(label),
+                    ),
+                    _ => SlackMrkdwnInline::Text(label.unwrap_or_default()),
+                }
+            }
+            (Some(range @ ("here" | "channel" | "everyone")), _) => {
+                SlackMrkdwnInline::Broadcast(range.into())
+            }
+            _ => SlackMrkdwnInline::Text(label.unwrap_or_default()),
+        }
+    } else {
+        SlackMrkdwnInline::Link(SlackMrkdwnLink::new(unescape_mrkdwn(value)).opt_label(label))
+    }
+}
+
+fn find_char(chars: &[char], from: usize, ch: char) -> Option<usize> {
+    chars
+        .iter()
+        .skip(from)
+        .position(|c| *c == ch)
+        .map(|position| position + from)
+}
+
+fn is_opening_marker(chars: &[char], start: usize) -> bool {
+    let marker = chars[start];
+    matches!(chars.get(start + 1), Some(next) if !next.is_whitespace() && *next != marker)
+}
+
+///
+/// Closing markers don't depend on opening ones, so if there is none after an opening marker,
+/// there are none after further opening markers too
+///
+fn find_closing_marker(chars: &[char], start: usize) -> Option<usize> {
+    let marker = chars[start];
+    (start + 2..chars.len()).find(|index| {
+        chars[*index] == marker
+            && !chars[*index - 1].is_whitespace()
+            && chars
+                .get(*index + 1)
+                .iter()
+                .all(|next| !next.is_alphanumeric())
+    })
+}
+
+fn collect_chars(chars: &[char]) -> String {
+    chars.iter().collect()
+}
+
+fn unescape_mrkdwn(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if "\\`*_~[]<>".contains(ch) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn longest_backticks_run(text: &str) -> usize {
+    text.split(|ch| ch != '`')
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0)
+}
+
+fn render_text(text: &str, format: &SlackMrkdwnRenderFormat) -> String {
+    match format {
+        SlackMrkdwnRenderFormat::PlainText | SlackMrkdwnRenderFormat::AnsiText => text.into(),
+        SlackMrkdwnRenderFormat::Html => escape_html(text),
+        SlackMrkdwnRenderFormat::Markdown => escape_markdown(text),
+    }
+}
+
+///
+/// Links with schemes other than `http`, `https` and `mailto` (e.g. `javascript:`) are rendered as their labels
+///
+fn render_link(url: &str, label: &str, format: &SlackMrkdwnRenderFormat) -> String {
+    if !is_safe_link_url(url) {
+        return render_text(label, format);
+    }
+
+    match format {
+        SlackMrkdwnRenderFormat::PlainText => label.into(),
+        SlackMrkdwnRenderFormat::AnsiText => format!("\x1b[4m{}\x1b[24m", label),
+        SlackMrkdwnRenderFormat::Html => format!(
+            "<a href=\"{}\">{}</a>",
+            escape_html(url),
+            escape_html(label)
+        ),
+        SlackMrkdwnRenderFormat::Markdown if url == label => format!("<{}>", url),
+        SlackMrkdwnRenderFormat::Markdown => {
+            if url.contains(|ch: char| ch.is_whitespace() || ch == '(' || ch == ')') {
+                format!("[{}](<{}>)", escape_markdown(label), url)
+            } else {
+                format!("[{}]({})", escape_markdown(label), url)
+            }
+        }
+    }
+}
+
+fn is_safe_link_url(url: &str) -> bool {
+    let url = url.trim_start().to_ascii_lowercase();
+    ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+}
+
+fn render_mention(mention: &str, format: &SlackMrkdwnRenderFormat) -> String {
+    match format {
+        SlackMrkdwnRenderFormat::Html => format!(
+            "<span class=\"slack-mention\">{}</span>",
+            escape_html(mention)
+        ),
+        SlackMrkdwnRenderFormat::AnsiText => format!("\x1b[34m{}\x1b[39m", mention),
+        _ => render_text(mention, format),
+    }
+}
+
+///
+/// Replaces Slack date tokens (`{date_num}`, `{date}`, `{time}`, etc.) in the date format
+///
+fn format_mrkdwn_date(date: &DateTime<Utc>, format: &str) -> String {
+    let day = date.day();
+    let day_suffix = match (day % 10, day % 100) {
+        (1, ordinal) if ordinal != 11 => "st",
+        (2, ordinal) if ordinal != 12 => "nd",
+        (3, ordinal) if ordinal != 13 => "rd",
+        _ => "th",
+    };
+    let full_date = format!(
+        "{} {}{}, {}",
+        date.format("%B"),
+        day,
+        day_suffix,
+        date.year()
+    );
+    let short_date = date.format("%b %-d, %Y").to_string();
+    let long_date = format!("{}, {}", date.format("%A"), full_date);
+
+    // Relative dates (today, yesterday) depend on the current time, so they aren't used
+    format
+        .replace("{date_num}", &date.format("%Y-%m-%d").to_string())
+        .replace("{date_short_pretty}", &short_date)
+        .replace("{date_long_pretty}", &long_date)
+        .replace("{date_pretty}", &full_date)
+        .replace("{date_short}", &short_date)
+        .replace("{date_long}", &long_date)
+        .replace("{date}", &full_date)
+        .replace("{time_secs}", &date.format("%-I:%M:%S %p").to_string())
+        .replace("{time}", &date.format("%-I:%M %p").to_string())
+}
+
+#[test]
+fn check_mrkdwn_document_rendering() {
+    let document = SlackMrkdwnDocument::parse(
+        "Hi <@U1>, *build <https://ci.example.com/1|#1>* _failed_ in <#C1|general> :boom:\n\
+         &gt; R&amp;D said: `a &lt; b`\n\
+         ```cargo test```\
+         Due <!date^1392734382^{date_num} {time}|Feb 18>, cc <!subteam^S1|@devs> <!here>",
+    );
+
+    let renderer = SlackMrkdwnRenderer::new().with_user_names(|user_id| {
+        if user_id.value() == "U1" {
+            Some("alice".into())
+        } else {
+            None
+        }
+    });
+
+    assert_eq!(
+        renderer.to_plain_text(&document),
+        "Hi @alice, build #1 failed in #general :boom:\n\
+         R&D said: a < b\n\
+         cargo test\n\
+         Due 2014-02-18 2:39 PM, cc @devs @here"
+    );
+
+    assert_eq!(
+        renderer.to_html(&document),
+        "<p>Hi <span class=\"slack-mention\">@alice</span>, \
+         <strong>build <a href=\"https://ci.example.com/1\">#1</a></strong> <em>failed</em> in \
+         <span class=\"slack-mention\">#general</span> :boom:</p>\n\
+         <blockquote>R&amp;D said: <code>a &lt; b</code></blockquote>\n\
+         <pre><code>cargo test</code></pre>\n\
+         <p>Due <time datetime=\"2014-02-18T14:39:42+00:00\">2014-02-18 2:39 PM</time>, cc \
+         <span class=\"slack-mention\">@devs</span> <span class=\"slack-mention\">@here</span></p>"
+    );
+
+    assert_eq!(
+        renderer.to_markdown(&document),
+        "Hi @alice, **build [#1](https://ci.example.com/1)** _failed_ in #general :boom:\n\n\
+         > R&D said: `a < b`\n\n\
+         ```\ncargo test\n```\n\n\
+         Due 2014-02-18 2:39 PM, cc @devs @here"
+    );
+}
+
+#[test]
+fn check_mrkdwn_unsafe_links() {
+    let document = SlackMrkdwnDocument::parse(
+        "<javascript:alert(1)|click *me*> <JavaScript:alert(2)> <mailto:dev@example.com|mail>",
+    );
+
+    assert_eq!(
+        document.to_html(),
+        "<p>click *me* JavaScript:alert(2) <a href=\"mailto:dev@example.com\">mail</a></p>"
+    );
+    assert_eq!(
+        document.to_markdown(),
+        "click \\*me\\* JavaScript:alert(2) [mail](mailto:dev@example.com)"
+    );
+}
+
+#[test]
+fn check_mrkdwn_unclosed_markers() {
+    let document =
+        SlackMrkdwnDocument::parse("<https://example.com> a <b _c _d ~e `f :g *bold* <h");
+
+    assert_eq!(
+        document.blocks,
+        vec![SlackMrkdwnBlock::Paragraph(vec![
+            SlackMrkdwnInline::Link(SlackMrkdwnLink::new("https://example.com".into())),
+            SlackMrkdwnInline::Text(" a <b _c _d ~e `f :g ".into()),
+            SlackMrkdwnInline::Bold(vec![SlackMrkdwnInline::Text("bold".into())]),
+            SlackMrkdwnInline::Text(" <h".into()),
+        ])]
+    );
+
+    // Unclosed markers aren't searched again, so long lines are parsed in linear time
+    let started = std::time::Instant::now();
+    SlackMrkdwnDocument::parse(&" *a".repeat(100_000));
+    SlackMrkdwnDocument::parse(&"<a ".repeat(100_000));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
@@ -0,0 +1,1010 @@
+//!
+//! Static previews of Block Kit messages and views as HTML or terminal texts,
+//! to review layouts and take snapshots of them without posting them to Slack.
+//!
+
+use crate::blocks::*;
+use crate::messages::SlackMessageContent;
+use rvstruct::ValueStruct;
+
+///
+/// Formats of Block Kit previews: an HTML approximation (use `SLACK_BLOCK_KIT_PREVIEW_CSS` to style it),
+/// plain text, or text for terminals with styles and colors using ANSI escape codes.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackBlockKitPreviewFormat {
+    Html,
+    Text,
+    AnsiText,
+}
+
+pub const SLACK_BLOCK_KIT_PREVIEW_CSS: &str = r#"
+.slack-message, .slack-modal, .slack-home { font-family: sans-serif; font-size: 15px; max-width: 600px; }
+.slack-modal-title { font-size: 18px; font-weight: bold; margin-bottom: 12px; }
+.slack-modal-footer { display: flex; justify-content: flex-end; gap: 8px; margin-top: 12px; }
+.slack-header { font-size: 18px; margin: 8px 0; }
+.slack-section { display: flex; justify-content: space-between; gap: 8px; margin: 8px 0; }
+.slack-section-fields { display: grid; grid-template-columns: 1fr 1fr; gap: 8px; }
+.slack-section p, .slack-context p { margin: 0; }
+.slack-context { display: flex; gap: 8px; align-items: center; color: #616061; font-size: 13px; }
+.slack-context-image { width: 20px; height: 20px; }
+.slack-image img { max-width: 100%; }
+.slack-actions { display: flex; flex-wrap: wrap; gap: 8px; margin: 8px 0; }
+.slack-button, .slack-overflow { border: 1px solid #ccc; border-radius: 4px; background: #fff; padding: 4px 12px; }
+.slack-button-primary { background: #007a5a; border-color: #007a5a; color: #fff; }
+.slack-button-danger { background: #e01e5a; border-color: #e01e5a; color: #fff; }
+.slack-input { display: flex; flex-direction: column; gap: 4px; margin: 8px 0; }
+.slack-input label { font-weight: bold; }
+.slack-optional, .slack-hint { color: #616061; font-weight: normal; font-size: 13px; }
+.slack-divider { border: none; border-top: 1px solid #ddd; }
+.slack-mention { background: #e8f5fa; color: #1264a3; }
+"#;
+
+impl SlackBlock {
+    pub fn to_preview(&self, format: &SlackBlockKitPreviewFormat) -> String {
+        SlackBlockKitPreviewRenderer::new(format).render_block(self)
+    }
+}
+
+impl SlackMessageContent {
+    ///
+    /// A preview of the message blocks, or of its `text` if it has no blocks
+    ///
+    pub fn to_preview(&self, format: &SlackBlockKitPreviewFormat) -> String {
+        let renderer = SlackBlockKitPreviewRenderer::new(format);
+        let content = match (&self.blocks, &self.text) {
+            (Some(blocks), _) if !blocks.is_empty() => renderer.render_blocks(blocks),
+            (_, Some(text)) => renderer.render_text(&SlackBlockText::MarkDown(
+                SlackBlockMarkDownText::new(text.clone()),
+            )),
+            _ => "".into(),
+        };
+        renderer.render_container("slack-message", None, content, None)
+    }
+}
+
+impl SlackView {
+    pub fn to_preview(&self, format: &SlackBlockKitPreviewFormat) -> String {
+        let renderer = SlackBlockKitPreviewRenderer::new(format);
+        match self {
+            SlackView::Modal(modal_view) => {
+                let footer = modal_view
+                    .close
+                    .iter()
+                    .chain(modal_view.submit.iter())
+                    .map(|button| {
+                        renderer.render_element(&SlackBlockKitPreviewElement::Button {
+                            text: button.text(),
+                            style: None,
+                        })
+                    })
+                    .collect::<Vec<String>>();
+                renderer.render_container(
+                    "slack-modal",
+                    Some(modal_view.title.text()),
+                    renderer.render_blocks(&modal_view.blocks),
+                    Some(footer),
+                )
+            }
+            SlackView::Home(home_view) => renderer.render_container(
+                "slack-home",
+                None,
+                renderer.render_blocks(&home_view.blocks),
+                None,
+            ),
+            SlackView::Other(_) => renderer.render_unsupported("view"),
+        }
+    }
+}
+
+///
+/// Elements of blocks (buttons, selects, inputs, etc.) as they look like in previews
+///
+enum SlackBlockKitPreviewElement<'a> {
+    Button {
+        text: &'a str,
+        style: Option<&'a str>,
+    },
+    Overflow,
+    Select {
+        placeholder: Option<&'a str>,
+        selected: Vec<String>,
+        multi: bool,
+    },
+    Input {
+        input_type: &'static str,
+        placeholder: Option<&'a str>,
+        value: Option<String>,
+        multiline: bool,
+    },
+    Choices {
+        options: Vec<(&'a SlackBlockText, bool)>,
+        multi: bool,
+    },
+    Image {
+        image_url: &'a str,
+        alt_text: &'a str,
+    },
+    Unsupported,
+}
+
+struct SlackBlockKitPreviewRenderer<'a> {
+    format: &'a SlackBlockKitPreviewFormat,
+    mrkdwn_renderer: SlackMrkdwnRenderer<'a>,
+}
+
+impl<'a> SlackBlockKitPreviewRenderer<'a> {
+    const DIVIDER_WIDTH: usize = 40;
+
+    fn new(format: &'a SlackBlockKitPreviewFormat) -> Self {
+        Self {
+            format,
+            mrkdwn_renderer: SlackMrkdwnRenderer::new(),
+        }
+    }
+
+    fn is_html(&self) -> bool {
+        *self.format == SlackBlockKitPreviewFormat::Html
+    }
+
+    fn ansi(&self, (ansi_on, ansi_off): (&str, &str), text: &str) -> String {
+        match self.format {
+            SlackBlockKitPreviewFormat::AnsiText => format!("{}{}{}", ansi_on, text, ansi_off),
+            _ => text.into(),
+        }
+    }
+
+    fn render_container(
+        &self,
+        class: &str,
+        title: Option<&str>,
+        content: String,
+        footer: Option<Vec<String>>,
+    ) -> String {
+        let mut parts = Vec::new();
+        if self.is_html() {
+            parts.push(format!("<div class=\"{}\">", class));
+        }
+        if let Some(title) = title {
+            parts.push(self.render_title(title, "slack-modal-title"));
+        }
+        if !content.is_empty() {
+            parts.push(content);
+        }
+        if let Some(footer) = footer.filter(|footer| !footer.is_empty()) {
+            if self.is_html() {
+                parts.push(format!(
+                    "<div class=\"slack-modal-footer\">{}</div>",
+                    footer.join("")
+                ));
+            } else {
+                parts.push(footer.join(" "));
+            }
+        }
+        if self.is_html() {
+            parts.push("</div>".into());
+            parts.join("\n")
+        } else {
+            parts.join("\n\n")
+        }
+    }
+
+    fn render_title(&self, title: &str, html_class: &str) -> String {
+        match self.format {
+            SlackBlockKitPreviewFormat::Html => {
+                format!("<div class=\"{}\">{}</div>", html_class, escape_html(title))
+            }
+            SlackBlockKitPreviewFormat::Text => {
+                format!("{}\n{}", title, "=".repeat(title.chars().count()))
+            }
+            SlackBlockKitPreviewFormat::AnsiText => self.ansi(("\x1b[1m", "\x1b[22m"), title),
+        }
+    }
+
+    fn render_unsupported(&self, kind: &str) -> String {
+        if self.is_html() {
+            format!(
+                "<div class=\"slack-unsupported\">Unsupported {}</div>",
+                kind
+            )
+        } else {
+            format!("[unsupported {}]", kind)
+        }
+    }
+
+    fn render_blocks(&self, blocks: &[SlackBlock]) -> String {
+        blocks
+            .iter()
+            .map(|block| self.render_block(block))
+            .collect::<Vec<String>>()
+            .join(if self.is_html() { "\n" } else { "\n\n" })
+    }
+
+    fn render_block(&self, block: &SlackBlock) -> String {
+        match block {
+            SlackBlock::Section(section) => {
+                let text = section.text.as_ref().map(|text| self.render_text(text));
+                let fields = section
+                    .fields
+                    .iter()
+                    .flatten()
+                    .map(|field| self.render_text(field))
+                    .collect::<Vec<String>>();
+                let accessory = section
+                    .accessory
+                    .as_ref()
+                    .map(|accessory| self.render_element(&Self::section_element(accessory)));
+
+                if self.is_html() {
+                    let fields = if fields.is_empty() {
+                        None
+                    } else {
+                        Some(format!(
+                            "<div class=\"slack-section-fields\">{}</div>",
+                            fields
+                                .iter()
+                                .map(|field| format!(
+                                    "<div class=\"slack-section-field\">{}</div>",
+                                    field
+                                ))
+                                .collect::<String>()
+                        ))
+                    };
+                    let content = text
+                        .map(|text| format!("<div class=\"slack-section-text\">{}</div>", text))
+                        .into_iter()
+                        .chain(fields)
+                        .collect::<String>();
+                    format!(
+                        "<div class=\"slack-section\"><div>{}</div>{}</div>",
+                        content,
+                        accessory
+                            .map(|accessory| format!(
+                                "<div class=\"slack-accessory\">{}</div>",
+                                accessory
+                            ))
+                            .unwrap_or_default()
+                    )
+                } else {
+                    text.into_iter()
+                        .chain(fields)
+                        .chain(accessory)
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                }
+            }
+            SlackBlock::Divider(_) => {
+                if self.is_html() {
+                    "<hr class=\"slack-divider\">".into()
+                } else {
+                    self.ansi(("\x1b[2m", "\x1b[22m"), &"─".repeat(Self::DIVIDER_WIDTH))
+                }
+            }
+            SlackBlock::Image(image) => {
+                let title = image.title.as_ref().map(|title| title.text.as_str());
+                let element = self.render_element(&SlackBlockKitPreviewElement::Image {
+                    image_url: &image.image_url,
+                    alt_text: &image.alt_text,
+                });
+                if self.is_html() {
+                    format!(
+                        "<figure class=\"slack-image\">{}{}</figure>",
+                        title
+                            .map(|title| format!("<figcaption>{}</figcaption>", escape_html(title)))
+                            .unwrap_or_default(),
+                        element
+                    )
+                } else {
+                    title
+                        .map(|title| self.ansi(("\x1b[1m", "\x1b[22m"), title))
+                        .into_iter()
+                        .chain(std::iter::once(element))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                }
+            }
+            SlackBlock::Actions(actions) => {
+                let elements = actions
+                    .elements
+                    .iter()
+                    .map(|element| self.render_element(&Self::action_element(element)))
+                    .collect::<Vec<String>>();
+                if self.is_html() {
+                    format!("<div class=\"slack-actions\">{}</div>", elements.join(""))
+                } else {
+                    elements.join(" ")
+                }
+            }
+            SlackBlock::Context(context) => {
+                let elements = context
+                    .elements
+                    .iter()
+                    .map(|element| match element {
+                        SlackContextBlockElement::Image(image) if self.is_html() => format!(
+                            "<img class=\"slack-context-image\" src=\"{}\" alt=\"{}\">",
+                            escape_html(&image.image_url),
+                            escape_html(&image.alt_text)
+                        ),
+                        SlackContextBlockElement::Image(image) => {
+                            self.render_element(&SlackBlockKitPreviewElement::Image {
+                                image_url: &image.image_url,
+                                alt_text: &image.alt_text,
+                            })
+                        }
+                        SlackContextBlockElement::Plain(text) => {
+                            self.render_text(&SlackBlockText::Plain(text.clone()))
+                        }
+                        SlackContextBlockElement::MarkDown(text) => {
+                            self.render_text(&SlackBlockText::MarkDown(text.clone()))
+                        }
+                        SlackContextBlockElement::Other(_) => {
+                            self.render_element(&SlackBlockKitPreviewElement::Unsupported)
+                        }
+                    })
+                    .collect::<Vec<String>>();
+                if self.is_html() {
+                    format!(
+                        "<div class=\"slack-context\">{}</div>",
+                        elements
+                            .iter()
+                            .map(|element| format!("<span>{}</span>", element))
+                            .collect::<String>()
+                    )
+                } else {
+                    self.ansi(("\x1b[2m", "\x1b[22m"), &elements.join("  "))
+                }
+            }
+            SlackBlock::Input(input) => {
+                let element = self.render_element(&Self::input_element(&input.element));
+                let optional = input.optional == Some(true);
+                if self.is_html() {
+                    format!(
+                        "<div class=\"slack-input\"><label>{}{}</label>{}{}</div>",
+                        escape_html(&input.label.text),
+                        if optional {
+                            " <span class=\"slack-optional\">(optional)</span>"
+                        } else {
+                            ""
+                        },
+                        element,
+                        input
+                            .hint
+                            .as_ref()
+                            .map(|hint| format!(
+                                "<div class=\"slack-hint\">{}</div>",
+                                escape_html(&hint.text)
+                            ))
+                            .unwrap_or_default()
+                    )
+                } else {
+                    let label = format!(
+                        "{}{}",
+                        self.ansi(("\x1b[1m", "\x1b[22m"), &input.label.text),
+                        if optional { " (optional)" } else { "" }
+                    );
+                    std::iter::once(label)
+                        .chain(std::iter::once(element))
+                        .chain(
+                            input
+                                .hint
+                                .as_ref()
+                                .map(|hint| self.ansi(("\x1b[2m", "\x1b[22m"), &hint.text)),
+                        )
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                }
+            }
+            SlackBlock::Header(header) => self.render_title(header.text.text(), "slack-header"),
+            SlackBlock::RichText(rich_text) => {
+                let document = SlackMrkdwnDocument::parse(&rich_text.to_mrkdwn());
+                self.render_mrkdwn(&document, "slack-rich-text")
+            }
+            SlackBlock::File(file) => self.render_placeholder("file", &file.external_id),
+            SlackBlock::Video(video) => self.render_placeholder("video", video.title.text()),
+            SlackBlock::Call(call) => self.render_placeholder("call", &call.call_id),
+            SlackBlock::Other(_) => self.render_unsupported("block"),
+        }
+    }
+
+    fn render_placeholder(&self, kind: &str, name: &str) -> String {
+        if self.is_html() {
+            format!("<div class=\"slack-{}\">{}</div>", kind, escape_html(name))
+        } else {
+            format!("[{}: {}]", kind, name)
+        }
+    }
+
+    fn render_text(&self, text: &SlackBlockText) -> String {
+        match text {
+            SlackBlockText::Plain(plain_text) if self.is_html() => escape_html(&plain_text.text),
+            SlackBlockText::Plain(plain_text) => plain_text.text.clone(),
+            SlackBlockText::MarkDown(mrkdwn) => {
+                self.render_mrkdwn(&SlackMrkdwnDocument::parse(&mrkdwn.text), "slack-mrkdwn")
+            }
+        }
+    }
+
+    fn render_mrkdwn(&self, document: &SlackMrkdwnDocument, html_class: &str) -> String {
+        match self.format {
+            SlackBlockKitPreviewFormat::Html => format!(
+                "<div class=\"{}\">{}</div>",
+                html_class,
+                self.mrkdwn_renderer.to_html(document)
+            ),
+            SlackBlockKitPreviewFormat::Text => self.mrkdwn_renderer.to_plain_text(document),
+            SlackBlockKitPreviewFormat::AnsiText => self.mrkdwn_renderer.to_ansi_text(document),
+        }
+    }
+
+    fn render_element(&self, element: &SlackBlockKitPreviewElement) -> String {
+        match element {
+            SlackBlockKitPreviewElement::Button { text, style } => {
+                if self.is_html() {
+                    let class = style
+                        .map(|style| format!("slack-button slack-button-{}", style))
+                        .unwrap_or_else(|| "slack-button".into());
+                    format!("<button class=\"{}\">{}</button>", class, escape_html(text))
+                } else {
+                    let button = format!("[ {} ]", text);
+                    match *style {
+                        Some("primary") => self.ansi(("\x1b[32m", "\x1b[39m"), &button),
+                        Some("danger") => self.ansi(("\x1b[31m", "\x1b[39m"), &button),
+                        _ => button,
+                    }
+                }
+            }
+            SlackBlockKitPreviewElement::Overflow => {
+                if self.is_html() {
+                    "<button class=\"slack-overflow\">⋯</button>".into()
+                } else {
+                    "[ ⋯ ]".into()
+                }
+            }
+            SlackBlockKitPreviewElement::Select {
+                placeholder,
+                selected,
+                multi,
+            } => {
+                if self.is_html() {
+                    let options = if selected.is_empty() {
+                        format!(
+                            "<option>{}</option>",
+                            escape_html(placeholder.unwrap_or(""))
+                        )
+                    } else {
+                        selected
+                            .iter()
+                            .map(|option| {
+                                format!("<option selected>{}</option>", escape_html(option))
+                            })
+                            .collect()
+                    };
+                    format!(
+                        "<select class=\"slack-select\"{} disabled>{}</select>",
+                        if *multi { " multiple" } else { "" },
+                        options
+                    )
+                } else if selected.is_empty() {
+                    format!(
+                        "[ {} ▾ ]",
+                        self.ansi(("\x1b[2m", "\x1b[22m"), placeholder.unwrap_or(""))
+                    )
+                } else {
+                    format!("[ {} ▾ ]", selected.join(", "))
+                }
+            }
+            SlackBlockKitPreviewElement::Input {
+                input_type,
+                placeholder,
+                value,
+                multiline,
+            } => {
+                if self.is_html() {
+                    let placeholder = placeholder
+                        .map(|placeholder| format!(" placeholder=\"{}\"", escape_html(placeholder)))
+                        .unwrap_or_default();
+                    if *multiline {
+                        format!(
+                            "<textarea class=\"slack-input-text\"{} disabled>{}</textarea>",
+                            placeholder,
+                            escape_html(value.as_deref().unwrap_or(""))
+                        )
+                    } else {
+                        format!(
+                            "<input class=\"slack-input-{}\" type=\"{}\"{}{} disabled>",
+                            input_type,
+                            input_type,
+                            placeholder,
+                            value
+                                .as_ref()
+                                .map(|value| format!(" value=\"{}\"", escape_html(value)))
+                                .unwrap_or_default()
+                        )
+                    }
+                } else {
+                    match (value, placeholder) {
+                        (Some(value), _) => format!("[ {} ]", value),
+                        (None, Some(placeholder)) => {
+                            format!("[ {} ]", self.ansi(("\x1b[2m", "\x1b[22m"), placeholder))
+                        }
+                        (None, None) => "[  ]".into(),
+                    }
+                }
+            }
+            SlackBlockKitPreviewElement::Choices { options, multi } => {
+                let input_type = if *multi { "checkbox" } else { "radio" };
+                let options = options.iter().map(|(text, checked)| {
+                    if self.is_html() {
+                        format!(
+                            "<label><input type=\"{}\" disabled{}> {}</label>",
+                            input_type,
+                            if *checked { " checked" } else { "" },
+                            self.render_text(text)
+                        )
+                    } else {
+                        let marker = match (*multi, *checked) {
+                            (true, true) => "[x]",
+                            (true, false) => "[ ]",
+                            (false, true) => "(•)",
+                            (false, false) => "( )",
+                        };
+                        format!("{} {}", marker, self.render_text(text))
+                    }
+                });
+                if self.is_html() {
+                    format!(
+                        "<div class=\"slack-choices\">{}</div>",
+                        options.collect::<String>()
+                    )
+                } else {
+                    options.collect::<Vec<String>>().join("\n")
+                }
+            }
+            SlackBlockKitPreviewElement::Image {
+                image_url,
+                alt_text,
+            } => {
+                if self.is_html() {
+                    format!(
+                        "<img src=\"{}\" alt=\"{}\">",
+                        escape_html(image_url),
+                        escape_html(alt_text)
+                    )
+                } else {
+                    format!("[image: {}]", alt_text)
+                }
+            }
+            SlackBlockKitPreviewElement::Unsupported => {
+                if self.is_html() {
+                    "<span class=\"slack-unsupported\">Unsupported element</span>".into()
+                } else {
+                    "[unsupported element]".into()
+                }
+            }
+        }
+    }
+
+    fn section_element(element: &SlackSectionBlockElement) -> SlackBlockKitPreviewElement<'_> {
+        match element {
+            SlackSectionBlockElement::Image(image) => SlackBlockKitPreviewElement::Image {
+                image_url: &image.image_url,
+                alt_text: &image.alt_text,
+            },
+            SlackSectionBlockElement::Button(button) => Self::button(button),
+            SlackSectionBlockElement::StaticSelect(select) => Self::static_select(select),
+            SlackSectionBlockElement::MultiStaticSelect(select) => {
+                Self::multi_static_select(select)
+            }
+            SlackSectionBlockElement::ExternalSelect(select) => Self::external_select(select),
+            SlackSectionBlockElement::MultiExternalSelect(select) => {
+                Self::multi_external_select(select)
+            }
+            SlackSectionBlockElement::UsersSelect(select) => Self::users_select(select),
+            SlackSectionBlockElement::MultiUsersSelect(select) => Self::multi_users_select(select),
+            SlackSectionBlockElement::ConversationsSelect(select) => {
+                Self::conversations_select(select)
+            }
+            SlackSectionBlockElement::MultiConversationsSelect(select) => {
+                Self::multi_conversations_select(select)
+            }
+            SlackSectionBlockElement::ChannelsSelect(select) => Self::channels_select(select),
+            SlackSectionBlockElement::MultiChannelsSelect(select) => {
+                Self::multi_channels_select(select)
+            }
+            SlackSectionBlockElement::Overflow(_) => SlackBlockKitPreviewElement::Overflow,
+            SlackSectionBlockElement::DatePicker(date_picker) => Self::date_picker(date_picker),
+            SlackSectionBlockElement::PlainTextInput(input) => Self::plain_text_input(input),
+            SlackSectionBlockElement::RadioButtons(radio_buttons) => {
+                Self::radio_buttons(radio_buttons)
+            }
+            SlackSectionBlockElement::Checkboxes(checkboxes) => Self::checkboxes(checkboxes),
+            SlackSectionBlockElement::TimePicker(time_picker) => Self::time_picker(time_picker),
+            SlackSectionBlockElement::Other(_) => SlackBlockKitPreviewElement::Unsupported,
+        }
+    }
+
+    fn action_element(element: &SlackActionBlockElement) -> SlackBlockKitPreviewElement<'_> {
+        match element {
+            SlackActionBlockElement::Button(button) => Self::button(button),
+            SlackActionBlockElement::Overflow(_) => SlackBlockKitPreviewElement::Overflow,
+            SlackActionBlockElement::DatePicker(date_picker) => Self::date_picker(date_picker),
+            SlackActionBlockElement::PlainTextInput(input) => Self::plain_text_input(input),
+            SlackActionBlockElement::RadioButtons(radio_buttons) => {
+                Self::radio_buttons(radio_buttons)
+            }
+            SlackActionBlockElement::Checkboxes(checkboxes) => Self::checkboxes(checkboxes),
+            SlackActionBlockElement::StaticSelect(select) => Self::static_select(select),
+            SlackActionBlockElement::MultiStaticSelect(select) => Self::multi_static_select(select),
+            SlackActionBlockElement::ExternalSelect(select) => Self::external_select(select),
+            SlackActionBlockElement::MultiExternalSelect(select) => {
+                Self::multi_external_select(select)
+            }
+            SlackActionBlockElement::UsersSelect(select) => Self::users_select(select),
+            SlackActionBlockElement::MultiUsersSelect(select) => Self::multi_users_select(select),
+            SlackActionBlockElement::ConversationsSelect(select) => {
+                Self::conversations_select(select)
+            }
+            SlackActionBlockElement::MultiConversationsSelect(select) => {
+                Self::multi_conversations_select(select)
+            }
+            SlackActionBlockElement::ChannelsSelect(select) => Self::channels_select(select),
+            SlackActionBlockElement::MultiChannelsSelect(select) => {
+                Self::multi_channels_select(select)
+            }
+            SlackActionBlockElement::TimePicker(time_picker) => Self::time_picker(time_picker),
+            SlackActionBlockElement::DateTimePicker(date_time_picker) => {
+                Self::date_time_picker(date_time_picker)
+            }
+            SlackActionBlockElement::Other(_) => SlackBlockKitPreviewElement::Unsupported,
+        }
+    }
+
+    fn input_element(element: &SlackInputBlockElement) -> SlackBlockKitPreviewElement<'_> {
+        match element {
+            SlackInputBlockElement::StaticSelect(select) => Self::static_select(select),
+            SlackInputBlockElement::MultiStaticSelect(select) => Self::multi_static_select(select),
+            SlackInputBlockElement::ExternalSelect(select) => Self::external_select(select),
+            SlackInputBlockElement::MultiExternalSelect(select) => {
+                Self::multi_external_select(select)
+            }
+            SlackInputBlockElement::UsersSelect(select) => Self::users_select(select),
+            SlackInputBlockElement::MultiUsersSelect(select) => Self::multi_users_select(select),
+            SlackInputBlockElement::ConversationsSelect(select) => {
+                Self::conversations_select(select)
+            }
+            SlackInputBlockElement::MultiConversationsSelect(select) => {
+                Self::multi_conversations_select(select)
+            }
+            SlackInputBlockElement::ChannelsSelect(select) => Self::channels_select(select),
+            SlackInputBlockElement::MultiChannelsSelect(select) => {
+                Self::multi_channels_select(select)
+            }
+            SlackInputBlockElement::DatePicker(date_picker) => Self::date_picker(date_picker),
+            SlackInputBlockElement::PlainTextInput(input) => Self::plain_text_input(input),
+            SlackInputBlockElement::RadioButtons(radio_buttons) => {
+                Self::radio_buttons(radio_buttons)
+            }
+            SlackInputBlockElement::Checkboxes(checkboxes) => Self::checkboxes(checkboxes),
+            SlackInputBlockElement::TimePicker(time_picker) => Self::time_picker(time_picker),
+            SlackInputBlockElement::DateTimePicker(date_time_picker) => {
+                Self::date_time_picker(date_time_picker)
+            }
+            SlackInputBlockElement::EmailInput(input) => SlackBlockKitPreviewElement::Input {
+                input_type: "email",
+                placeholder: input
+                    .placeholder
+                    .as_ref()
+                    .map(|placeholder| placeholder.text()),
+                value: input
+                    .initial_value
+                    .as_ref()
+                    .map(|value| value.value().clone()),
+                multiline: false,
+            },
+            SlackInputBlockElement::UrlInput(input) => SlackBlockKitPreviewElement::Input {
+                input_type: "url",
+                placeholder: input
+                    .placeholder
+                    .as_ref()
+                    .map(|placeholder| placeholder.text()),
+                value: input.initial_value.clone(),
+                multiline: false,
+            },
+            SlackInputBlockElement::NumberInput(input) => SlackBlockKitPreviewElement::Input {
+                input_type: "number",
+                placeholder: input
+                    .placeholder
+                    .as_ref()
+                    .map(|placeholder| placeholder.text()),
+                value: input.initial_value.clone(),
+                multiline: false,
+            },
+            SlackInputBlockElement::Other(_) => SlackBlockKitPreviewElement::Unsupported,
+        }
+    }
+
+    fn button(button: &SlackBlockButtonElement) -> SlackBlockKitPreviewElement<'_> {
+        SlackBlockKitPreviewElement::Button {
+            text: button.text.text(),
+            style: button.style.as_deref(),
+        }
+    }
+
+    fn select<'e, I>(
+        placeholder: &'e SlackBlockPlainTextOnly,
+        selected: I,
+        multi: bool,
+    ) -> SlackBlockKitPreviewElement<'e>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        SlackBlockKitPreviewElement::Select {
+            placeholder: Some(placeholder.text()),
+            selected: selected.into_iter().collect(),
+            multi,
+        }
+    }
+
+    fn static_select(select: &SlackBlockStaticSelectElement) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_option
+                .iter()
+                .map(|option| option.text.text().to_string()),
+            false,
+        )
+    }
+
+    fn multi_static_select(
+        select: &SlackBlockMultiStaticSelectElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_options
+                .iter()
+                .flatten()
+                .map(|option| option.text.text().to_string()),
+            true,
+        )
+    }
+
+    fn external_select(
+        select: &SlackBlockExternalSelectElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_option
+                .iter()
+                .map(|option| option.text.text().to_string()),
+            false,
+        )
+    }
+
+    fn multi_external_select(
+        select: &SlackBlockMultiExternalSelectElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_options
+                .iter()
+                .flatten()
+                .map(|option| option.text.text().to_string()),
+            true,
+        )
+    }
+
+    fn users_select(select: &SlackBlockUsersSelectElement) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select.initial_user.iter().map(|user| format!("@{}", user)),
+            false,
+        )
+    }
+
+    fn multi_users_select(
+        select: &SlackBlockMultiUsersSelectElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_users
+                .iter()
+                .flatten()
+                .map(|user| format!("@{}", user)),
+            true,
+        )
+    }
+
+    fn conversations_select(
+        select: &SlackBlockConversationsSelectElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_conversation
+                .iter()
+                .map(|conversation| format!("#{}", conversation.value())),
+            false,
+        )
+    }
+
+    fn multi_conversations_select(
+        select: &SlackBlockMultiConversationsSelectElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_conversations
+                .iter()
+                .flatten()
+                .map(|conversation| format!("#{}", conversation.value())),
+            true,
+        )
+    }
+
+    fn channels_select(
+        select: &SlackBlockChannelsSelectElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_channel
+                .iter()
+                .map(|channel| format!("#{}", channel.value())),
+            false,
+        )
+    }
+
+    fn multi_channels_select(
+        select: &SlackBlockMultiChannelsSelectElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        Self::select(
+            &select.placeholder,
+            select
+                .initial_channels
+                .iter()
+                .flatten()
+                .map(|channel| format!("#{}", channel.value())),
+            true,
+        )
+    }
+
+    fn date_picker(date_picker: &SlackBlockDatePickerElement) -> SlackBlockKitPreviewElement<'_> {
+        SlackBlockKitPreviewElement::Input {
+            input_type: "date",
+            placeholder: Some(date_picker.placeholder.text()),
+            value: date_picker.initial_date.clone(),
+            multiline: false,
+        }
+    }
+
+    fn time_picker(time_picker: &SlackBlockTimePickerElement) -> SlackBlockKitPreviewElement<'_> {
+        SlackBlockKitPreviewElement::Input {
+            input_type: "time",
+            placeholder: time_picker
+                .placeholder
+                .as_ref()
+                .map(|placeholder| placeholder.text()),
+            value: time_picker.initial_time.clone(),
+            multiline: false,
+        }
+    }
+
+    fn date_time_picker(
+        date_time_picker: &SlackBlockDateTimePickerElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        SlackBlockKitPreviewElement::Input {
+            input_type: "datetime-local",
+            placeholder: None,
+            value: date_time_picker
+                .initial_date_time
+                .as_ref()
+                .map(|date_time| date_time.0.format("%Y-%m-%dT%H:%M").to_string()),
+            multiline: false,
+        }
+    }
+
+    fn plain_text_input(
+        input: &SlackBlockPlainTextInputElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        SlackBlockKitPreviewElement::Input {
+            input_type: "text",
+            placeholder: Some(input.placeholder.text()),
+            value: input.initial_value.clone(),
+            multiline: input.multiline == Some(true),
+        }
+    }
+
+    fn radio_buttons(
+        radio_buttons: &SlackBlockRadioButtonsElement,
+    ) -> SlackBlockKitPreviewElement<'_> {
+        SlackBlockKitPreviewElement::Choices {
+            options: radio_buttons
+                .options
+                .iter()
+                .map(|option| {
+                    let checked = radio_buttons
+                        .initial_option
+                        .iter()
+                        .any(|initial_option| initial_option.value == option.value);
+                    (&option.text, checked)
+                })
+                .collect(),
+            multi: false,
+        }
+    }
+
+    fn checkboxes(checkboxes: &SlackBlockCheckboxesElement) -> SlackBlockKitPreviewElement<'_> {
+        SlackBlockKitPreviewElement::Choices {
+            options: checkboxes
+                .options
+                .iter()
+                .map(|option| {
+                    let checked = checkboxes
+                        .initial_options
+                        .iter()
+                        .flatten()
+                        .any(|initial_option| initial_option.value == option.value);
+                    (&option.text, checked)
+                })
+                .collect(),
+            multi: true,
+        }
+    }
+}
+
+#[test]
+fn check_block_kit_preview() {
+    let content = SlackMessageContent::new().with_blocks(vec![
+        SlackHeaderBlock::new("Release 1.2".into()).into(),
+        SlackSectionBlock::new()
+            .with_text(SlackBlockText::MarkDown("Deploy *R&amp;D* build?".into()))
+            .with_fields(vec![
+                SlackBlockText::Plain("Env: prod".into()),
+                SlackBlockText::Plain("Owner: <@U1>".into()),
+            ])
+            .with_accessory(SlackSectionBlockElement::Button(
+                SlackBlockButtonElement::new("approve".into(), "Approve".into())
+                    .with_style("primary".into()),
+            ))
+            .into(),
+        SlackDividerBlock::new().into(),
+        SlackActionsBlock::new(vec![SlackActionBlockElement::StaticSelect(
+            SlackBlockStaticSelectElement::new("region".into(), "Pick a region".into()),
+        )])
+        .into(),
+        SlackContextBlock::new(vec![SlackContextBlockElement::MarkDown(
+            "by <@U1|alice>".into(),
+        )])
+        .into(),
+    ]);
+
+    assert_eq!(
+        content.to_preview(&SlackBlockKitPreviewFormat::Text),
+        "Release 1.2\n\
+         ===========\n\n\
+         Deploy R&D build?\n\
+         Env: prod\n\
+         Owner: <@U1>\n\
+         [ Approve ]\n\n\
+         ────────────────────────────────────────\n\n\
+         [ Pick a region ▾ ]\n\n\
+         by @alice"
+    );
+
+    let ansi = content.to_preview(&SlackBlockKitPreviewFormat::AnsiText);
+    assert!(ansi.contains("\x1b[1mRelease 1.2\x1b[22m"));
+    assert!(ansi.contains("Deploy \x1b[1mR&D\x1b[22m build?"));
+    assert!(ansi.contains("\x1b[32m[ Approve ]\x1b[39m"));
+
+    let html = content.to_preview(&SlackBlockKitPreviewFormat::Html);
+    assert!(html.starts_with("<div class=\"slack-message\">\n<div class=\"slack-header\">"));
+    assert!(html.contains("<strong>R&amp;D</strong>"));
+    assert!(html.contains("Owner: &lt;@U1&gt;"));
+    assert!(html.contains("<button class=\"slack-button slack-button-primary\">Approve</button>"));
+    assert!(html.contains("<hr class=\"slack-divider\">"));
+}
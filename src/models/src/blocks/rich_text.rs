@@ -0,0 +1,513 @@
+use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::blocks::*;
+use crate::common::*;
+
+///
+/// Rich text blocks are produced by Slack clients for messages authored by users.
+/// https://api.slack.com/reference/block-kit/blocks#rich_text
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextBlock {
+    pub block_id: Option<SlackBlockId>,
+    pub elements: Vec<SlackRichTextElement>,
+}
+
+impl From<SlackRichTextBlock> for SlackBlock {
+    fn from(block: SlackRichTextBlock) -> Self {
+        SlackBlock::RichText(block)
+    }
+}
+
+impl SlackRichTextBlock {
+    pub fn to_plain_text(&self) -> String {
+        render_rich_text_elements(&self.elements, &SlackRichTextRenderFormat::PlainText)
+    }
+
+    pub fn to_mrkdwn(&self) -> String {
+        render_rich_text_elements(&self.elements, &SlackRichTextRenderFormat::Mrkdwn)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", remote = "Self")]
+pub enum SlackRichTextElement {
+    #[serde(rename = "rich_text_section")]
+    Section(SlackRichTextSection),
+    #[serde(rename = "rich_text_list")]
+    List(SlackRichTextList),
+    #[serde(rename = "rich_text_preformatted")]
+    Preformatted(SlackRichTextPreformatted),
+    #[serde(rename = "rich_text_quote")]
+    Quote(SlackRichTextQuote),
+    ///
+    /// Elements that aren't modelled (yet) are preserved as JSON to be able to return them back.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl_slack_tagged_with_other!(SlackRichTextElement, "type");
+
+impl SlackRichTextElement {
+    pub fn to_plain_text(&self) -> String {
+        render_rich_text_element(self, &SlackRichTextRenderFormat::PlainText)
+    }
+
+    pub fn to_mrkdwn(&self) -> String {
+        render_rich_text_element(self, &SlackRichTextRenderFormat::Mrkdwn)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextSection {
+    pub elements: Vec<SlackRichTextInlineElement>,
+}
+
+impl From<SlackRichTextSection> for SlackRichTextElement {
+    fn from(section: SlackRichTextSection) -> Self {
+        SlackRichTextElement::Section(section)
+    }
+}
+
+///
+/// List items are `rich_text_section` elements
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextList {
+    pub style: SlackRichTextListStyle,
+    pub elements: Vec<SlackRichTextElement>,
+    pub indent: Option<u64>,
+    pub offset: Option<u64>,
+    pub border: Option<u64>,
+}
+
+impl From<SlackRichTextList> for SlackRichTextElement {
+    fn from(list: SlackRichTextList) -> Self {
+        SlackRichTextElement::List(list)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum SlackRichTextListStyle {
+    #[serde(rename = "bullet")]
+    Bullet,
+    #[serde(rename = "ordered")]
+    Ordered,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextPreformatted {
+    pub elements: Vec<SlackRichTextInlineElement>,
+    pub border: Option<u64>,
+}
+
+impl From<SlackRichTextPreformatted> for SlackRichTextElement {
+    fn from(preformatted: SlackRichTextPreformatted) -> Self {
+        SlackRichTextElement::Preformatted(preformatted)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextQuote {
+    pub elements: Vec<SlackRichTextInlineElement>,
+    pub border: Option<u64>,
+}
+
+impl From<SlackRichTextQuote> for SlackRichTextElement {
+    fn from(quote: SlackRichTextQuote) -> Self {
+        SlackRichTextElement::Quote(quote)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", remote = "Self")]
+pub enum SlackRichTextInlineElement {
+    #[serde(rename = "text")]
+    Text(SlackRichTextInlineText),
+    #[serde(rename = "link")]
+    Link(SlackRichTextInlineLink),
+    #[serde(rename = "user")]
+    User(SlackRichTextInlineUser),
+    #[serde(rename = "channel")]
+    Channel(SlackRichTextInlineChannel),
+    #[serde(rename = "usergroup")]
+    UserGroup(SlackRichTextInlineUserGroup),
+    #[serde(rename = "emoji")]
+    Emoji(SlackRichTextInlineEmoji),
+    #[serde(rename = "broadcast")]
+    Broadcast(SlackRichTextInlineBroadcast),
+    #[serde(rename = "date")]
+    Date(SlackRichTextInlineDate),
+    ///
+    /// Elements that aren't modelled (yet) are preserved as JSON to be able to return them back.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl_slack_tagged_with_other!(SlackRichTextInlineElement, "type");
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextStyle {
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub strike: Option<bool>,
+    pub code: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextInlineText {
+    pub text: String,
+    pub style: Option<SlackRichTextStyle>,
+}
+
+impl From<SlackRichTextInlineText> for SlackRichTextInlineElement {
+    fn from(element: SlackRichTextInlineText) -> Self {
+        SlackRichTextInlineElement::Text(element)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextInlineLink {
+    pub url: String,
+    pub text: Option<String>,
+    #[serde(rename = "unsafe")]
+    pub unsafe_link: Option<bool>,
+    pub style: Option<SlackRichTextStyle>,
+}
+
+impl From<SlackRichTextInlineLink> for SlackRichTextInlineElement {
+    fn from(element: SlackRichTextInlineLink) -> Self {
+        SlackRichTextInlineElement::Link(element)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextInlineUser {
+    pub user_id: SlackUserId,
+    pub style: Option<SlackRichTextStyle>,
+}
+
+impl From<SlackRichTextInlineUser> for SlackRichTextInlineElement {
+    fn from(element: SlackRichTextInlineUser) -> Self {
+        SlackRichTextInlineElement::User(element)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextInlineChannel {
+    pub channel_id: SlackChannelId,
+    pub style: Option<SlackRichTextStyle>,
+}
+
+impl From<SlackRichTextInlineChannel> for SlackRichTextInlineElement {
+    fn from(element: SlackRichTextInlineChannel) -> Self {
+        SlackRichTextInlineElement::Channel(element)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextInlineUserGroup {
+    pub usergroup_id: SlackUserGroupId,
+    pub style: Option<SlackRichTextStyle>,
+}
+
+impl From<SlackRichTextInlineUserGroup> for SlackRichTextInlineElement {
+    fn from(element: SlackRichTextInlineUserGroup) -> Self {
+        SlackRichTextInlineElement::UserGroup(element)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextInlineEmoji {
+    pub name: String,
+    /// Hex code points separated by `-`, e.g. `1f44d`
+    pub unicode: Option<String>,
+    pub skin_tone: Option<u8>,
+}
+
+impl From<SlackRichTextInlineEmoji> for SlackRichTextInlineElement {
+    fn from(element: SlackRichTextInlineEmoji) -> Self {
+        SlackRichTextInlineElement::Emoji(element)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextInlineBroadcast {
+    /// `here`, `channel` or `everyone`
+    pub range: String,
+}
+
+impl From<SlackRichTextInlineBroadcast> for SlackRichTextInlineElement {
+    fn from(element: SlackRichTextInlineBroadcast) -> Self {
+        SlackRichTextInlineElement::Broadcast(element)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackRichTextInlineDate {
+    pub timestamp: SlackDateTime,
+    pub format: String,
+    pub url: Option<String>,
+    pub fallback: Option<String>,
+}
+
+impl From<SlackRichTextInlineDate> for SlackRichTextInlineElement {
+    fn from(element: SlackRichTextInlineDate) -> Self {
+        SlackRichTextInlineElement::Date(element)
+    }
+}
+
+enum SlackRichTextRenderFormat {
+    PlainText,
+    Mrkdwn,
+}
+
+fn render_rich_text_elements(
+    elements: &[SlackRichTextElement],
+    format: &SlackRichTextRenderFormat,
+) -> String {
+    let mut rendered = String::new();
+    for element in elements {
+        if !rendered.is_empty() && !rendered.ends_with('\n') {
+            rendered.push('\n');
+        }
+        rendered.push_str(&render_rich_text_element(element, format));
+    }
+    rendered.trim_end_matches('\n').into()
+}
+
+fn render_rich_text_element(
+    element: &SlackRichTextElement,
+    format: &SlackRichTextRenderFormat,
+) -> String {
+    match element {
+        SlackRichTextElement::Section(section) => render_inline_elements(&section.elements, format),
+        SlackRichTextElement::List(list) => {
+            let indent = "    ".repeat(list.indent.unwrap_or(0) as usize);
+            list.elements
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    let marker = match list.style {
+                        SlackRichTextListStyle::Bullet => "•".to_string(),
+                        SlackRichTextListStyle::Ordered => {
+                            format!("{}.", list.offset.unwrap_or(0) as usize + index + 1)
+                        }
+                    };
+                    format!(
+                        "{}{} {}",
+                        indent,
+                        marker,
+                        render_rich_text_element(item, format)
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+        SlackRichTextElement::Preformatted(preformatted) => {
+            let text = render_inline_elements(
+                &preformatted.elements,
+                &SlackRichTextRenderFormat::PlainText,
+            );
+            match format {
+                SlackRichTextRenderFormat::PlainText => text,
+                SlackRichTextRenderFormat::Mrkdwn => {
+                    SlackMrkdwnBuilder::new().code_block(&text).build()
+                }
+            }
+        }
+        SlackRichTextElement::Quote(quote) => {
+            let text = render_inline_elements(&quote.elements, format);
+            match format {
+                SlackRichTextRenderFormat::PlainText => text,
+                SlackRichTextRenderFormat::Mrkdwn => text
+                    .split('\n')
+                    .map(|line| format!("> {}", line))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            }
+        }
+        SlackRichTextElement::Other(_) => "".into(),
+    }
+}
+
+fn render_inline_elements(
+    elements: &[SlackRichTextInlineElement],
+    format: &SlackRichTextRenderFormat,
+) -> String {
+    elements
+        .iter()
+        .map(|element| render_inline_element(element, format))
+        .collect()
+}
+
+fn render_inline_element(
+    element: &SlackRichTextInlineElement,
+    format: &SlackRichTextRenderFormat,
+) -> String {
+    match format {
+        SlackRichTextRenderFormat::PlainText => match element {
+            SlackRichTextInlineElement::Text(text) => text.text.clone(),
+            SlackRichTextInlineElement::Link(link) => {
+                link.text.clone().unwrap_or_else(|| link.url.clone())
+            }
+            SlackRichTextInlineElement::User(user) => format!("@{}", user.user_id.value()),
+            SlackRichTextInlineElement::Channel(channel) => {
+                format!("#{}", channel.channel_id.value())
+            }
+            SlackRichTextInlineElement::UserGroup(user_group) => {
+                format!("@{}", user_group.usergroup_id.value())
+            }
+            SlackRichTextInlineElement::Emoji(emoji) => emoji
+                .unicode
+                .as_ref()
+                .and_then(|unicode| unicode_emoji(unicode))
+                .unwrap_or_else(|| format!(":{}:", emoji.name)),
+            SlackRichTextInlineElement::Broadcast(broadcast) => format!("@{}", broadcast.range),
+            SlackRichTextInlineElement::Date(date) => date
+                .fallback
+                .clone()
+                .unwrap_or_else(|| date.timestamp.0.to_rfc2822()),
+            SlackRichTextInlineElement::Other(_) => "".into(),
+        },
+        SlackRichTextRenderFormat::Mrkdwn => match element {
+            SlackRichTextInlineElement::Text(text) => {
+                // Slack doesn't apply styles in code, so its markers are kept as they are
+                let escaped = if text.style.as_ref().and_then(|style| style.code) == Some(true) {
+                    SlackMrkdwnBuilder::escape(&text.text)
+                } else {
+                    SlackMrkdwnBuilder::escape_text(&text.text)
+                };
+                apply_mrkdwn_style(escaped, text.style.as_ref())
+            }
+            SlackRichTextInlineElement::Link(link) => apply_mrkdwn_style(
+                match &link.text {
+                    Some(text) => format!(
+                        "<{}|{}>",
+                        SlackMrkdwnBuilder::escape_url(&link.url),
+                        SlackMrkdwnBuilder::escape(text)
+                    ),
+                    None => format!("<{}>", SlackMrkdwnBuilder::escape_url(&link.url)),
+                },
+                link.style.as_ref(),
+            ),
+            SlackRichTextInlineElement::User(user) => {
+                apply_mrkdwn_style(user.user_id.to_slack_format(), user.style.as_ref())
+            }
+            SlackRichTextInlineElement::Channel(channel) => {
+                apply_mrkdwn_style(channel.channel_id.to_slack_format(), channel.style.as_ref())
+            }
+            SlackRichTextInlineElement::UserGroup(user_group) => apply_mrkdwn_style(
+                user_group.usergroup_id.to_slack_format(),
+                user_group.style.as_ref(),
+            ),
+            SlackRichTextInlineElement::Emoji(emoji) => format!(":{}:", emoji.name),
+            SlackRichTextInlineElement::Broadcast(broadcast) => format!("<!{}>", broadcast.range),
+            SlackRichTextInlineElement::Date(date) => {
+                fmt_slack_date(date.timestamp.0, &date.format, date.url.as_ref())
+            }
+            SlackRichTextInlineElement::Other(_) => "".into(),
+        },
+    }
+}
+
+fn apply_mrkdwn_style(text: String, style: Option<&SlackRichTextStyle>) -> String {
+    let core = text.trim();
+    match style {
+        Some(style) if !core.is_empty() => {
+            let mut styled = core.to_string();
+            if style.code == Some(true) {
+                styled = format!("`{}`", styled);
+            }
+            if style.strike == Some(true) {
+                styled = format!("~{}~", styled);
+            }
+            if style.italic == Some(true) {
+                styled = format!("_{}_", styled);
+            }
+            if style.bold == Some(true) {
+                styled = format!("*{}*", styled);
+            }
+            // Slack doesn't apply styles with whitespaces next to the markers
+            text.replacen(core, &styled, 1)
+        }
+        _ => text,
+    }
+}
+
+fn unicode_emoji(unicode: &str) -> Option<String> {
+    unicode
+        .split('-')
+        .map(|code_point| {
+            u32::from_str_radix(code_point, 16)
+                .ok()
+                .and_then(std::char::from_u32)
+        })
+        .collect()
+}
+
+#[test]
+fn check_rich_text_rendering() {
+    let block: SlackRichTextBlock = serde_json::from_value(serde_json::json!({
+        "type": "rich_text",
+        "block_id": "b1",
+        "elements": [
+            {
+                "type": "rich_text_section",
+                "elements": [
+                    { "type": "text", "text": "Hello *all* " },
+                    { "type": "user", "user_id": "U1" },
+                    { "type": "text", "text": " see ", "style": { "bold": true } },
+                    { "type": "link", "url": "https://example.com/?a=1&b=<2>|x", "text": "docs" },
+                    { "type": "text", "text": " *raw_code*", "style": { "code": true } },
+                    { "type": "emoji", "name": "thumbsup", "unicode": "1f44d" }
+                ]
+            },
+            {
+                "type": "rich_text_list",
+                "style": "ordered",
+                "elements": [
+                    { "type": "rich_text_section", "elements": [{ "type": "text", "text": "one" }] },
+                    { "type": "rich_text_section", "elements": [{ "type": "text", "text": "two" }] }
+                ]
+            },
+            {
+                "type": "rich_text_quote",
+                "elements": [{ "type": "text", "text": "a < b" }]
+            },
+            {
+                "type": "rich_text_preformatted",
+                "elements": [{ "type": "text", "text": "if a < b { ``` }" }]
+            }
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+        block.to_plain_text(),
+        "Hello *all* @U1 see docs *raw_code*👍\n1. one\n2. two\na < b\nif a < b { ``` }"
+    );
+    assert_eq!(
+        block.to_mrkdwn(),
+        "Hello \u{200d}*\u{200d}all\u{200d}*\u{200d} <@U1> *see* <https://example.com/?a=1&amp;b=&lt;2&gt;%7Cx|docs> `*raw_code*`:thumbsup:\n1. one\n2. two\n> a &lt; b\n```\nif a &lt; b { ˋˋˋ }\n```"
+    );
+}
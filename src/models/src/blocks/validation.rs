@@ -0,0 +1,524 @@
+//!
+//! Validation of Block Kit models against the limits documented by Slack,
+//! to catch `invalid_blocks` errors before calling Slack.
+//!
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use crate::blocks::*;
+use crate::common::*;
+use crate::messages::SlackMessageContent;
+use rvstruct::ValueStruct;
+
+pub const SLACK_MESSAGE_MAX_BLOCKS: usize = 50;
+pub const SLACK_VIEW_MAX_BLOCKS: usize = 100;
+const SLACK_ID_MAX_LENGTH: usize = 255;
+const SLACK_SECTION_TEXT_MAX_LENGTH: usize = 3000;
+const SLACK_SECTION_MAX_FIELDS: usize = 10;
+const SLACK_SECTION_FIELD_MAX_LENGTH: usize = 2000;
+const SLACK_ACTIONS_MAX_ELEMENTS: usize = 25;
+const SLACK_CONTEXT_MAX_ELEMENTS: usize = 10;
+const SLACK_HEADER_TEXT_MAX_LENGTH: usize = 150;
+const SLACK_LABEL_MAX_LENGTH: usize = 2000;
+const SLACK_BUTTON_TEXT_MAX_LENGTH: usize = 75;
+const SLACK_BUTTON_VALUE_MAX_LENGTH: usize = 2000;
+const SLACK_URL_MAX_LENGTH: usize = 3000;
+const SLACK_OVERFLOW_MAX_OPTIONS: usize = 5;
+const SLACK_CHOICE_MAX_OPTIONS: usize = 10;
+const SLACK_SELECT_MAX_OPTIONS: usize = 100;
+const SLACK_VIEW_TITLE_MAX_LENGTH: usize = 24;
+pub const SLACK_VIEW_PRIVATE_METADATA_MAX_LENGTH: usize = 3000;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackBlockKitViolationKind {
+    TooManyBlocks { max: usize, actual: usize },
+    TooManyFields { max: usize, actual: usize },
+    TooManyElements { max: usize, actual: usize },
+    TooManyOptions { max: usize, actual: usize },
+    TextTooLong { max: usize, actual: usize },
+    DuplicateBlockId(SlackBlockId),
+    DuplicateActionId(SlackActionId),
+}
+
+///
+/// A Block Kit limit violation with a JSON path to the violating value (e.g. `$.blocks[2].text.text`).
+/// Plain text only fields are enforced with `SlackBlockPlainTextOnly` at compile time, so they aren't validated.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlackBlockKitViolation {
+    pub path: String,
+    pub kind: SlackBlockKitViolationKind,
+}
+
+impl Display for SlackBlockKitViolation {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match &self.kind {
+            SlackBlockKitViolationKind::TooManyBlocks { max, actual } => {
+                write!(f, "{}: too many blocks ({}/{})", self.path, actual, max)
+            }
+            SlackBlockKitViolationKind::TooManyFields { max, actual } => {
+                write!(f, "{}: too many fields ({}/{})", self.path, actual, max)
+            }
+            SlackBlockKitViolationKind::TooManyElements { max, actual } => {
+                write!(f, "{}: too many elements ({}/{})", self.path, actual, max)
+            }
+            SlackBlockKitViolationKind::TooManyOptions { max, actual } => {
+                write!(f, "{}: too many options ({}/{})", self.path, actual, max)
+            }
+            SlackBlockKitViolationKind::TextTooLong { max, actual } => write!(
+                f,
+                "{}: text is too long ({}/{} characters)",
+                self.path, actual, max
+            ),
+            SlackBlockKitViolationKind::DuplicateBlockId(block_id) => {
+                write!(
+                    f,
+                    "{}: duplicate block_id '{}'",
+                    self.path,
+                    block_id.value()
+                )
+            }
+            SlackBlockKitViolationKind::DuplicateActionId(action_id) => write!(
+                f,
+                "{}: duplicate action_id '{}'",
+                self.path,
+                action_id.value()
+            ),
+        }
+    }
+}
+
+impl SlackBlock {
+    pub fn validate(&self) -> Vec<SlackBlockKitViolation> {
+        let mut validator = SlackBlockKitValidator::new();
+        validator.check_block(self, "$");
+        validator.violations
+    }
+}
+
+impl SlackMessageContent {
+    pub fn validate(&self) -> Vec<SlackBlockKitViolation> {
+        let mut validator = SlackBlockKitValidator::new();
+        if let Some(blocks) = &self.blocks {
+            validator.check_blocks(blocks, "$.blocks", SLACK_MESSAGE_MAX_BLOCKS);
+        }
+        validator.violations
+    }
+}
+
+impl SlackView {
+    pub fn validate(&self) -> Vec<SlackBlockKitViolation> {
+        let mut validator = SlackBlockKitValidator::new();
+        match self {
+            SlackView::Modal(modal_view) => {
+                validator.check_text(
+                    "$.title.text",
+                    modal_view.title.text(),
+                    SLACK_VIEW_TITLE_MAX_LENGTH,
+                );
+                if let Some(close) = &modal_view.close {
+                    validator.check_text("$.close.text", close.text(), SLACK_VIEW_TITLE_MAX_LENGTH);
+                }
+                if let Some(submit) = &modal_view.submit {
+                    validator.check_text(
+                        "$.submit.text",
+                        submit.text(),
+                        SLACK_VIEW_TITLE_MAX_LENGTH,
+                    );
+                }
+                if let Some(callback_id) = &modal_view.callback_id {
+                    validator.check_text("$.callback_id", callback_id.value(), SLACK_ID_MAX_LENGTH);
+                }
+                if let Some(private_metadata) = &modal_view.private_metadata {
+                    validator.check_text(
+                        "$.private_metadata",
+                        private_metadata,
+                        SLACK_VIEW_PRIVATE_METADATA_MAX_LENGTH,
+                    );
+                }
+                validator.check_blocks(&modal_view.blocks, "$.blocks", SLACK_VIEW_MAX_BLOCKS);
+            }
+            SlackView::Home(home_view) => {
+                if let Some(callback_id) = &home_view.callback_id {
+                    validator.check_text("$.callback_id", callback_id.value(), SLACK_ID_MAX_LENGTH);
+                }
+                if let Some(private_metadata) = &home_view.private_metadata {
+                    validator.check_text(
+                        "$.private_metadata",
+                        private_metadata,
+                        SLACK_VIEW_PRIVATE_METADATA_MAX_LENGTH,
+                    );
+                }
+                validator.check_blocks(&home_view.blocks, "$.blocks", SLACK_VIEW_MAX_BLOCKS);
+            }
+            SlackView::Other(_) => {}
+        }
+        validator.violations
+    }
+}
+
+struct SlackBlockKitValidator {
+    violations: Vec<SlackBlockKitViolation>,
+}
+
+impl SlackBlockKitValidator {
+    fn new() -> Self {
+        Self {
+            violations: Vec::new(),
+        }
+    }
+
+    fn violation(&mut self, path: String, kind: SlackBlockKitViolationKind) {
+        self.violations.push(SlackBlockKitViolation { path, kind });
+    }
+
+    fn check_text(&mut self, path: &str, text: &str, max: usize) {
+        let actual = text.chars().count();
+        if actual > max {
+            self.violation(
+                path.into(),
+                SlackBlockKitViolationKind::TextTooLong { max, actual },
+            );
+        }
+    }
+
+    fn check_block_text(&mut self, path: &str, text: &SlackBlockText, max: usize) {
+        let text_value = match text {
+            SlackBlockText::Plain(plain_text) => &plain_text.text,
+            SlackBlockText::MarkDown(markdown_text) => &markdown_text.text,
+        };
+        self.check_text(&format!("{}.text", path), text_value, max);
+    }
+
+    fn check_options_count(&mut self, path: &str, actual: usize, max: usize) {
+        if actual > max {
+            self.violation(
+                path.into(),
+                SlackBlockKitViolationKind::TooManyOptions { max, actual },
+            );
+        }
+    }
+
+    fn check_blocks(&mut self, blocks: &[SlackBlock], path: &str, max_blocks: usize) {
+        if blocks.len() > max_blocks {
+            self.violation(
+                path.into(),
+                SlackBlockKitViolationKind::TooManyBlocks {
+                    max: max_blocks,
+                    actual: blocks.len(),
+                },
+            );
+        }
+
+        let mut block_ids = HashSet::new();
+        for (index, block) in blocks.iter().enumerate() {
+            let block_path = format!("{}[{}]", path, index);
+            if let Some(block_id) = Self::block_id(block) {
+                if !block_ids.insert(block_id) {
+                    self.violation(
+                        format!("{}.block_id", block_path),
+                        SlackBlockKitViolationKind::DuplicateBlockId(block_id.clone()),
+                    );
+                }
+            }
+            self.check_block(block, &block_path);
+        }
+    }
+
+    fn block_id(block: &SlackBlock) -> Option<&SlackBlockId> {
+        match block {
+            SlackBlock::Section(block) => block.block_id.as_ref(),
+            SlackBlock::Divider(block) => block.block_id.as_ref(),
+            SlackBlock::Image(block) => block.block_id.as_ref(),
+            SlackBlock::Actions(block) => block.block_id.as_ref(),
+            SlackBlock::Context(block) => block.block_id.as_ref(),
+            SlackBlock::Input(block) => block.block_id.as_ref(),
+            SlackBlock::File(block) => block.block_id.as_ref(),
+            SlackBlock::RichText(block) => block.block_id.as_ref(),
+            SlackBlock::Header(block) => block.block_id.as_ref(),
+            SlackBlock::Video(block) => block.block_id.as_ref(),
+            SlackBlock::Call(block) => block.block_id.as_ref(),
+            SlackBlock::Other(_) => None,
+        }
+    }
+
+    fn check_block(&mut self, block: &SlackBlock, path: &str) {
+        if let Some(block_id) = Self::block_id(block) {
+            self.check_text(
+                &format!("{}.block_id", path),
+                block_id.value(),
+                SLACK_ID_MAX_LENGTH,
+            );
+        }
+
+        match block {
+            SlackBlock::Section(section) => {
+                if let Some(text) = &section.text {
+                    self.check_block_text(
+                        &format!("{}.text", path),
+                        text,
+                        SLACK_SECTION_TEXT_MAX_LENGTH,
+                    );
+                }
+                if let Some(fields) = &section.fields {
+                    if fields.len() > SLACK_SECTION_MAX_FIELDS {
+                        self.violation(
+                            format!("{}.fields", path),
+                            SlackBlockKitViolationKind::TooManyFields {
+                                max: SLACK_SECTION_MAX_FIELDS,
+                                actual: fields.len(),
+                            },
+                        );
+                    }
+                    for (index, field) in fields.iter().enumerate() {
+                        self.check_block_text(
+                            &format!("{}.fields[{}]", path, index),
+                            field,
+                            SLACK_SECTION_FIELD_MAX_LENGTH,
+                        );
+                    }
+                }
+                if let Some(SlackSectionBlockElement::Button(button)) = &section.accessory {
+                    self.check_button(button, &format!("{}.accessory", path));
+                }
+            }
+            SlackBlock::Actions(actions) => {
+                if actions.elements.len() > SLACK_ACTIONS_MAX_ELEMENTS {
+                    self.violation(
+                        format!("{}.elements", path),
+                        SlackBlockKitViolationKind::TooManyElements {
+                            max: SLACK_ACTIONS_MAX_ELEMENTS,
+                            actual: actions.elements.len(),
+                        },
+                    );
+                }
+                let mut action_ids = HashSet::new();
+                for (index, element) in actions.elements.iter().enumerate() {
+                    let element_path = format!("{}.elements[{}]", path, index);
+                    if let Some(action_id) = Self::action_element_id(element) {
+                        self.check_text(
+                            &format!("{}.action_id", element_path),
+                            action_id.value(),
+                            SLACK_ID_MAX_LENGTH,
+                        );
+                        if !action_ids.insert(action_id) {
+                            self.violation(
+                                format!("{}.action_id", element_path),
+                                SlackBlockKitViolationKind::DuplicateActionId(action_id.clone()),
+                            );
+                        }
+                    }
+                    self.check_action_element(element, &element_path);
+                }
+            }
+            SlackBlock::Context(context) if context.elements.len() > SLACK_CONTEXT_MAX_ELEMENTS => {
+                self.violation(
+                    format!("{}.elements", path),
+                    SlackBlockKitViolationKind::TooManyElements {
+                        max: SLACK_CONTEXT_MAX_ELEMENTS,
+                        actual: context.elements.len(),
+                    },
+                );
+            }
+            SlackBlock::Header(header) => self.check_text(
+                &format!("{}.text.text", path),
+                header.text.text(),
+                SLACK_HEADER_TEXT_MAX_LENGTH,
+            ),
+            SlackBlock::Image(image) => {
+                self.check_text(
+                    &format!("{}.image_url", path),
+                    &image.image_url,
+                    SLACK_URL_MAX_LENGTH,
+                );
+                self.check_text(
+                    &format!("{}.alt_text", path),
+                    &image.alt_text,
+                    SLACK_LABEL_MAX_LENGTH,
+                );
+            }
+            SlackBlock::Input(input) => {
+                self.check_text(
+                    &format!("{}.label.text", path),
+                    &input.label.text,
+                    SLACK_LABEL_MAX_LENGTH,
+                );
+                if let Some(hint) = &input.hint {
+                    self.check_text(
+                        &format!("{}.hint.text", path),
+                        &hint.text,
+                        SLACK_LABEL_MAX_LENGTH,
+                    );
+                }
+                self.check_input_element(&input.element, &format!("{}.element", path));
+            }
+            _ => {}
+        }
+    }
+
+    fn action_element_id(element: &SlackActionBlockElement) -> Option<&SlackActionId> {
+        match element {
+            SlackActionBlockElement::Button(element) => Some(&element.action_id),
+            SlackActionBlockElement::Overflow(element) => Some(&element.action_id),
+            SlackActionBlockElement::DatePicker(element) => Some(&element.action_id),
+            SlackActionBlockElement::PlainTextInput(element) => Some(&element.action_id),
+            SlackActionBlockElement::RadioButtons(element) => Some(&element.action_id),
+            SlackActionBlockElement::Checkboxes(element) => Some(&element.action_id),
+            SlackActionBlockElement::StaticSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::MultiStaticSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::ExternalSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::MultiExternalSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::UsersSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::MultiUsersSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::ConversationsSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::MultiConversationsSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::ChannelsSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::MultiChannelsSelect(element) => Some(&element.action_id),
+            SlackActionBlockElement::TimePicker(element) => Some(&element.action_id),
+            SlackActionBlockElement::DateTimePicker(element) => Some(&element.action_id),
+            SlackActionBlockElement::Other(_) => None,
+        }
+    }
+
+    fn check_action_element(&mut self, element: &SlackActionBlockElement, path: &str) {
+        match element {
+            SlackActionBlockElement::Button(button) => self.check_button(button, path),
+            SlackActionBlockElement::Overflow(overflow) => self.check_options_count(
+                &format!("{}.options", path),
+                overflow.options.len(),
+                SLACK_OVERFLOW_MAX_OPTIONS,
+            ),
+            SlackActionBlockElement::RadioButtons(radio_buttons) => self.check_options_count(
+                &format!("{}.options", path),
+                radio_buttons.options.len(),
+                SLACK_CHOICE_MAX_OPTIONS,
+            ),
+            SlackActionBlockElement::Checkboxes(checkboxes) => self.check_options_count(
+                &format!("{}.options", path),
+                checkboxes.options.len(),
+                SLACK_CHOICE_MAX_OPTIONS,
+            ),
+            SlackActionBlockElement::StaticSelect(select) => {
+                if let Some(options) = &select.options {
+                    self.check_options_count(
+                        &format!("{}.options", path),
+                        options.len(),
+                        SLACK_SELECT_MAX_OPTIONS,
+                    );
+                }
+            }
+            SlackActionBlockElement::MultiStaticSelect(select) => {
+                if let Some(options) = &select.options {
+                    self.check_options_count(
+                        &format!("{}.options", path),
+                        options.len(),
+                        SLACK_SELECT_MAX_OPTIONS,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_input_element(&mut self, element: &SlackInputBlockElement, path: &str) {
+        match element {
+            SlackInputBlockElement::RadioButtons(radio_buttons) => self.check_options_count(
+                &format!("{}.options", path),
+                radio_buttons.options.len(),
+                SLACK_CHOICE_MAX_OPTIONS,
+            ),
+            SlackInputBlockElement::Checkboxes(checkboxes) => self.check_options_count(
+                &format!("{}.options", path),
+                checkboxes.options.len(),
+                SLACK_CHOICE_MAX_OPTIONS,
+            ),
+            SlackInputBlockElement::StaticSelect(select) => {
+                if let Some(options) = &select.options {
+                    self.check_options_count(
+                        &format!("{}.options", path),
+                        options.len(),
+                        SLACK_SELECT_MAX_OPTIONS,
+                    );
+                }
+            }
+            SlackInputBlockElement::MultiStaticSelect(select) => {
+                if let Some(options) = &select.options {
+                    self.check_options_count(
+                        &format!("{}.options", path),
+                        options.len(),
+                        SLACK_SELECT_MAX_OPTIONS,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_button(&mut self, button: &SlackBlockButtonElement, path: &str) {
+        self.check_text(
+            &format!("{}.text.text", path),
+            button.text.text(),
+            SLACK_BUTTON_TEXT_MAX_LENGTH,
+        );
+        if let Some(value) = &button.value {
+            self.check_text(
+                &format!("{}.value", path),
+                value,
+                SLACK_BUTTON_VALUE_MAX_LENGTH,
+            );
+        }
+        if let Some(url) = &button.url {
+            self.check_text(&format!("{}.url", path), url, SLACK_URL_MAX_LENGTH);
+        }
+    }
+}
+
+#[test]
+fn check_block_kit_validation() {
+    let blocks: Vec<SlackBlock> = (0..51)
+        .map(|index| {
+            SlackSectionBlock::new()
+                .with_block_id(if index == 1 { "b0" } else { "b" }.into())
+                .with_text(SlackBlockText::Plain("x".repeat(index * 100).into()))
+                .into()
+        })
+        .collect();
+    let violations = SlackMessageContent::new().with_blocks(blocks).validate();
+
+    assert!(violations.contains(&SlackBlockKitViolation {
+        path: "$.blocks".into(),
+        kind: SlackBlockKitViolationKind::TooManyBlocks {
+            max: SLACK_MESSAGE_MAX_BLOCKS,
+            actual: 51
+        }
+    }));
+    assert!(violations.contains(&SlackBlockKitViolation {
+        path: "$.blocks[31].text.text".into(),
+        kind: SlackBlockKitViolationKind::TextTooLong {
+            max: SLACK_SECTION_TEXT_MAX_LENGTH,
+            actual: 3100
+        }
+    }));
+    assert!(violations.contains(&SlackBlockKitViolation {
+        path: "$.blocks[2].block_id".into(),
+        kind: SlackBlockKitViolationKind::DuplicateBlockId("b".into())
+    }));
+
+    let actions_block: SlackBlock = SlackActionsBlock::new(vec![
+        SlackBlockButtonElement::new("button".into(), "x".repeat(76).as_str().into()).into(),
+        SlackBlockButtonElement::new("button".into(), "Ok".into()).into(),
+    ])
+    .into();
+    assert_eq!(
+        actions_block
+            .validate()
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<String>>(),
+        vec![
+            "$.elements[0].text.text: text is too long (76/75 characters)",
+            "$.elements[1].action_id: duplicate action_id 'button'",
+        ]
+    );
+}
@@ -1,31 +1,57 @@
 use crate::blocks::kit::SlackBlock;
-use crate::blocks::SlackBlockPlainTextOnly;
+use crate::blocks::*;
 use crate::common::SlackCallbackId;
 use crate::*;
 use rsb_derive::Builder;
+use rvstruct::ValueStruct;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackView {
     #[serde(rename = "home")]
     Home(SlackHomeView),
     #[serde(rename = "modal")]
     Modal(SlackModalView),
+    ///
+    /// Views of types that aren't modelled (yet) are preserved as JSON.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl_slack_tagged_with_other!(SlackView, "type");
+
+///
+/// Extra fields of views are read-only attributes from Slack (like `id`, `state` or `team_id`),
+/// which Slack API methods don't accept back, so they are never serialized.
+///
+#[cfg(feature = "lenient")]
+fn serialize_no_extra_fields<S>(_: &SlackExtraFields, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeMap;
+    serializer.serialize_map(Some(0))?.end()
 }
 
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackHomeView {
     pub blocks: Vec<SlackBlock>,
-    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    #[serde(default, with = "serde_with::rust::string_empty_as_none")]
     pub private_metadata: Option<String>,
-    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    #[serde(default, with = "serde_with::rust::string_empty_as_none")]
     pub callback_id: Option<SlackCallbackId>,
-    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    #[serde(default, with = "serde_with::rust::string_empty_as_none")]
     pub external_id: Option<String>,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten, serialize_with = "serialize_no_extra_fields")]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 #[skip_serializing_none]
@@ -35,15 +61,19 @@ pub struct SlackModalView {
     pub blocks: Vec<SlackBlock>,
     pub close: Option<SlackBlockPlainTextOnly>,
     pub submit: Option<SlackBlockPlainTextOnly>,
-    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    #[serde(default, with = "serde_with::rust::string_empty_as_none")]
     pub private_metadata: Option<String>,
-    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    #[serde(default, with = "serde_with::rust::string_empty_as_none")]
     pub callback_id: Option<SlackCallbackId>,
     pub clear_on_close: Option<bool>,
     pub notify_on_close: Option<bool>,
     pub hash: Option<String>,
-    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    #[serde(default, with = "serde_with::rust::string_empty_as_none")]
     pub external_id: Option<String>,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten, serialize_with = "serialize_no_extra_fields")]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 #[skip_serializing_none]
@@ -71,5 +101,414 @@ pub struct SlackStatefulStateParams {
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackViewState {
-    pub values: HashMap<String, serde_json::Value>,
+    pub values: HashMap<SlackBlockId, HashMap<SlackActionId, SlackViewStateValue>>,
+}
+
+impl SlackViewState {
+    pub fn value(
+        &self,
+        block_id: &SlackBlockId,
+        action_id: &SlackActionId,
+    ) -> Option<&SlackViewStateValue> {
+        self.values
+            .get(block_id)
+            .and_then(|block_values| block_values.get(action_id))
+    }
+
+    ///
+    /// Finds a value by an action id in any block
+    ///
+    pub fn action_value(&self, action_id: &SlackActionId) -> Option<&SlackViewStateValue> {
+        self.values
+            .values()
+            .find_map(|block_values| block_values.get(action_id))
+    }
+
+    ///
+    /// Deserializes values into your own type with fields named by action ids.
+    /// Values are simplified with `SlackViewStateValue::to_simplified_json` (e.g. selected options become their values).
+    /// Action ids are unique only within blocks, so the same action id in different blocks is an error,
+    /// use `deserialize_block_values` for such views.
+    ///
+    pub fn deserialize_values<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        let mut values: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
+        for (action_id, value) in self
+            .values
+            .values()
+            .flat_map(|block_values| block_values.iter())
+        {
+            if values
+                .insert(action_id.value().clone(), value.to_simplified_json())
+                .is_some()
+            {
+                return Err(serde::de::Error::custom(format!(
+                    "duplicate action id `{}` in different blocks, use deserialize_block_values",
+                    action_id.value()
+                )));
+            }
+        }
+        serde_json::from_value(serde_json::Value::Object(values))
+    }
+
+    ///
+    /// Deserializes values into your own type with fields named by block ids
+    /// containing types with fields named by action ids.
+    ///
+    pub fn deserialize_block_values<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        let values: serde_json::Map<String, serde_json::Value> = self
+            .values
+            .iter()
+            .map(|(block_id, block_values)| {
+                (
+                    block_id.value().clone(),
+                    serde_json::Value::Object(
+                        block_values
+                            .iter()
+                            .map(|(action_id, value)| {
+                                (action_id.value().clone(), value.to_simplified_json())
+                            })
+                            .collect(),
+                    ),
+                )
+            })
+            .collect();
+        serde_json::from_value(serde_json::Value::Object(values))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", remote = "Self")]
+pub enum SlackViewStateValue {
+    #[serde(rename = "plain_text_input")]
+    PlainTextInput(SlackViewStateTextValue),
+    #[serde(rename = "email_text_input")]
+    EmailInput(SlackViewStateTextValue),
+    #[serde(rename = "url_text_input")]
+    UrlInput(SlackViewStateTextValue),
+    #[serde(rename = "number_input")]
+    NumberInput(SlackViewStateTextValue),
+    #[serde(rename = "static_select")]
+    StaticSelect(SlackViewStateSelectedOptionValue),
+    #[serde(rename = "external_select")]
+    ExternalSelect(SlackViewStateSelectedOptionValue),
+    #[serde(rename = "radio_buttons")]
+    RadioButtons(SlackViewStateSelectedOptionValue),
+    #[serde(rename = "multi_static_select")]
+    MultiStaticSelect(SlackViewStateSelectedOptionsValue),
+    #[serde(rename = "multi_external_select")]
+    MultiExternalSelect(SlackViewStateSelectedOptionsValue),
+    #[serde(rename = "checkboxes")]
+    Checkboxes(SlackViewStateSelectedOptionsValue),
+    #[serde(rename = "users_select")]
+    UsersSelect(SlackViewStateSelectedUserValue),
+    #[serde(rename = "multi_users_select")]
+    MultiUsersSelect(SlackViewStateSelectedUsersValue),
+    #[serde(rename = "conversations_select")]
+    ConversationsSelect(SlackViewStateSelectedConversationValue),
+    #[serde(rename = "multi_conversations_select")]
+    MultiConversationsSelect(SlackViewStateSelectedConversationsValue),
+    #[serde(rename = "channels_select")]
+    ChannelsSelect(SlackViewStateSelectedChannelValue),
+    #[serde(rename = "multi_channels_select")]
+    MultiChannelsSelect(SlackViewStateSelectedChannelsValue),
+    #[serde(rename = "datepicker")]
+    DatePicker(SlackViewStateSelectedDateValue),
+    #[serde(rename = "timepicker")]
+    TimePicker(SlackViewStateSelectedTimeValue),
+    #[serde(rename = "datetimepicker")]
+    DateTimePicker(SlackViewStateSelectedDateTimeValue),
+    ///
+    /// Values of elements that aren't modelled (yet) are preserved as JSON.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl_slack_tagged_with_other!(SlackViewStateValue, "type");
+
+impl SlackViewStateValue {
+    ///
+    /// A JSON representation without the element details: texts and dates as strings,
+    /// selected options as their values, selected users/channels/conversations as their ids
+    /// and empty values as `null`.
+    ///
+    pub fn to_simplified_json(&self) -> serde_json::Value {
+        fn to_json<T: Serialize>(value: T) -> serde_json::Value {
+            serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+        }
+
+        match self {
+            SlackViewStateValue::PlainTextInput(state)
+            | SlackViewStateValue::EmailInput(state)
+            | SlackViewStateValue::UrlInput(state)
+            | SlackViewStateValue::NumberInput(state) => to_json(&state.value),
+            SlackViewStateValue::StaticSelect(state)
+            | SlackViewStateValue::ExternalSelect(state)
+            | SlackViewStateValue::RadioButtons(state) => {
+                to_json(state.selected_option.as_ref().map(|option| &option.value))
+            }
+            SlackViewStateValue::MultiStaticSelect(state)
+            | SlackViewStateValue::MultiExternalSelect(state)
+            | SlackViewStateValue::Checkboxes(state) => to_json(
+                state
+                    .selected_options
+                    .iter()
+                    .map(|option| &option.value)
+                    .collect::<Vec<&String>>(),
+            ),
+            SlackViewStateValue::UsersSelect(state) => to_json(&state.selected_user),
+            SlackViewStateValue::MultiUsersSelect(state) => to_json(&state.selected_users),
+            SlackViewStateValue::ConversationsSelect(state) => {
+                to_json(&state.selected_conversation)
+            }
+            SlackViewStateValue::MultiConversationsSelect(state) => {
+                to_json(&state.selected_conversations)
+            }
+            SlackViewStateValue::ChannelsSelect(state) => to_json(&state.selected_channel),
+            SlackViewStateValue::MultiChannelsSelect(state) => to_json(&state.selected_channels),
+            SlackViewStateValue::DatePicker(state) => to_json(&state.selected_date),
+            SlackViewStateValue::TimePicker(state) => to_json(&state.selected_time),
+            SlackViewStateValue::DateTimePicker(state) => to_json(&state.selected_date_time),
+            SlackViewStateValue::Other(value) => value.clone(),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateTextValue {
+    pub value: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedOptionValue {
+    pub selected_option: Option<SlackBlockChoiceItem<SlackBlockText>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedOptionsValue {
+    #[serde(default)]
+    pub selected_options: Vec<SlackBlockChoiceItem<SlackBlockText>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedUserValue {
+    pub selected_user: Option<SlackUserId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedUsersValue {
+    #[serde(default)]
+    pub selected_users: Vec<SlackUserId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedConversationValue {
+    pub selected_conversation: Option<SlackConversationId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedConversationsValue {
+    #[serde(default)]
+    pub selected_conversations: Vec<SlackConversationId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedChannelValue {
+    pub selected_channel: Option<SlackChannelId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedChannelsValue {
+    #[serde(default)]
+    pub selected_channels: Vec<SlackChannelId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedDateValue {
+    pub selected_date: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedTimeValue {
+    pub selected_time: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewStateSelectedDateTimeValue {
+    pub selected_date_time: Option<SlackDateTime>,
+}
+
+#[test]
+fn check_view_state_values() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct IssueForm {
+        title: String,
+        priority: Option<String>,
+        labels: Vec<String>,
+        assignee: Option<SlackUserId>,
+        due_date: Option<String>,
+    }
+
+    let state: SlackViewState = serde_json::from_value(serde_json::json!({
+        "values": {
+            "title-block": {
+                "title": { "type": "plain_text_input", "value": "Broken build" }
+            },
+            "details-block": {
+                "priority": {
+                    "type": "static_select",
+                    "selected_option": {
+                        "text": { "type": "plain_text", "text": "High" },
+                        "value": "high"
+                    }
+                },
+                "labels": {
+                    "type": "checkboxes",
+                    "selected_options": [
+                        { "text": { "type": "plain_text", "text": "Bug" }, "value": "bug" }
+                    ]
+                },
+                "assignee": { "type": "users_select", "selected_user": "U1" },
+                "due_date": { "type": "datepicker", "selected_date": null }
+            }
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(
+        state.value(&"title-block".into(), &"title".into()),
+        Some(&SlackViewStateValue::PlainTextInput(
+            SlackViewStateTextValue::new().with_value("Broken build".into())
+        ))
+    );
+
+    assert_eq!(
+        state.deserialize_values::<IssueForm>().unwrap(),
+        IssueForm {
+            title: "Broken build".into(),
+            priority: Some("high".into()),
+            labels: vec!["bug".into()],
+            assignee: Some("U1".into()),
+            due_date: None,
+        }
+    );
+}
+
+#[test]
+fn check_view_state_duplicate_action_ids() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Address {
+        city: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Addresses {
+        billing: Address,
+        shipping: Address,
+    }
+
+    let state: SlackViewState = serde_json::from_value(serde_json::json!({
+        "values": {
+            "billing": {
+                "city": { "type": "plain_text_input", "value": "Paris" }
+            },
+            "shipping": {
+                "city": { "type": "plain_text_input", "value": "Berlin" }
+            }
+        }
+    }))
+    .unwrap();
+
+    let err = state.deserialize_values::<Address>().unwrap_err();
+    assert!(err.to_string().contains("duplicate action id `city`"));
+
+    assert_eq!(
+        state.deserialize_block_values::<Addresses>().unwrap(),
+        Addresses {
+            billing: Address {
+                city: Some("Paris".into())
+            },
+            shipping: Address {
+                city: Some("Berlin".into())
+            },
+        }
+    );
+}
+
+#[test]
+fn check_view_optional_string_fields() {
+    let view: SlackView = serde_json::from_value(serde_json::json!({
+        "type": "modal",
+        "title": { "type": "plain_text", "text": "Report" },
+        "blocks": []
+    }))
+    .unwrap();
+
+    match view {
+        SlackView::Modal(modal) => {
+            assert_eq!(modal.private_metadata, None);
+            assert_eq!(modal.callback_id, None);
+            assert_eq!(modal.external_id, None);
+        }
+        other => panic!("unexpected view: {:?}", other),
+    }
+}
+
+#[test]
+fn check_unknown_views_and_state_values() {
+    let view_json = serde_json::json!({ "type": "workflow_step", "blocks": [] });
+    let view: SlackView = serde_json::from_value(view_json.clone()).unwrap();
+    assert_eq!(view, SlackView::Other(view_json.clone()));
+    assert_eq!(serde_json::to_value(&view).unwrap(), view_json);
+    assert!(serde_json::from_value::<SlackView>(serde_json::json!({ "type": "modal" })).is_err());
+
+    let value_json = serde_json::json!({ "type": "rich_text_input", "rich_text_value": {} });
+    let value: SlackViewStateValue = serde_json::from_value(value_json.clone()).unwrap();
+    assert_eq!(value, SlackViewStateValue::Other(value_json.clone()));
+    assert_eq!(serde_json::to_value(&value).unwrap(), value_json);
+    assert!(
+        serde_json::from_value::<SlackViewStateValue>(serde_json::json!({
+            "type": "users_select",
+            "selected_user": 42
+        }))
+        .is_err()
+    );
+}
+
+#[cfg(feature = "lenient")]
+#[test]
+fn check_lenient_view_extra_fields() {
+    let view: SlackView = serde_json::from_value(serde_json::json!({
+        "type": "modal",
+        "id": "V1",
+        "title": { "type": "plain_text", "text": "Title" },
+        "blocks": []
+    }))
+    .unwrap();
+    match &view {
+        SlackView::Modal(modal_view) => {
+            assert_eq!(modal_view.extra.get("id"), Some(&serde_json::json!("V1")))
+        }
+        other => panic!("unexpected view: {:?}", other),
+    }
+    assert_eq!(
+        serde_json::to_value(&view).unwrap(),
+        serde_json::json!({
+            "type": "modal",
+            "title": { "type": "plain_text", "text": "Title" },
+            "blocks": []
+        })
+    );
 }
@@ -15,3 +15,15 @@ pub struct SlackBotInfo {
     #[serde(flatten)]
     pub icons: Option<SlackIconImages>,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackBotProfile {
+    pub id: SlackBotId,
+    pub app_id: Option<SlackAppId>,
+    pub name: Option<String>,
+    pub icons: Option<SlackIconImages>,
+    pub deleted: Option<bool>,
+    pub updated: Option<SlackDateTime>,
+    pub team_id: Option<SlackTeamId>,
+}
@@ -25,6 +25,10 @@ pub struct SlackChannelInfo {
     pub flags: SlackChannelFlags,
     #[serde(flatten)]
     pub last_state: SlackChannelCurrentState,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten)]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 impl HasChannelInfo for SlackChannelInfo {
@@ -0,0 +1,27 @@
+use crate::common::*;
+
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackFile {
+    pub id: SlackFileId,
+    pub created: Option<SlackDateTime>,
+    pub timestamp: Option<SlackDateTime>,
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub mimetype: Option<String>,
+    pub filetype: Option<String>,
+    pub pretty_type: Option<String>,
+    pub user: Option<SlackUserId>,
+    pub size: Option<u64>,
+    pub mode: Option<String>,
+    pub is_external: Option<bool>,
+    pub is_public: Option<bool>,
+    pub url_private: Option<String>,
+    pub url_private_download: Option<String>,
+    pub permalink: Option<String>,
+    pub permalink_public: Option<String>,
+}
@@ -13,6 +13,10 @@ mod team;
 pub use team::*;
 mod channel;
 pub use channel::*;
+mod user_group;
+pub use user_group::*;
+mod file;
+pub use file::*;
 
 mod bot;
 pub use bot::*;
@@ -30,7 +34,7 @@ impl SlackTs {
     pub fn to_date_time(&self) -> Result<DateTime<Utc>, num::ParseIntError> {
         let parts: Vec<&str> = self.value().split('.').collect();
         let ts_int: i64 = parts[0].parse()?;
-        Ok(Utc.timestamp_millis(ts_int * 1000))
+        Ok(Utc.timestamp_millis_opt(ts_int * 1000).unwrap())
     }
 }
 
@@ -46,12 +50,24 @@ pub struct SlackAppId(pub String);
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackChannelId(pub String);
 
+impl SlackTextFormat for SlackChannelId {
+    fn to_slack_format(&self) -> String {
+        format!("<#{}>", self.value())
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackChannelType(pub String);
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackConversationId(pub String);
 
+impl SlackTextFormat for SlackConversationId {
+    fn to_slack_format(&self) -> String {
+        format!("<#{}>", self.value())
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackActionId(pub String);
 
@@ -67,6 +83,21 @@ impl SlackTextFormat for SlackUserId {
     }
 }
 
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackUserGroupId(pub String);
+
+impl SlackTextFormat for SlackUserGroupId {
+    fn to_slack_format(&self) -> String {
+        format!("<!subteam^{}>", self.value())
+    }
+}
+
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackFileId(pub String);
+
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Serialize, Deserialize, ValueStruct)]
+pub struct SlackReactionName(pub String);
+
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct SlackBotId(pub String);
 
@@ -103,6 +134,11 @@ pub struct SlackClientSecret(pub String);
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, ValueStruct)]
 pub struct EmailAddress(pub String);
 
+///
+/// Unknown fields of models captured in the `lenient` mode
+///
+pub type SlackExtraFields = std::collections::HashMap<String, serde_json::Value>;
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackResponseMetadata {
@@ -122,13 +158,14 @@ pub enum SlackConversationType {
     Public,
 }
 
-impl ToString for SlackConversationType {
-    fn to_string(&self) -> String {
-        match self {
-            SlackConversationType::Im => "im".into(),
-            SlackConversationType::Mpim => "mpim".into(),
-            SlackConversationType::Private => "private_channel".into(),
-            SlackConversationType::Public => "public_channel".into(),
-        }
+impl fmt::Display for SlackConversationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let as_str = match self {
+            SlackConversationType::Im => "im",
+            SlackConversationType::Mpim => "mpim",
+            SlackConversationType::Private => "private_channel",
+            SlackConversationType::Public => "public_channel",
+        };
+        write!(f, "{}", as_str)
     }
 }
@@ -12,6 +12,10 @@ pub struct SlackTeamInfo {
     pub domain: Option<String>,
     pub email_domain: Option<String>,
     pub icon: Option<SlackIcon>,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten)]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 #[skip_serializing_none]
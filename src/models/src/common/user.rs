@@ -21,6 +21,10 @@ pub struct SlackUser {
     pub deleted: Option<bool>,
     pub color: Option<SlackColor>,
     pub real_name: Option<String>,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten)]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 #[skip_serializing_none]
@@ -0,0 +1,24 @@
+use crate::common::*;
+
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackUserGroup {
+    pub id: SlackUserGroupId,
+    pub team_id: SlackTeamId,
+    pub name: Option<String>,
+    pub handle: Option<String>,
+    pub description: Option<String>,
+    pub is_usergroup: Option<bool>,
+    pub is_external: Option<bool>,
+    pub date_create: Option<SlackDateTime>,
+    pub date_update: Option<SlackDateTime>,
+    pub date_delete: Option<SlackDateTime>,
+    pub created_by: Option<SlackUserId>,
+    pub updated_by: Option<SlackUserId>,
+    pub user_count: Option<u64>,
+    pub users: Option<Vec<SlackUserId>>,
+}
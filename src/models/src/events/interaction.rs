@@ -7,8 +7,9 @@ use crate::common::*;
 use crate::messages::*;
 use std::collections::HashMap;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackInteractionEvent {
     #[serde(rename = "block_actions")]
     BlockActions(SlackInteractionBlockActionsEvent),
@@ -22,6 +23,55 @@ pub enum SlackInteractionEvent {
     ViewSubmission(SlackInteractionViewSubmissionEvent),
     #[serde(rename = "view_closed")]
     ViewClosed(SlackInteractionViewClosedEvent),
+    #[serde(rename = "block_suggestion")]
+    BlockSuggestion(SlackInteractionBlockSuggestionEvent),
+    ///
+    /// Interactions that aren't modelled (yet) are preserved as JSON,
+    /// so new Slack interactions never break the listener.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl_slack_tagged_with_other!(SlackInteractionEvent, "type");
+
+impl SlackInteractionEvent {
+    pub fn event_type_name(&self) -> &str {
+        match self {
+            SlackInteractionEvent::BlockActions(_) => "block_actions",
+            SlackInteractionEvent::DialogSubmission(_) => "dialog_submission",
+            SlackInteractionEvent::MessageAction(_) => "message_action",
+            SlackInteractionEvent::Shortcut(_) => "shortcut",
+            SlackInteractionEvent::ViewSubmission(_) => "view_submission",
+            SlackInteractionEvent::ViewClosed(_) => "view_closed",
+            SlackInteractionEvent::BlockSuggestion(_) => "block_suggestion",
+            SlackInteractionEvent::Other(value) => value
+                .get("type")
+                .and_then(|event_type| event_type.as_str())
+                .unwrap_or("unknown"),
+        }
+    }
+
+    pub fn team(&self) -> Option<&SlackBasicTeamInfo> {
+        match self {
+            SlackInteractionEvent::BlockActions(event) => Some(&event.team),
+            SlackInteractionEvent::DialogSubmission(event) => Some(&event.team),
+            SlackInteractionEvent::MessageAction(event) => Some(&event.team),
+            SlackInteractionEvent::Shortcut(event) => Some(&event.team),
+            SlackInteractionEvent::ViewSubmission(event) => Some(&event.team),
+            SlackInteractionEvent::ViewClosed(event) => Some(&event.team),
+            SlackInteractionEvent::BlockSuggestion(event) => Some(&event.team),
+            SlackInteractionEvent::Other(_) => None,
+        }
+    }
+
+    pub fn response_url(&self) -> Option<&str> {
+        match self {
+            SlackInteractionEvent::BlockActions(event) => event.response_url.as_deref(),
+            SlackInteractionEvent::MessageAction(event) => Some(&event.response_url),
+            _ => None,
+        }
+    }
 }
 
 #[skip_serializing_none]
@@ -34,9 +84,13 @@ pub struct SlackInteractionBlockActionsEvent {
     pub trigger_id: SlackTriggerId,
     pub channel: Option<SlackBasicChannelInfo>,
     pub message: Option<SlackHistoryMessage>,
-    pub view: Option<SlackView>,
+    pub view: Option<SlackStatefulView>,
     pub response_url: Option<String>,
     pub actions: Option<Vec<SlackInteractionActionInfo>>,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten)]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -67,13 +121,13 @@ pub struct SlackInteractionActionViewContainer {
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackInteractionActionInfo {
     #[serde(rename = "type")]
-    action_type: SlackActionType,
-    action_id: SlackActionId,
-    block_id: Option<SlackBlockId>,
-    text: Option<SlackBlockText>,
-    value: Option<String>,
-    selected_option: Option<SlackBlockChoiceItem<SlackBlockText>>,
-    action_ts: Option<SlackTs>,
+    pub action_type: SlackActionType,
+    pub action_id: SlackActionId,
+    pub block_id: Option<SlackBlockId>,
+    pub text: Option<SlackBlockText>,
+    pub value: Option<String>,
+    pub selected_option: Option<SlackBlockChoiceItem<SlackBlockText>>,
+    pub action_ts: Option<SlackTs>,
 }
 
 #[skip_serializing_none]
@@ -117,6 +171,10 @@ pub struct SlackInteractionViewSubmissionEvent {
     pub team: SlackBasicTeamInfo,
     pub user: SlackBasicUserInfo,
     pub view: SlackStatefulView,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten)]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 #[skip_serializing_none]
@@ -126,3 +184,299 @@ pub struct SlackInteractionViewClosedEvent {
     pub user: SlackBasicUserInfo,
     pub view: SlackStatefulView,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackInteractionBlockSuggestionEvent {
+    pub team: SlackBasicTeamInfo,
+    pub user: SlackBasicUserInfo,
+    pub api_app_id: SlackAppId,
+    pub action_id: SlackActionId,
+    pub block_id: SlackBlockId,
+    pub value: String,
+    pub container: Option<SlackInteractionActionContainer>,
+    pub channel: Option<SlackBasicChannelInfo>,
+    pub message: Option<SlackHistoryMessage>,
+    pub view: Option<SlackView>,
+}
+
+///
+/// Response action for `view_submission` events to show validation errors, update or push a view,
+/// or clear the views stack
+///
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "response_action")]
+pub enum SlackViewSubmissionResponse {
+    #[serde(rename = "errors")]
+    Errors(SlackViewSubmissionErrorsResponse),
+    #[serde(rename = "update")]
+    Update(SlackViewSubmissionUpdateResponse),
+    #[serde(rename = "push")]
+    Push(SlackViewSubmissionPushResponse),
+    #[serde(rename = "clear")]
+    Clear(SlackViewSubmissionClearResponse),
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewSubmissionErrorsResponse {
+    pub errors: HashMap<SlackBlockId, String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewSubmissionUpdateResponse {
+    pub view: SlackView,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewSubmissionPushResponse {
+    pub view: SlackView,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackViewSubmissionClearResponse {}
+
+impl From<SlackViewSubmissionErrorsResponse> for SlackViewSubmissionResponse {
+    fn from(response: SlackViewSubmissionErrorsResponse) -> Self {
+        SlackViewSubmissionResponse::Errors(response)
+    }
+}
+
+impl From<SlackViewSubmissionUpdateResponse> for SlackViewSubmissionResponse {
+    fn from(response: SlackViewSubmissionUpdateResponse) -> Self {
+        SlackViewSubmissionResponse::Update(response)
+    }
+}
+
+impl From<SlackViewSubmissionPushResponse> for SlackViewSubmissionResponse {
+    fn from(response: SlackViewSubmissionPushResponse) -> Self {
+        SlackViewSubmissionResponse::Push(response)
+    }
+}
+
+impl From<SlackViewSubmissionClearResponse> for SlackViewSubmissionResponse {
+    fn from(response: SlackViewSubmissionClearResponse) -> Self {
+        SlackViewSubmissionResponse::Clear(response)
+    }
+}
+
+///
+/// Options for external select elements in response to `block_suggestion` events
+///
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SlackBlockSuggestionResponse {
+    Options(SlackBlockSuggestionOptionsResponse),
+    OptionGroups(SlackBlockSuggestionOptionGroupsResponse),
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackBlockSuggestionOptionsResponse {
+    pub options: Vec<SlackBlockChoiceItem<SlackBlockPlainTextOnly>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackBlockSuggestionOptionGroupsResponse {
+    pub option_groups: Vec<SlackBlockOptionGroup<SlackBlockPlainTextOnly>>,
+}
+
+impl From<SlackBlockSuggestionOptionsResponse> for SlackBlockSuggestionResponse {
+    fn from(response: SlackBlockSuggestionOptionsResponse) -> Self {
+        SlackBlockSuggestionResponse::Options(response)
+    }
+}
+
+impl From<SlackBlockSuggestionOptionGroupsResponse> for SlackBlockSuggestionResponse {
+    fn from(response: SlackBlockSuggestionOptionGroupsResponse) -> Self {
+        SlackBlockSuggestionResponse::OptionGroups(response)
+    }
+}
+
+///
+/// Response of interaction handlers. Interaction events other than `view_submission` are
+/// acknowledged with an empty response.
+///
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackInteractionEventResponse {
+    Empty,
+    ViewSubmission(SlackViewSubmissionResponse),
+    BlockSuggestion(SlackBlockSuggestionResponse),
+}
+
+impl From<()> for SlackInteractionEventResponse {
+    fn from(_: ()) -> Self {
+        SlackInteractionEventResponse::Empty
+    }
+}
+
+impl From<SlackViewSubmissionResponse> for SlackInteractionEventResponse {
+    fn from(response: SlackViewSubmissionResponse) -> Self {
+        SlackInteractionEventResponse::ViewSubmission(response)
+    }
+}
+
+impl From<SlackBlockSuggestionResponse> for SlackInteractionEventResponse {
+    fn from(response: SlackBlockSuggestionResponse) -> Self {
+        SlackInteractionEventResponse::BlockSuggestion(response)
+    }
+}
+
+impl<T> From<Option<T>> for SlackInteractionEventResponse
+where
+    T: Into<SlackInteractionEventResponse>,
+{
+    fn from(response: Option<T>) -> Self {
+        response.map_or(SlackInteractionEventResponse::Empty, |r| r.into())
+    }
+}
+
+#[test]
+fn check_view_submission_response_serialization() {
+    let errors_response: SlackViewSubmissionResponse = SlackViewSubmissionErrorsResponse::new(
+        vec![("title-block".into(), "Title is required".into())]
+            .into_iter()
+            .collect(),
+    )
+    .into();
+    assert_eq!(
+        serde_json::to_value(&errors_response).unwrap(),
+        serde_json::json!({
+            "response_action": "errors",
+            "errors": { "title-block": "Title is required" }
+        })
+    );
+
+    let update_response: SlackViewSubmissionResponse = SlackViewSubmissionUpdateResponse::new(
+        SlackView::Modal(SlackModalView::new("Report".into(), vec![])),
+    )
+    .into();
+    let update_json = serde_json::json!({
+        "response_action": "update",
+        "view": {
+            "type": "modal",
+            "title": { "type": "plain_text", "text": "Report" },
+            "blocks": []
+        }
+    });
+    assert_eq!(serde_json::to_value(&update_response).unwrap(), update_json);
+    assert_eq!(
+        serde_json::from_value::<SlackViewSubmissionResponse>(update_json).unwrap(),
+        update_response
+    );
+
+    let push_response: SlackViewSubmissionResponse = SlackViewSubmissionPushResponse::new(
+        SlackView::Modal(SlackModalView::new("Details".into(), vec![])),
+    )
+    .into();
+    assert_eq!(
+        serde_json::to_value(&push_response).unwrap()["response_action"],
+        "push"
+    );
+
+    let clear_response: SlackViewSubmissionResponse =
+        SlackViewSubmissionClearResponse::new().into();
+    assert_eq!(
+        serde_json::to_value(&clear_response).unwrap(),
+        serde_json::json!({ "response_action": "clear" })
+    );
+}
+
+#[test]
+fn check_interaction_event_response_conversions() {
+    assert_eq!(
+        SlackInteractionEventResponse::from(()),
+        SlackInteractionEventResponse::Empty
+    );
+    assert_eq!(
+        SlackInteractionEventResponse::from(None::<SlackViewSubmissionResponse>),
+        SlackInteractionEventResponse::Empty
+    );
+
+    let clear_response: SlackViewSubmissionResponse =
+        SlackViewSubmissionClearResponse::new().into();
+    assert_eq!(
+        SlackInteractionEventResponse::from(Some(clear_response.clone())),
+        SlackInteractionEventResponse::ViewSubmission(clear_response)
+    );
+
+    let options_response: SlackBlockSuggestionResponse =
+        SlackBlockSuggestionOptionsResponse::new(vec![]).into();
+    assert_eq!(
+        SlackInteractionEventResponse::from(options_response.clone()),
+        SlackInteractionEventResponse::BlockSuggestion(options_response)
+    );
+}
+
+#[test]
+fn check_block_suggestion_response_serialization() {
+    let options_json = serde_json::json!({
+        "options": [
+            { "text": { "type": "plain_text", "text": "Backend" }, "value": "backend" }
+        ]
+    });
+    let options_response: SlackBlockSuggestionResponse =
+        SlackBlockSuggestionOptionsResponse::new(vec![SlackBlockChoiceItem::new(
+            "Backend".into(),
+            "backend".into(),
+        )])
+        .into();
+    assert_eq!(
+        serde_json::to_value(&options_response).unwrap(),
+        options_json
+    );
+    assert_eq!(
+        serde_json::from_value::<SlackBlockSuggestionResponse>(options_json).unwrap(),
+        options_response
+    );
+
+    let option_groups_json = serde_json::json!({
+        "option_groups": [
+            {
+                "label": { "type": "plain_text", "text": "Teams" },
+                "options": [
+                    { "text": { "type": "plain_text", "text": "Frontend" }, "value": "frontend" }
+                ]
+            }
+        ]
+    });
+    let option_groups_response: SlackBlockSuggestionResponse =
+        SlackBlockSuggestionOptionGroupsResponse::new(vec![SlackBlockOptionGroup::new(
+            "Teams".into(),
+            vec![SlackBlockChoiceItem::new(
+                "Frontend".into(),
+                "frontend".into(),
+            )],
+        )])
+        .into();
+    assert_eq!(
+        serde_json::to_value(&option_groups_response).unwrap(),
+        option_groups_json
+    );
+    assert_eq!(
+        serde_json::from_value::<SlackBlockSuggestionResponse>(option_groups_json).unwrap(),
+        option_groups_response
+    );
+}
+
+#[test]
+fn check_unknown_interaction_event() {
+    let json = serde_json::json!({ "type": "brand_new_interaction", "team": { "id": "T1" } });
+    let event: SlackInteractionEvent = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(event.event_type_name(), "brand_new_interaction");
+    assert_eq!(event, SlackInteractionEvent::Other(json.clone()));
+    assert_eq!(serde_json::to_value(&event).unwrap(), json);
+
+    assert!(
+        serde_json::from_value::<SlackInteractionEvent>(serde_json::json!({
+            "type": "view_closed",
+            "team": { "id": "T1" }
+        }))
+        .is_err()
+    );
+}
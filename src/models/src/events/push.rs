@@ -9,7 +9,7 @@ use crate::messages::*;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackPushEvent {
     #[serde(rename = "url_verification")]
     UrlVerification(SlackUrlVerificationEvent),
@@ -17,8 +17,15 @@ pub enum SlackPushEvent {
     EventCallback(SlackEventCallback),
     #[serde(rename = "app_rate_limited")]
     AppRateLimited(SlackAppRateLimitedEvent),
+    ///
+    /// Push events that aren't modelled (yet) are preserved as JSON.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
 }
 
+impl_slack_tagged_with_other!(SlackPushEvent, "type");
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackUrlVerificationEvent {
@@ -44,10 +51,14 @@ pub struct SlackEventCallback {
     pub event_context: Option<SlackEventContext>,
     pub authed_users: Option<Vec<SlackUserId>>,
     pub authorizations: Option<Vec<SlackEventAuthorization>>,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten)]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum SlackEventCallbackBody {
     #[serde(rename = "message")]
     Message(SlackMessageEvent),
@@ -57,6 +68,132 @@ pub enum SlackEventCallbackBody {
     AppMention(SlackAppMentionEvent),
     #[serde(rename = "app_uninstalled")]
     AppUninstalled(SlackAppUninstalledEvent),
+    #[serde(rename = "reaction_added")]
+    ReactionAdded(SlackReactionAddedEvent),
+    #[serde(rename = "reaction_removed")]
+    ReactionRemoved(SlackReactionRemovedEvent),
+    #[serde(rename = "member_joined_channel")]
+    MemberJoinedChannel(SlackMemberJoinedChannelEvent),
+    #[serde(rename = "member_left_channel")]
+    MemberLeftChannel(SlackMemberLeftChannelEvent),
+    #[serde(rename = "channel_created")]
+    ChannelCreated(SlackChannelCreatedEvent),
+    #[serde(rename = "channel_rename")]
+    ChannelRename(SlackChannelRenameEvent),
+    #[serde(rename = "channel_archive")]
+    ChannelArchive(SlackChannelArchiveEvent),
+    #[serde(rename = "channel_unarchive")]
+    ChannelUnarchive(SlackChannelUnarchiveEvent),
+    #[serde(rename = "channel_deleted")]
+    ChannelDeleted(SlackChannelDeletedEvent),
+    #[serde(rename = "channel_left")]
+    ChannelLeft(SlackChannelLeftEvent),
+    #[serde(rename = "team_join")]
+    TeamJoin(SlackTeamJoinEvent),
+    #[serde(rename = "team_rename")]
+    TeamRename(SlackTeamRenameEvent),
+    #[serde(rename = "user_change")]
+    UserChange(SlackUserChangeEvent),
+    #[serde(rename = "user_status_changed")]
+    UserStatusChanged(SlackUserStatusChangedEvent),
+    #[serde(rename = "file_created")]
+    FileCreated(SlackFileEvent),
+    #[serde(rename = "file_shared")]
+    FileShared(SlackFileEvent),
+    #[serde(rename = "file_unshared")]
+    FileUnshared(SlackFileEvent),
+    #[serde(rename = "file_change")]
+    FileChange(SlackFileEvent),
+    #[serde(rename = "file_deleted")]
+    FileDeleted(SlackFileDeletedEvent),
+    #[serde(rename = "link_shared")]
+    LinkShared(SlackLinkSharedEvent),
+    #[serde(rename = "emoji_changed")]
+    EmojiChanged(SlackEmojiChangedEvent),
+    #[serde(rename = "tokens_revoked")]
+    TokensRevoked(SlackTokensRevokedEvent),
+    #[serde(rename = "pin_added")]
+    PinAdded(SlackPinEvent),
+    #[serde(rename = "pin_removed")]
+    PinRemoved(SlackPinEvent),
+    #[serde(rename = "subteam_created")]
+    SubteamCreated(SlackSubteamEvent),
+    #[serde(rename = "subteam_updated")]
+    SubteamUpdated(SlackSubteamEvent),
+    #[serde(rename = "subteam_members_changed")]
+    SubteamMembersChanged(SlackSubteamMembersChangedEvent),
+    #[serde(rename = "subteam_self_added")]
+    SubteamSelfAdded(SlackSubteamSelfEvent),
+    #[serde(rename = "subteam_self_removed")]
+    SubteamSelfRemoved(SlackSubteamSelfEvent),
+    #[serde(rename = "dnd_updated")]
+    DndUpdated(SlackDndUpdatedEvent),
+    #[serde(rename = "dnd_updated_user")]
+    DndUpdatedUser(SlackDndUpdatedEvent),
+    #[serde(rename = "message_metadata_posted")]
+    MessageMetadataPosted(SlackMessageMetadataPostedEvent),
+    #[serde(rename = "message_metadata_updated")]
+    MessageMetadataUpdated(SlackMessageMetadataUpdatedEvent),
+    #[serde(rename = "message_metadata_deleted")]
+    MessageMetadataDeleted(SlackMessageMetadataDeletedEvent),
+    ///
+    /// Events that aren't modelled (yet) are preserved as JSON,
+    /// so new Slack events never break the listener.
+    /// Events of known types with unexpected payloads are deserialization errors.
+    ///
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl_slack_tagged_with_other!(SlackEventCallbackBody, "type");
+
+impl SlackEventCallbackBody {
+    pub fn event_type_name(&self) -> &str {
+        match self {
+            SlackEventCallbackBody::Message(_) => "message",
+            SlackEventCallbackBody::AppHomeOpened(_) => "app_home_opened",
+            SlackEventCallbackBody::AppMention(_) => "app_mention",
+            SlackEventCallbackBody::AppUninstalled(_) => "app_uninstalled",
+            SlackEventCallbackBody::ReactionAdded(_) => "reaction_added",
+            SlackEventCallbackBody::ReactionRemoved(_) => "reaction_removed",
+            SlackEventCallbackBody::MemberJoinedChannel(_) => "member_joined_channel",
+            SlackEventCallbackBody::MemberLeftChannel(_) => "member_left_channel",
+            SlackEventCallbackBody::ChannelCreated(_) => "channel_created",
+            SlackEventCallbackBody::ChannelRename(_) => "channel_rename",
+            SlackEventCallbackBody::ChannelArchive(_) => "channel_archive",
+            SlackEventCallbackBody::ChannelUnarchive(_) => "channel_unarchive",
+            SlackEventCallbackBody::ChannelDeleted(_) => "channel_deleted",
+            SlackEventCallbackBody::ChannelLeft(_) => "channel_left",
+            SlackEventCallbackBody::TeamJoin(_) => "team_join",
+            SlackEventCallbackBody::TeamRename(_) => "team_rename",
+            SlackEventCallbackBody::UserChange(_) => "user_change",
+            SlackEventCallbackBody::UserStatusChanged(_) => "user_status_changed",
+            SlackEventCallbackBody::FileCreated(_) => "file_created",
+            SlackEventCallbackBody::FileShared(_) => "file_shared",
+            SlackEventCallbackBody::FileUnshared(_) => "file_unshared",
+            SlackEventCallbackBody::FileChange(_) => "file_change",
+            SlackEventCallbackBody::FileDeleted(_) => "file_deleted",
+            SlackEventCallbackBody::LinkShared(_) => "link_shared",
+            SlackEventCallbackBody::EmojiChanged(_) => "emoji_changed",
+            SlackEventCallbackBody::TokensRevoked(_) => "tokens_revoked",
+            SlackEventCallbackBody::PinAdded(_) => "pin_added",
+            SlackEventCallbackBody::PinRemoved(_) => "pin_removed",
+            SlackEventCallbackBody::SubteamCreated(_) => "subteam_created",
+            SlackEventCallbackBody::SubteamUpdated(_) => "subteam_updated",
+            SlackEventCallbackBody::SubteamMembersChanged(_) => "subteam_members_changed",
+            SlackEventCallbackBody::SubteamSelfAdded(_) => "subteam_self_added",
+            SlackEventCallbackBody::SubteamSelfRemoved(_) => "subteam_self_removed",
+            SlackEventCallbackBody::DndUpdated(_) => "dnd_updated",
+            SlackEventCallbackBody::DndUpdatedUser(_) => "dnd_updated_user",
+            SlackEventCallbackBody::MessageMetadataPosted(_) => "message_metadata_posted",
+            SlackEventCallbackBody::MessageMetadataUpdated(_) => "message_metadata_updated",
+            SlackEventCallbackBody::MessageMetadataDeleted(_) => "message_metadata_deleted",
+            SlackEventCallbackBody::Other(value) => value
+                .get("type")
+                .and_then(|event_type| event_type.as_str())
+                .unwrap_or("unknown"),
+        }
+    }
 }
 
 #[skip_serializing_none]
@@ -72,6 +209,96 @@ pub struct SlackMessageEvent {
     pub hidden: Option<bool>,
     pub edited: Option<SlackMessageEventEdited>,
     pub deleted_ts: Option<SlackTs>,
+    /// The new version of the message for `message_changed`
+    pub message: Option<Box<SlackMessageEvent>>,
+    /// The previous version of the message for `message_changed` and `message_deleted`
+    pub previous_message: Option<Box<SlackMessageEvent>>,
+    /// The thread root message for `thread_broadcast`
+    pub root: Option<Box<SlackMessageEvent>>,
+    /// The bot profile for `bot_message` and other messages posted by apps
+    pub bot_profile: Option<SlackBotProfile>,
+    /// The user who invited the joined user for `channel_join`
+    pub inviter: Option<SlackUserId>,
+    /// Shared files for `file_share`
+    pub files: Option<Vec<SlackFile>>,
+    pub upload: Option<bool>,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten)]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
+}
+
+impl SlackMessageEvent {
+    ///
+    /// Typed data of the message subtype. Returns `None` for messages without subtypes,
+    /// subtypes without specific data, and events missing the data required for their subtype.
+    ///
+    pub fn subtype_data(&self) -> Option<SlackMessageEventSubtypeData<'_>> {
+        match self.subtype.as_ref()? {
+            SlackMessageEventType::MessageChanged => {
+                Some(SlackMessageEventSubtypeData::MessageChanged {
+                    message: self.message.as_deref()?,
+                    previous_message: self.previous_message.as_deref(),
+                })
+            }
+            SlackMessageEventType::MessageDeleted => {
+                Some(SlackMessageEventSubtypeData::MessageDeleted {
+                    deleted_ts: self.deleted_ts.as_ref()?,
+                    previous_message: self.previous_message.as_deref(),
+                })
+            }
+            SlackMessageEventType::ThreadBroadcast => {
+                Some(SlackMessageEventSubtypeData::ThreadBroadcast {
+                    thread_ts: self.origin.thread_ts.as_ref()?,
+                    root: self.root.as_deref(),
+                })
+            }
+            SlackMessageEventType::ChannelJoin => Some(SlackMessageEventSubtypeData::ChannelJoin {
+                user: self.sender.user.as_ref()?,
+                inviter: self.inviter.as_ref(),
+            }),
+            SlackMessageEventType::BotMessage => Some(SlackMessageEventSubtypeData::BotMessage {
+                bot_id: self.sender.bot_id.as_ref()?,
+                bot_profile: self.bot_profile.as_ref(),
+            }),
+            SlackMessageEventType::FileShare => Some(SlackMessageEventSubtypeData::FileShare {
+                files: self.files.as_deref().unwrap_or(&[]),
+                upload: self.upload.unwrap_or(false),
+            }),
+            _ => None,
+        }
+    }
+}
+
+///
+/// Data of message event subtypes borrowed from `SlackMessageEvent`
+///
+#[derive(Debug, PartialEq, Clone)]
+pub enum SlackMessageEventSubtypeData<'a> {
+    MessageChanged {
+        message: &'a SlackMessageEvent,
+        previous_message: Option<&'a SlackMessageEvent>,
+    },
+    MessageDeleted {
+        deleted_ts: &'a SlackTs,
+        previous_message: Option<&'a SlackMessageEvent>,
+    },
+    ThreadBroadcast {
+        thread_ts: &'a SlackTs,
+        root: Option<&'a SlackMessageEvent>,
+    },
+    ChannelJoin {
+        user: &'a SlackUserId,
+        inviter: Option<&'a SlackUserId>,
+    },
+    BotMessage {
+        bot_id: &'a SlackBotId,
+        bot_profile: Option<&'a SlackBotProfile>,
+    },
+    FileShare {
+        files: &'a [SlackFile],
+        upload: bool,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -102,6 +329,17 @@ pub enum SlackMessageEventType {
     JoinerNotification,
     #[serde(rename = "slackbot_response")]
     SlackbotResponse,
+    #[serde(rename = "thread_broadcast")]
+    ThreadBroadcast,
+    #[serde(rename = "file_share")]
+    FileShare,
+    #[serde(rename = "channel_leave")]
+    ChannelLeave,
+    ///
+    /// Subtypes that aren't modelled (yet) are preserved as is.
+    ///
+    #[serde(untagged)]
+    Other(String),
 }
 
 #[skip_serializing_none]
@@ -110,7 +348,7 @@ pub struct SlackAppHomeOpenedEvent {
     pub user: SlackUserId,
     pub channel: SlackChannelId,
     pub tab: String,
-    pub view: Option<SlackView>,
+    pub view: Option<SlackStatefulView>,
 }
 
 #[skip_serializing_none]
@@ -134,3 +372,512 @@ pub struct SlackMessageEventEdited {
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
 pub struct SlackAppUninstalledEvent {}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackReactionAddedEvent {
+    pub user: SlackUserId,
+    pub reaction: SlackReactionName,
+    pub item_user: Option<SlackUserId>,
+    pub item: SlackReactionsItem,
+    pub event_ts: SlackTs,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackReactionRemovedEvent {
+    pub user: SlackUserId,
+    pub reaction: SlackReactionName,
+    pub item_user: Option<SlackUserId>,
+    pub item: SlackReactionsItem,
+    pub event_ts: SlackTs,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SlackReactionsItem {
+    #[serde(rename = "message")]
+    Message(SlackReactionsItemMessage),
+    #[serde(rename = "file")]
+    File(SlackReactionsItemFile),
+    #[serde(rename = "file_comment")]
+    FileComment(SlackReactionsItemFileComment),
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackReactionsItemMessage {
+    pub channel: SlackChannelId,
+    pub ts: SlackTs,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackReactionsItemFile {
+    pub file: SlackFileId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackReactionsItemFileComment {
+    pub file: SlackFileId,
+    pub file_comment: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMemberJoinedChannelEvent {
+    pub user: SlackUserId,
+    pub channel: SlackChannelId,
+    pub channel_type: Option<SlackChannelType>,
+    pub team: Option<SlackTeamId>,
+    pub inviter: Option<SlackUserId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMemberLeftChannelEvent {
+    pub user: SlackUserId,
+    pub channel: SlackChannelId,
+    pub channel_type: Option<SlackChannelType>,
+    pub team: Option<SlackTeamId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackChannelCreatedEvent {
+    pub channel: SlackChannelCreatedInfo,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackChannelCreatedInfo {
+    pub id: SlackChannelId,
+    pub name: String,
+    pub created: Option<SlackDateTime>,
+    pub creator: Option<SlackUserId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackChannelRenameEvent {
+    pub channel: SlackChannelCreatedInfo,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackChannelArchiveEvent {
+    pub channel: SlackChannelId,
+    pub user: Option<SlackUserId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackChannelUnarchiveEvent {
+    pub channel: SlackChannelId,
+    pub user: Option<SlackUserId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackChannelDeletedEvent {
+    pub channel: SlackChannelId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackChannelLeftEvent {
+    pub channel: SlackChannelId,
+    pub actor_id: Option<SlackUserId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackTeamJoinEvent {
+    pub user: SlackUser,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackTeamRenameEvent {
+    pub name: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackUserChangeEvent {
+    pub user: SlackUser,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackUserStatusChangedEvent {
+    pub user: SlackUser,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackFileEvent {
+    pub file_id: SlackFileId,
+    pub user_id: Option<SlackUserId>,
+    pub channel_id: Option<SlackChannelId>,
+    pub file: Option<SlackFileEventInfo>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackFileEventInfo {
+    pub id: SlackFileId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackFileDeletedEvent {
+    pub file_id: SlackFileId,
+    pub channel_ids: Option<Vec<SlackChannelId>>,
+    pub event_ts: Option<SlackTs>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackLinkSharedEvent {
+    pub channel: SlackChannelId,
+    pub user: SlackUserId,
+    pub message_ts: SlackTs,
+    pub thread_ts: Option<SlackTs>,
+    pub links: Vec<SlackLinkSharedLink>,
+    pub unfurl_id: Option<String>,
+    pub source: Option<String>,
+    pub is_bot_user_member: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackLinkSharedLink {
+    pub domain: String,
+    pub url: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackEmojiChangedEvent {
+    pub subtype: SlackEmojiChangedEventType,
+    pub name: Option<String>,
+    pub names: Option<Vec<String>>,
+    pub value: Option<String>,
+    pub old_name: Option<String>,
+    pub new_name: Option<String>,
+    pub event_ts: SlackTs,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum SlackEmojiChangedEventType {
+    #[serde(rename = "add")]
+    Add,
+    #[serde(rename = "remove")]
+    Remove,
+    #[serde(rename = "rename")]
+    Rename,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackTokensRevokedEvent {
+    pub tokens: SlackTokensRevokedTokens,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackTokensRevokedTokens {
+    pub oauth: Option<Vec<SlackUserId>>,
+    pub bot: Option<Vec<SlackUserId>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackPinEvent {
+    pub user: SlackUserId,
+    pub channel_id: SlackChannelId,
+    pub item: SlackPinItem,
+    pub has_pins: Option<bool>,
+    pub event_ts: SlackTs,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackPinItem {
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub channel: Option<SlackChannelId>,
+    pub message: Option<SlackHistoryMessage>,
+    pub file: Option<SlackFileEventInfo>,
+    pub created: Option<SlackDateTime>,
+    pub created_by: Option<SlackUserId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackSubteamEvent {
+    pub subteam: SlackUserGroup,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackSubteamMembersChangedEvent {
+    pub subteam_id: SlackUserGroupId,
+    pub team_id: SlackTeamId,
+    pub date_previous_update: Option<SlackDateTime>,
+    pub date_update: Option<SlackDateTime>,
+    pub added_users: Option<Vec<SlackUserId>>,
+    pub added_users_count: Option<u64>,
+    pub removed_users: Option<Vec<SlackUserId>>,
+    pub removed_users_count: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackSubteamSelfEvent {
+    pub subteam_id: SlackUserGroupId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackDndUpdatedEvent {
+    pub user: SlackUserId,
+    pub dnd_status: SlackDndStatus,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackDndStatus {
+    pub dnd_enabled: bool,
+    pub next_dnd_start_ts: Option<SlackDateTime>,
+    pub next_dnd_end_ts: Option<SlackDateTime>,
+    pub snooze_enabled: Option<bool>,
+    pub snooze_endtime: Option<SlackDateTime>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMessageMetadataPostedEvent {
+    pub app_id: Option<SlackAppId>,
+    pub bot_id: Option<SlackBotId>,
+    pub user_id: Option<SlackUserId>,
+    pub team_id: Option<SlackTeamId>,
+    pub channel_id: SlackChannelId,
+    pub metadata: SlackMessageMetadata,
+    pub message_ts: SlackTs,
+    pub event_ts: Option<SlackTs>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMessageMetadataUpdatedEvent {
+    pub app_id: Option<SlackAppId>,
+    pub bot_id: Option<SlackBotId>,
+    pub user_id: Option<SlackUserId>,
+    pub team_id: Option<SlackTeamId>,
+    pub channel_id: SlackChannelId,
+    pub metadata: SlackMessageMetadata,
+    pub previous_metadata: Option<SlackMessageMetadata>,
+    pub message_ts: SlackTs,
+    pub event_ts: Option<SlackTs>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMessageMetadataDeletedEvent {
+    pub app_id: Option<SlackAppId>,
+    pub bot_id: Option<SlackBotId>,
+    pub user_id: Option<SlackUserId>,
+    pub team_id: Option<SlackTeamId>,
+    pub channel_id: SlackChannelId,
+    pub previous_metadata: Option<SlackMessageMetadata>,
+    pub message_ts: SlackTs,
+    pub deleted_ts: Option<SlackTs>,
+    pub event_ts: Option<SlackTs>,
+}
+
+#[test]
+fn check_unknown_event_callback_body() {
+    let known: SlackEventCallbackBody = serde_json::from_str(
+        r#"{"type":"reaction_added","user":"U1","reaction":"thumbsup","item":{"type":"message","channel":"C1","ts":"1.2"},"event_ts":"1.3"}"#,
+    )
+    .unwrap();
+    assert_eq!(known.event_type_name(), "reaction_added");
+
+    let unknown: SlackEventCallbackBody =
+        serde_json::from_str(r#"{"type":"brand_new_event","field":42}"#).unwrap();
+    assert_eq!(unknown.event_type_name(), "brand_new_event");
+    assert_eq!(
+        serde_json::to_value(&unknown).unwrap(),
+        serde_json::json!({"type": "brand_new_event", "field": 42})
+    );
+    assert_eq!(
+        serde_json::from_value::<SlackEventCallbackBody>(serde_json::to_value(&unknown).unwrap())
+            .unwrap(),
+        unknown
+    );
+}
+
+#[test]
+fn check_unknown_push_event() {
+    let json = serde_json::json!({ "type": "brand_new_push_event", "token": "t" });
+    let event: SlackPushEvent = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(event, SlackPushEvent::Other(json.clone()));
+    assert_eq!(serde_json::to_value(&event).unwrap(), json);
+
+    let malformed_callback = serde_json::from_value::<SlackPushEvent>(serde_json::json!({
+        "type": "event_callback",
+        "team_id": "T1",
+        "api_app_id": "A1",
+        "event": { "type": "app_home_opened", "user": "U1", "channel": "D1" },
+        "event_id": "Ev1",
+        "event_time": 1
+    }));
+    assert!(malformed_callback.is_err());
+}
+
+#[test]
+fn check_malformed_known_event_callback_body() {
+    let malformed = serde_json::from_value::<SlackEventCallbackBody>(serde_json::json!({
+        "type": "app_home_opened",
+        "user": "U1",
+        "channel": "D1"
+    }));
+    assert!(malformed
+        .unwrap_err()
+        .to_string()
+        .contains("missing field `tab`"));
+}
+
+#[test]
+fn check_message_changed_event() {
+    let event: SlackEventCallbackBody = serde_json::from_value(serde_json::json!({
+        "type": "message",
+        "subtype": "message_changed",
+        "hidden": true,
+        "channel": "C1",
+        "ts": "1.3",
+        "message": {
+            "type": "message",
+            "user": "U1",
+            "text": "Hello, world",
+            "ts": "1.2",
+            "edited": { "user": "U1", "ts": "1.3" }
+        },
+        "previous_message": { "type": "message", "user": "U1", "text": "Hello", "ts": "1.2" }
+    }))
+    .unwrap();
+
+    let text = |message: &SlackMessageEvent| {
+        message
+            .content
+            .as_ref()
+            .and_then(|content| content.text.clone())
+    };
+
+    match event {
+        SlackEventCallbackBody::Message(message_event) => match message_event.subtype_data() {
+            Some(SlackMessageEventSubtypeData::MessageChanged {
+                message,
+                previous_message,
+            }) => {
+                assert_eq!(text(message), Some("Hello, world".into()));
+                assert_eq!(
+                    message.edited.as_ref().map(|edited| edited.ts.clone()),
+                    Some("1.3".into())
+                );
+                assert_eq!(previous_message.and_then(text), Some("Hello".into()));
+            }
+            other => panic!("Unexpected subtype data: {:?}", other),
+        },
+        other => panic!("Unexpected event: {:?}", other),
+    }
+}
+
+#[test]
+fn check_message_deleted_event() {
+    let message_event: SlackMessageEvent = serde_json::from_value(serde_json::json!({
+        "type": "message",
+        "subtype": "message_deleted",
+        "hidden": true,
+        "channel": "C1",
+        "ts": "1.4",
+        "deleted_ts": "1.2",
+        "previous_message": { "type": "message", "user": "U1", "text": "Hello", "ts": "1.2" }
+    }))
+    .unwrap();
+
+    match message_event.subtype_data() {
+        Some(SlackMessageEventSubtypeData::MessageDeleted {
+            deleted_ts,
+            previous_message,
+        }) => {
+            assert_eq!(deleted_ts, &SlackTs("1.2".into()));
+            assert_eq!(
+                previous_message.and_then(|message| message.sender.user.clone()),
+                Some("U1".into())
+            );
+        }
+        other => panic!("Unexpected subtype data: {:?}", other),
+    }
+}
+
+#[test]
+fn check_thread_broadcast_event() {
+    let message_event: SlackMessageEvent = serde_json::from_value(serde_json::json!({
+        "type": "message",
+        "subtype": "thread_broadcast",
+        "channel": "C1",
+        "user": "U2",
+        "text": "Also sent to the channel",
+        "ts": "1.5",
+        "thread_ts": "1.2",
+        "root": { "type": "message", "user": "U1", "text": "Question", "ts": "1.2", "thread_ts": "1.2" }
+    }))
+    .unwrap();
+
+    match message_event.subtype_data() {
+        Some(SlackMessageEventSubtypeData::ThreadBroadcast { thread_ts, root }) => {
+            assert_eq!(thread_ts, &SlackTs("1.2".into()));
+            assert_eq!(
+                root.map(|root| root.origin.ts.clone()),
+                Some(SlackTs("1.2".into()))
+            );
+        }
+        other => panic!("Unexpected subtype data: {:?}", other),
+    }
+
+    let plain_message: SlackMessageEvent = serde_json::from_value(serde_json::json!({
+        "type": "message",
+        "channel": "C1",
+        "user": "U1",
+        "text": "Hello",
+        "ts": "1.6"
+    }))
+    .unwrap();
+    assert_eq!(plain_message.subtype_data(), None);
+}
+
+#[cfg(feature = "lenient")]
+#[test]
+fn check_lenient_extra_fields() {
+    let json = serde_json::json!({
+        "type": "message",
+        "channel": "C1",
+        "ts": "1.2",
+        "text": "Hello",
+        "user": "U1",
+        "brand_new_field": {"nested": true}
+    });
+    let event: SlackEventCallbackBody = serde_json::from_value(json.clone()).unwrap();
+    match &event {
+        SlackEventCallbackBody::Message(message_event) => assert_eq!(
+            message_event.extra.get("brand_new_field"),
+            Some(&serde_json::json!({"nested": true}))
+        ),
+        other => panic!("Unexpected event: {:?}", other),
+    }
+    assert_eq!(serde_json::to_value(&event).unwrap(), json);
+}
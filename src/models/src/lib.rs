@@ -1,3 +1,10 @@
+// Allows derived templates to refer to the crate by its name inside the crate itself
+#[cfg(feature = "derive")]
+extern crate self as slack_morphism_models;
+
+#[macro_use]
+mod tagged;
+
 mod common;
 
 pub mod blocks;
@@ -0,0 +1,136 @@
+use crate::blocks::*;
+use crate::common::*;
+use rsb_derive::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+///
+/// Legacy secondary message attachments.
+/// https://api.slack.com/reference/messaging/attachments
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMessageAttachment {
+    pub id: Option<i64>,
+    pub color: Option<String>,
+    pub fallback: Option<String>,
+    pub pretext: Option<String>,
+    pub author_name: Option<String>,
+    pub author_link: Option<String>,
+    pub author_icon: Option<String>,
+    pub title: Option<String>,
+    pub title_link: Option<String>,
+    pub text: Option<String>,
+    pub fields: Option<Vec<SlackMessageAttachmentFieldObject>>,
+    pub image_url: Option<String>,
+    pub thumb_url: Option<String>,
+    pub footer: Option<String>,
+    pub footer_icon: Option<String>,
+    pub mrkdwn_in: Option<Vec<String>>,
+    pub blocks: Option<Vec<SlackBlock>>,
+    pub callback_id: Option<SlackCallbackId>,
+    pub attachment_type: Option<String>,
+    pub actions: Option<Vec<SlackMessageAttachmentAction>>,
+}
+
+impl SlackMessageAttachment {
+    pub fn new_colored(color: String, text: String) -> Self {
+        Self::new()
+            .with_color(color)
+            .with_fallback(text.clone())
+            .with_text(text)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMessageAttachmentFieldObject {
+    pub title: Option<String>,
+    pub value: Option<String>,
+    pub short: Option<bool>,
+}
+
+///
+/// Legacy interactive message actions (buttons and menus).
+/// https://api.slack.com/legacy/interactive-message-field-guide#action_fields
+///
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMessageAttachmentAction {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub text: Option<String>,
+    #[serde(rename = "type")]
+    pub action_type: SlackMessageAttachmentActionType,
+    pub value: Option<String>,
+    pub style: Option<String>,
+    pub url: Option<String>,
+    pub confirm: Option<SlackMessageAttachmentActionConfirm>,
+    pub options: Option<Vec<SlackMessageAttachmentActionOption>>,
+    pub selected_options: Option<Vec<SlackMessageAttachmentActionOption>>,
+    pub data_source: Option<String>,
+    pub min_query_length: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum SlackMessageAttachmentActionType {
+    #[serde(rename = "button")]
+    Button,
+    #[serde(rename = "select")]
+    Select,
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMessageAttachmentActionConfirm {
+    pub title: Option<String>,
+    pub text: String,
+    pub ok_text: Option<String>,
+    pub dismiss_text: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Builder)]
+pub struct SlackMessageAttachmentActionOption {
+    pub text: String,
+    pub value: String,
+    pub description: Option<String>,
+}
+
+#[test]
+fn check_legacy_attachment_actions() {
+    let attachment: SlackMessageAttachment = serde_json::from_value(serde_json::json!({
+        "callback_id": "legacy_actions",
+        "actions": [
+            {
+                "type": "button",
+                "text": "Ok",
+                "url": "https://example.net/ok"
+            },
+            {
+                "name": "due_date",
+                "type": "datepicker"
+            }
+        ]
+    }))
+    .unwrap();
+
+    let actions = attachment.actions.clone().unwrap();
+    assert_eq!(
+        actions[0],
+        SlackMessageAttachmentAction::new(SlackMessageAttachmentActionType::Button)
+            .with_text("Ok".into())
+            .with_url("https://example.net/ok".into())
+    );
+    assert_eq!(actions[0].name, None);
+    assert_eq!(
+        actions[1].action_type,
+        SlackMessageAttachmentActionType::Other("datepicker".into())
+    );
+
+    let serialized = serde_json::to_value(&attachment).unwrap();
+    assert_eq!(serialized["actions"][0]["type"], "button");
+    assert_eq!(serialized["actions"][1]["type"], "datepicker");
+}
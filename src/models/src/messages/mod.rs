@@ -2,11 +2,14 @@ use crate::blocks::*;
 use crate::common::*;
 use crate::events::SlackMessageEventType;
 use rsb_derive::Builder;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
+mod attachments;
 mod templates;
 
+pub use attachments::*;
 pub use templates::*;
 
 #[skip_serializing_none]
@@ -23,6 +26,109 @@ pub struct SlackMessageOrigin {
 pub struct SlackMessageContent {
     pub text: Option<String>,
     pub blocks: Option<Vec<SlackBlock>>,
+    pub attachments: Option<Vec<SlackMessageAttachment>>,
+    pub metadata: Option<SlackMessageMetadata>,
+}
+
+impl SlackMessageContent {
+    ///
+    /// The message without formatting: its mrkdwn `text`, or texts of its blocks if there is no `text`
+    ///
+    pub fn to_plain_text(&self) -> String {
+        self.to_plain_text_with(&SlackMrkdwnRenderer::new())
+    }
+
+    ///
+    /// The message without formatting, with names of mentions resolved by the renderer
+    ///
+    pub fn to_plain_text_with(&self, renderer: &SlackMrkdwnRenderer) -> String {
+        match &self.text {
+            Some(text) if !text.trim().is_empty() => {
+                renderer.to_plain_text(&SlackMrkdwnDocument::parse(text))
+            }
+            _ => self
+                .blocks
+                .iter()
+                .flatten()
+                .map(|block| block_plain_text(block, renderer))
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<String>>()
+                .join("\n"),
+        }
+    }
+}
+
+fn block_plain_text(block: &SlackBlock, renderer: &SlackMrkdwnRenderer) -> String {
+    let text_plain_text = |text: &SlackBlockText| match text {
+        SlackBlockText::Plain(plain_text) => plain_text.text.clone(),
+        SlackBlockText::MarkDown(mrkdwn) => {
+            renderer.to_plain_text(&SlackMrkdwnDocument::parse(&mrkdwn.text))
+        }
+    };
+
+    match block {
+        SlackBlock::Section(section) => section
+            .text
+            .iter()
+            .chain(section.fields.iter().flatten())
+            .map(text_plain_text)
+            .collect::<Vec<String>>()
+            .join("\n"),
+        SlackBlock::Header(header) => header.text.text().into(),
+        SlackBlock::Context(context) => context
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                SlackContextBlockElement::Plain(plain_text) => Some(plain_text.text.clone()),
+                SlackContextBlockElement::MarkDown(mrkdwn) => {
+                    Some(text_plain_text(&SlackBlockText::MarkDown(mrkdwn.clone())))
+                }
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+            .join(" "),
+        SlackBlock::RichText(rich_text) => rich_text.to_plain_text(),
+        _ => "".into(),
+    }
+}
+
+///
+/// Message metadata with a typed payload.
+/// Messages carry metadata with JSON payloads, use `to_json_metadata`/`parse_payload` to convert them.
+///
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SlackMessageMetadata<T = serde_json::Value> {
+    pub event_type: String,
+    pub event_payload: T,
+}
+
+impl<T> SlackMessageMetadata<T> {
+    pub fn new(event_type: String, event_payload: T) -> Self {
+        Self {
+            event_type,
+            event_payload,
+        }
+    }
+}
+
+impl<T: Serialize> SlackMessageMetadata<T> {
+    pub fn to_json_metadata(&self) -> serde_json::Result<SlackMessageMetadata> {
+        Ok(SlackMessageMetadata::new(
+            self.event_type.clone(),
+            serde_json::to_value(&self.event_payload)?,
+        ))
+    }
+}
+
+impl SlackMessageMetadata {
+    pub fn parse_payload<T: DeserializeOwned>(
+        &self,
+    ) -> serde_json::Result<SlackMessageMetadata<T>> {
+        Ok(SlackMessageMetadata::new(
+            self.event_type.clone(),
+            serde_json::from_value(self.event_payload.clone())?,
+        ))
+    }
 }
 
 #[skip_serializing_none]
@@ -52,6 +158,10 @@ pub struct SlackHistoryMessage {
     #[serde(flatten)]
     pub sender: SlackMessageSender,
     pub subtype: Option<SlackMessageEventType>,
+    #[cfg(feature = "lenient")]
+    #[serde(flatten)]
+    #[default = "SlackExtraFields::new()"]
+    pub extra: SlackExtraFields,
 }
 
 #[skip_serializing_none]
@@ -62,3 +172,47 @@ pub enum SlackMessageResponseType {
     #[serde(rename = "ephemeral")]
     Ephemeral,
 }
+
+#[test]
+fn check_message_metadata_payloads() {
+    #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+    struct TaskCreated {
+        id: String,
+        title: String,
+    }
+
+    let metadata = SlackMessageMetadata::new(
+        "task_created".into(),
+        TaskCreated {
+            id: "TK-1".into(),
+            title: "Write docs".into(),
+        },
+    );
+
+    let json_metadata = metadata.to_json_metadata().unwrap();
+    assert_eq!(
+        serde_json::to_value(&json_metadata).unwrap(),
+        serde_json::json!({
+            "event_type": "task_created",
+            "event_payload": { "id": "TK-1", "title": "Write docs" }
+        })
+    );
+    assert_eq!(
+        json_metadata.parse_payload::<TaskCreated>().unwrap(),
+        metadata
+    );
+
+    let content: SlackMessageContent = serde_json::from_value(serde_json::json!({
+        "text": "Task created",
+        "metadata": {
+            "event_type": "task_created",
+            "event_payload": { "id": "TK-1" }
+        }
+    }))
+    .unwrap();
+    assert!(content
+        .metadata
+        .unwrap()
+        .parse_payload::<TaskCreated>()
+        .is_err());
+}
@@ -1,6 +1,9 @@
 use crate::blocks::SlackBlock;
 use crate::SlackMessageContent;
 
+#[cfg(feature = "derive")]
+pub use slack_morphism_macros::{SlackBlocksTemplate, SlackMessageTemplate};
+
 pub trait SlackMessageTemplate {
     fn render_template(&self) -> SlackMessageContent;
 }
@@ -8,3 +11,142 @@ pub trait SlackMessageTemplate {
 pub trait SlackBlocksTemplate {
     fn render_template(&self) -> Vec<SlackBlock>;
 }
+
+#[cfg(feature = "derive")]
+#[test]
+fn check_derived_message_template() {
+    use crate::common::*;
+
+    #[derive(SlackMessageTemplate)]
+    #[slack(text = "New issue: {title}")]
+    struct IssueNotification {
+        #[slack(header)]
+        title: String,
+        #[slack(field = "Priority")]
+        priority: String,
+        #[slack(field = "Assignee", with = "SlackTextFormat::to_slack_format")]
+        assignee: Option<SlackUserId>,
+        #[slack(field = "Reviewer", with = "SlackTextFormat::to_slack_format")]
+        reviewer: Option<SlackUserId>,
+        #[slack(button(text = "Open", action_id = "open-issue"))]
+        issue_id: String,
+        #[slack(context)]
+        reporter: String,
+        #[allow(dead_code)]
+        internal_id: u64,
+    }
+
+    let content = IssueNotification {
+        title: "Broken build".into(),
+        priority: "High".into(),
+        assignee: Some("U1".into()),
+        reviewer: None,
+        issue_id: "ISSUE-1".into(),
+        reporter: "Reported by the CI".into(),
+        internal_id: 1,
+    }
+    .render_template();
+
+    assert_eq!(content.text.as_deref(), Some("New issue: Broken build"));
+    assert_eq!(
+        serde_json::to_value(&content.blocks).unwrap(),
+        serde_json::json!([
+            { "type": "header", "text": { "type": "plain_text", "text": "Broken build" } },
+            {
+                "type": "section",
+                "fields": [
+                    { "type": "mrkdwn", "text": "*Priority*\nHigh" },
+                    { "type": "mrkdwn", "text": "*Assignee*\n<@U1>" }
+                ]
+            },
+            {
+                "type": "actions",
+                "elements": [
+                    {
+                        "type": "button",
+                        "action_id": "open-issue",
+                        "text": { "type": "plain_text", "text": "Open" },
+                        "value": "ISSUE-1"
+                    }
+                ]
+            },
+            {
+                "type": "context",
+                "elements": [{ "type": "mrkdwn", "text": "Reported by the CI" }]
+            }
+        ])
+    );
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn check_derived_template_escaping() {
+    use crate::common::*;
+
+    #[derive(SlackBlocksTemplate)]
+    struct Alert {
+        #[slack(section)]
+        summary: String,
+        #[slack(field = "Condition")]
+        condition: String,
+        #[slack(field = "Owner", with = "SlackTextFormat::to_slack_format")]
+        owner: SlackUserId,
+        #[slack(context = "Source")]
+        source: String,
+    }
+
+    let blocks = Alert {
+        summary: "<!channel> the build is broken".into(),
+        condition: "a < b & c".into(),
+        owner: "U1".into(),
+        source: "<https://example.com|CI>".into(),
+    }
+    .render_template();
+
+    assert_eq!(
+        serde_json::to_value(&blocks).unwrap(),
+        serde_json::json!([
+            {
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": "&lt;!channel&gt; the build is broken" }
+            },
+            {
+                "type": "section",
+                "fields": [
+                    { "type": "mrkdwn", "text": "*Condition*\na &lt; b &amp; c" },
+                    { "type": "mrkdwn", "text": "*Owner*\n<@U1>" }
+                ]
+            },
+            {
+                "type": "context",
+                "elements": [
+                    { "type": "mrkdwn", "text": "Source: &lt;https://example.com|CI&gt;" }
+                ]
+            }
+        ])
+    );
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn check_derived_template_text_escaping() {
+    #[derive(SlackMessageTemplate)]
+    #[slack(text = "{{{title}}}: {details} ({progress:.1}%)")]
+    struct Alert {
+        title: String,
+        details: String,
+        progress: f64,
+    }
+
+    let content = Alert {
+        title: "R&D".into(),
+        details: "<!here> a < b".into(),
+        progress: 42.25,
+    }
+    .render_template();
+
+    assert_eq!(
+        content.text.as_deref(),
+        Some("{R&amp;D}: &lt;!here&gt; a &lt; b (42.2%)")
+    );
+}
@@ -0,0 +1,95 @@
+use std::fmt::{Display, Formatter};
+
+///
+/// Implements `Serialize` and `Deserialize` for tagged enums with an `Other(serde_json::Value)` fallback.
+/// The enum derives strict (de)serialization with `#[serde(tag = "...", remote = "Self")]`
+/// and `#[serde(skip)]` on the `Other` variant.
+///
+/// Only payloads with unknown (or absent) tags are deserialized as `Other`,
+/// payloads with known tags are deserialized strictly, so their errors are reported instead of being
+/// silently turned into `Other`. `Other` payloads are serialized back as is.
+///
+macro_rules! impl_slack_tagged_with_other {
+    ($enum_type:ident, $tag_name:literal) => {
+        impl serde::Serialize for $enum_type {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self {
+                    $enum_type::Other(value) => serde::Serialize::serialize(value, serializer),
+                    known => $enum_type::serialize(known, serializer),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $enum_type {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value: serde_json::Value = serde::Deserialize::deserialize(deserializer)?;
+                match $enum_type::deserialize(&value) {
+                    Ok(known) => Ok(known),
+                    Err(err) => {
+                        let unknown_tag =
+                            value
+                                .get($tag_name)
+                                .and_then(|tag| tag.as_str())
+                                .map_or(true, |tag| {
+                                    $enum_type::deserialize(
+                                        crate::tagged::SlackTagProbeError::probe($tag_name, tag),
+                                    )
+                                    .map_or_else(|probe_err| probe_err.unknown_tag, |_| false)
+                                });
+                        if unknown_tag {
+                            Ok($enum_type::Other(value))
+                        } else {
+                            Err(serde::de::Error::custom(err))
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+///
+/// Error of deserializing a payload with only a tag, which tells apart unknown tags
+/// from known tags of tagged enums
+///
+#[derive(Debug)]
+pub(crate) struct SlackTagProbeError {
+    pub unknown_tag: bool,
+}
+
+impl SlackTagProbeError {
+    pub fn probe<'a>(
+        tag_name: &'static str,
+        tag: &'a str,
+    ) -> serde::de::value::MapDeserializer<'a, std::iter::Once<(&'static str, &'a str)>, Self> {
+        serde::de::value::MapDeserializer::new(std::iter::once((tag_name, tag)))
+    }
+}
+
+impl Display for SlackTagProbeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Slack tag probe error. Unknown tag: {}",
+            self.unknown_tag
+        )
+    }
+}
+
+impl std::error::Error for SlackTagProbeError {}
+
+impl serde::de::Error for SlackTagProbeError {
+    fn custom<T: Display>(_msg: T) -> Self {
+        SlackTagProbeError { unknown_tag: false }
+    }
+
+    fn unknown_variant(_variant: &str, _expected: &'static [&'static str]) -> Self {
+        SlackTagProbeError { unknown_tag: true }
+    }
+}